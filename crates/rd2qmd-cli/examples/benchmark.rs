@@ -16,9 +16,12 @@ use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use clap::Parser;
+use rd2qmd_core::{
+    ChunkOptions, ItemsFormat, MathFormat, TableHeader, TabularFormat, Typography, WriterFormat,
+};
 use rd2qmd_package::{
     PackageConvertOptions, PackageUrlResolver, PackageUrlResolverOptions, RdPackage,
-    collect_external_packages, convert_package,
+    TopicIndexOptions, collect_external_packages, convert_package, generate_topic_index,
 };
 
 #[derive(Parser, Debug)]
@@ -46,14 +49,31 @@ fn main() -> Result<()> {
 
     // Load package
     println!("Loading .Rd files from {}...", args.man_dir.display());
+    let start = Instant::now();
     let package =
         RdPackage::from_directory(&args.man_dir, false).context("Failed to load Rd files")?;
+    let load_time = start.elapsed();
 
     println!("Found {} .Rd files", package.files().len());
     println!(
         "Built alias index with {} entries",
         package.alias_index().len()
     );
+    println!("Loaded in {:.2}s", load_time.as_secs_f64());
+
+    let start = Instant::now();
+    let index = generate_topic_index(
+        &package,
+        &TopicIndexOptions {
+            output_extension: "qmd".to_string(),
+            include_internal: false,
+        },
+    )?;
+    println!(
+        "Topic index of {} topics in {:.2}s",
+        index.topics.len(),
+        start.elapsed().as_secs_f64()
+    );
     println!();
 
     // Create temp output directory
@@ -77,6 +97,37 @@ fn main() -> Result<()> {
     }
     println!();
 
+    // Benchmark the whole directory conversion, as `rd2qmd man/ --topic-index`
+    println!("=== Load, convert and topic index ===");
+    println!();
+    println!("{:<45} {:>8}", "Configuration", "Time");
+    println!("{:<45} {:>8}", "-------------", "----");
+
+    for jobs in [1, 2, 4] {
+        let mut times = Vec::with_capacity(args.iterations);
+        for _ in 0..args.iterations {
+            let start = Instant::now();
+            let package = RdPackage::from_directory(&args.man_dir, false)
+                .context("Failed to load Rd files")?;
+            run_single_benchmark(&package, &output_dir, jobs, None)?;
+            generate_topic_index(
+                &package,
+                &TopicIndexOptions {
+                    output_extension: "qmd".to_string(),
+                    include_internal: false,
+                },
+            )?;
+            times.push(start.elapsed());
+        }
+        let avg = average_duration(&times);
+        println!(
+            "{:<45} {:>7.2}s",
+            format!("Jobs: {}", jobs),
+            avg.as_secs_f64()
+        );
+    }
+    println!();
+
     // Benchmark with external links if r-lib-path is provided
     if !args.r_lib_paths.is_empty() {
         // Collect external packages
@@ -194,9 +245,25 @@ fn run_single_benchmark(
         parallel_jobs: Some(jobs),
         unresolved_link_url: Some("https://rdrr.io/r/base/{topic}.html".to_string()),
         external_package_urls: external_urls.cloned(),
+        link_resolver: None,
+        link_titles: true,
         exec_dontrun: false,
-        exec_donttest: true,     // pkgdown-compatible default
+        exec_donttest: true, // pkgdown-compatible default
+        chunk_labels: true,
+        chunk_options: ChunkOptions::default(),
         include_internal: false, // skip internal topics by default
+        format: WriterFormat::Markdown,
+        callouts: true,
+        translate_html: false,
+        heading_ids: true,
+        math: MathFormat::Latex,
+        typography: Typography::Unicode,
+        tabular_format: TabularFormat::Auto,
+        table_header: TableHeader::FirstRow,
+        value_items: ItemsFormat::DefinitionList,
+        format_items: ItemsFormat::DefinitionList,
+        assets_dir: None,
+        home_page: false,
     };
 
     convert_package(package, &options)?;
@@ -5,6 +5,7 @@
 use anyhow::{Context, Result};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 /// Default configuration file name (following Quarto's `_quarto.yml` convention)
@@ -35,7 +36,7 @@ pub struct Config {
 #[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(default)]
 pub struct OutputConfig {
-    /// Output format: "qmd" (Quarto Markdown), "md" (standard Markdown), or "rmd" (R Markdown)
+    /// Output format: "qmd" (Quarto Markdown), "md" (standard Markdown), "rmd" (R Markdown), "html", "ipynb", "latex", "man", "mdast-json", "pandoc-json", "mdx", or "txt"
     #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<String>,
     /// Add YAML frontmatter with title (default: true)
@@ -47,10 +48,31 @@ pub struct OutputConfig {
     /// Table format for Arguments section: "grid" (Pandoc grid table) or "pipe" (default: "grid")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub arguments_table: Option<String>,
+    /// Equation rendering: "latex", "ascii" (the Rd ascii alternative) or "unicode" (default: "unicode" for man and txt, "latex" otherwise)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub math: Option<String>,
+    #[doc = r#"Quotes, dashes and ellipses in text, such as \sQuote{} and \dots: "ascii" (\enc{} uses its ASCII fallback), "unicode" or "html" (entities) (default: "unicode")"#]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typography: Option<String>,
+    #[doc = r#"Rendering of \value components: "deflist", "table" (in the arguments_table format) or "list" (default: "deflist")"#]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_items: Option<String>,
+    #[doc = r#"Rendering of \describe items in the Format section, such as dataset columns: "deflist", "table" or "list" (default: "deflist")"#]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format_items: Option<String>,
+    #[doc = r#"Table format for \tabular: "auto" (grid table only when cells contain lists or code), "pipe" or "grid" (default: "auto")"#]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tabular_table: Option<String>,
+    #[doc = r#"Header row for \tabular tables: "first-row", "empty" or "synthetic" ("Column 1", ...) (default: "first-row")"#]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tabular_header: Option<String>,
     /// Include topics with \keyword{internal} (default: false)
     /// By default, internal topics are skipped (matching pkgdown behavior).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub include_internal: Option<bool>,
+    /// Write a package home page (index.<ext>) from DESCRIPTION and README.md (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub home_page: Option<bool>,
 }
 
 impl OutputConfig {
@@ -59,7 +81,14 @@ impl OutputConfig {
             && self.frontmatter.is_none()
             && self.pagetitle.is_none()
             && self.arguments_table.is_none()
+            && self.math.is_none()
+            && self.typography.is_none()
+            && self.value_items.is_none()
+            && self.format_items.is_none()
+            && self.tabular_table.is_none()
+            && self.tabular_header.is_none()
             && self.include_internal.is_none()
+            && self.home_page.is_none()
     }
 }
 
@@ -76,6 +105,34 @@ pub struct CodeConfig {
     #[doc = r"Make \donttest{} example code executable (default: true)"]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exec_donttest: Option<bool>,
+    /// Label executable example chunks "ex-<topic>-1", "ex-<topic>-2", ... (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_labels: Option<bool>,
+    /// Chunk options for all executable example chunks, e.g. `error = true` as pkgdown uses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_options: Option<BTreeMap<String, ChunkOptionValue>>,
+    #[doc = r"Chunk options for single topics by \name{}, overriding chunk_options"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic_chunk_options: Option<BTreeMap<String, BTreeMap<String, ChunkOptionValue>>>,
+}
+
+/// Value of a chunk option
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ChunkOptionValue {
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl From<ChunkOptionValue> for rd2qmd_core::ChunkValue {
+    fn from(value: ChunkOptionValue) -> Self {
+        match value {
+            ChunkOptionValue::Bool(b) => rd2qmd_core::ChunkValue::Bool(b),
+            ChunkOptionValue::Number(n) => rd2qmd_core::ChunkValue::Number(n),
+            ChunkOptionValue::String(s) => rd2qmd_core::ChunkValue::String(s),
+        }
+    }
 }
 
 impl CodeConfig {
@@ -83,6 +140,9 @@ impl CodeConfig {
         self.quarto_code_blocks.is_none()
             && self.exec_dontrun.is_none()
             && self.exec_donttest.is_none()
+            && self.chunk_labels.is_none()
+            && self.chunk_options.is_none()
+            && self.topic_chunk_options.is_none()
     }
 }
 
@@ -94,11 +154,16 @@ pub struct LinksConfig {
     /// (default: "https://rdrr.io/r/base/{topic}.html")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unresolved_url: Option<String>,
+
+    /// Give links to topics of the package their title and the first sentence of
+    /// their description as link title (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub titles: Option<bool>,
 }
 
 impl LinksConfig {
     fn is_empty(&self) -> bool {
-        self.unresolved_url.is_none()
+        self.unresolved_url.is_none() && self.titles.is_none()
     }
 }
 
@@ -180,15 +245,26 @@ impl Config {
                 frontmatter: Some(true),
                 pagetitle: Some(true),
                 arguments_table: Some("grid".to_string()),
+                math: None, // depends on format
+                typography: Some("unicode".to_string()),
+                value_items: Some("deflist".to_string()),
+                format_items: Some("deflist".to_string()),
+                tabular_table: Some("auto".to_string()),
+                tabular_header: Some("first-row".to_string()),
                 include_internal: Some(false),
+                home_page: Some(false),
             },
             code: CodeConfig {
                 quarto_code_blocks: None, // auto-detect
                 exec_dontrun: Some(false),
                 exec_donttest: Some(true),
+                chunk_labels: Some(true),
+                chunk_options: None,
+                topic_chunk_options: None,
             },
             links: LinksConfig {
                 unresolved_url: Some("https://rdrr.io/r/base/{topic}.html".to_string()),
+                titles: Some(true),
             },
             external: ExternalConfig {
                 enabled: Some(true),
@@ -247,6 +323,34 @@ mod tests {
         assert_eq!(config.code.exec_donttest, Some(true));
     }
 
+    #[test]
+    fn test_parse_chunk_options() {
+        let config: Config = toml::from_str(
+            r#"
+            [code]
+            chunk_labels = false
+
+            [code.chunk_options]
+            error = true
+            fig-width = 7
+
+            [code.topic_chunk_options.plot_data]
+            fig-cap = "A plot"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.code.chunk_labels, Some(false));
+        let options = config.code.chunk_options.unwrap();
+        assert_eq!(options["error"], ChunkOptionValue::Bool(true));
+        assert_eq!(options["fig-width"], ChunkOptionValue::Number(7.0));
+        let topics = config.code.topic_chunk_options.unwrap();
+        assert_eq!(
+            topics["plot_data"]["fig-cap"],
+            ChunkOptionValue::String("A plot".to_string())
+        );
+    }
+
     #[test]
     fn test_parse_links_section() {
         let config: Config = toml::from_str(
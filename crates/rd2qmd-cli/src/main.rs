@@ -4,14 +4,21 @@ mod config;
 
 use anyhow::{Context, Result};
 use clap::{Args, Parser, Subcommand};
-use config::Config;
+use config::{ChunkOptionValue, Config};
+use std::collections::BTreeMap;
 use std::fs;
+use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
-use rd2qmd_core::{ArgumentsFormat, RdConverter};
+use rd2qmd_core::{
+    ArgumentsFormat, ChunkOptions, ChunkValue, Frontmatter, ItemsFormat, MathFormat, RdConverter,
+    TableHeader, TabularFormat, TextOptions, Typography, WriterFormat, WriterOptions,
+};
 use rd2qmd_package::{
     ExternalLinkOptions as PackageExternalLinkOptions, FallbackReason, FullConvertResult,
-    PackageConvertOptions, PackageConverter, RdPackage, TopicIndexOptions, generate_topic_index,
+    PackageConvertOptions, PackageConverter, PkgdownReference, RdPackage, ReferenceIndex,
+    TopicIndex, TopicIndexOptions, generate_topic_index, package_to_latex,
 };
 
 /// Options for external package link resolution
@@ -22,7 +29,7 @@ struct ExternalLinkOptions {
     fallback_url: Option<String>,
 }
 
-/// Output format for markdown conversion
+/// Output format for conversion
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
 enum OutputFormat {
     /// Quarto Markdown (.qmd) - uses {r} code blocks for examples
@@ -32,6 +39,212 @@ enum OutputFormat {
     Md,
     /// R Markdown (.Rmd) - uses {r} code blocks for examples
     Rmd,
+    /// HTML (.html) - Rd2HTML/pkgdown-style markup, no Pandoc required
+    Html,
+    /// Jupyter notebook (.ipynb) - examples become R kernel code cells
+    Ipynb,
+    /// LaTeX (.tex) - standalone document per topic, for PDF output without R
+    Latex,
+    /// man page (.3) - roff for `man ./topic.3`, no R required
+    Man,
+    /// mdast JSON (.json) - unist syntax tree for remark, Astro, Docusaurus, etc.
+    MdastJson,
+    /// Pandoc JSON (.json) - Pandoc's native AST, for `pandoc -f json` and Quarto
+    PandocJson,
+    /// MDX (.mdx) - Docusaurus-safe Markdown with admonitions and sidebar frontmatter
+    Mdx,
+    /// Plain text (.txt) - wrapped, like R's help pages in a terminal
+    Txt,
+}
+
+impl OutputFormat {
+    /// File extension for output files and internal links
+    fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Qmd => "qmd",
+            OutputFormat::Md => "md",
+            OutputFormat::Rmd => "Rmd",
+            OutputFormat::Html => "html",
+            OutputFormat::Ipynb => "ipynb",
+            OutputFormat::Latex => "tex",
+            OutputFormat::Man => "3",
+            OutputFormat::MdastJson | OutputFormat::PandocJson => "json",
+            OutputFormat::Mdx => "mdx",
+            OutputFormat::Txt => "txt",
+        }
+    }
+
+    /// Whether Note and warning-like sections become callouts
+    ///
+    /// Quarto renders them as callout blocks and MDX as admonitions; other
+    /// Markdown renderers would show the fenced div markers.
+    fn callouts(self) -> bool {
+        matches!(self, OutputFormat::Qmd | OutputFormat::Mdx)
+    }
+
+    /// Whether `\out{}` HTML is translated to mdast instead of kept as raw HTML
+    ///
+    /// Quarto, R Markdown, plain Markdown, HTML and the JSON ASTs pass raw HTML
+    /// through; the other targets would leak it into the output or fail to parse it.
+    fn translate_html(self) -> bool {
+        !matches!(
+            self,
+            OutputFormat::Qmd
+                | OutputFormat::Md
+                | OutputFormat::Rmd
+                | OutputFormat::Html
+                | OutputFormat::MdastJson
+                | OutputFormat::PandocJson
+        )
+    }
+
+    /// Whether headings and arguments get pkgdown-compatible anchor ids
+    ///
+    /// Pandoc-based formats read `{#id}` attributes and Docusaurus reads heading ids;
+    /// other Markdown renderers would show them as text.
+    fn heading_ids(self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Qmd
+                | OutputFormat::Rmd
+                | OutputFormat::Html
+                | OutputFormat::Mdx
+                | OutputFormat::MdastJson
+                | OutputFormat::PandocJson
+        )
+    }
+
+    /// Default equation rendering
+    ///
+    /// Terminals and man pages cannot render LaTeX, so they get Unicode text.
+    fn default_math(self) -> MathFormat {
+        match self {
+            OutputFormat::Man | OutputFormat::Txt => MathFormat::Unicode,
+            _ => MathFormat::Latex,
+        }
+    }
+
+    /// Writer used to render the converted document
+    fn writer_format(self) -> WriterFormat {
+        match self {
+            OutputFormat::Qmd => WriterFormat::Markdown,
+            OutputFormat::Md => WriterFormat::CommonMark,
+            OutputFormat::Rmd => WriterFormat::Rmd,
+            OutputFormat::Html => WriterFormat::Html,
+            OutputFormat::Ipynb => WriterFormat::Ipynb,
+            OutputFormat::Latex => WriterFormat::Latex,
+            OutputFormat::Man => WriterFormat::Man,
+            OutputFormat::MdastJson => WriterFormat::MdastJson,
+            OutputFormat::PandocJson => WriterFormat::PandocJson,
+            OutputFormat::Mdx => WriterFormat::Mdx,
+            OutputFormat::Txt => WriterFormat::Text(TextOptions::default()),
+        }
+    }
+}
+
+/// Rendering of \eqn and \deqn equations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum MathOption {
+    /// LaTeX math, for MathJax/KaTeX or LaTeX output
+    Latex,
+    /// The Rd ascii alternative when present, otherwise Unicode text
+    Ascii,
+    /// Unicode text converted from common LaTeX (Greek letters, sub/superscripts, \frac, ...)
+    Unicode,
+}
+
+impl From<MathOption> for MathFormat {
+    fn from(option: MathOption) -> Self {
+        match option {
+            MathOption::Latex => MathFormat::Latex,
+            MathOption::Ascii => MathFormat::Ascii,
+            MathOption::Unicode => MathFormat::Unicode,
+        }
+    }
+}
+
+/// Rendering of quotes, dashes and ellipses in text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum TypographyOption {
+    /// Straight quotes, `--` and `...`; \enc{} uses its ASCII fallback
+    Ascii,
+    /// Typographic Unicode quotes, dashes and ellipses
+    Unicode,
+    /// HTML entities (&lsquo;, &ndash;, ...)
+    Html,
+}
+
+impl From<TypographyOption> for Typography {
+    fn from(option: TypographyOption) -> Self {
+        match option {
+            TypographyOption::Ascii => Typography::Ascii,
+            TypographyOption::Unicode => Typography::Unicode,
+            TypographyOption::Html => Typography::Html,
+        }
+    }
+}
+
+/// Rendering of \value components and \format \describe items
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum ItemsOption {
+    /// Definition list
+    Deflist,
+    /// Two-column table, in the --arguments-table format
+    Table,
+    /// Bullet list with the term in bold
+    List,
+}
+
+impl From<ItemsOption> for ItemsFormat {
+    fn from(option: ItemsOption) -> Self {
+        match option {
+            ItemsOption::Deflist => ItemsFormat::DefinitionList,
+            ItemsOption::Table => ItemsFormat::Table,
+            ItemsOption::List => ItemsFormat::List,
+        }
+    }
+}
+
+/// Table format for \tabular
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum TabularTableFormat {
+    /// Pipe table, or a grid table when a cell contains lists or code
+    Auto,
+    /// Pipe table (lists in cells are flattened)
+    Pipe,
+    /// Pandoc grid table
+    Grid,
+}
+
+impl From<TabularTableFormat> for TabularFormat {
+    fn from(option: TabularTableFormat) -> Self {
+        match option {
+            TabularTableFormat::Auto => TabularFormat::Auto,
+            TabularTableFormat::Pipe => TabularFormat::PipeTable,
+            TabularTableFormat::Grid => TabularFormat::GridTable,
+        }
+    }
+}
+
+/// Header row for \tabular tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum TabularHeaderOption {
+    /// Use the first row as the header
+    FirstRow,
+    /// No header (empty header row in pipe tables)
+    Empty,
+    /// Numbered column headers ("Column 1", "Column 2", ...)
+    Synthetic,
+}
+
+impl From<TabularHeaderOption> for TableHeader {
+    fn from(option: TabularHeaderOption) -> Self {
+        match option {
+            TabularHeaderOption::FirstRow => TableHeader::FirstRow,
+            TabularHeaderOption::Empty => TableHeader::Empty,
+            TabularHeaderOption::Synthetic => TableHeader::Synthetic,
+        }
+    }
 }
 
 /// Table format for the Arguments section
@@ -54,11 +267,21 @@ enum ArgumentsTableFormat {
   rd2qmd file.Rd -o output.qmd      # Convert to specific output file
   rd2qmd file.Rd -f md              # Convert to standard Markdown (.md)
   rd2qmd file.Rd -f rmd             # Convert to R Markdown (.Rmd)
+  rd2qmd file.Rd -f html            # Convert to HTML (.html)
+  rd2qmd file.Rd -f ipynb           # Convert to a Jupyter notebook (.ipynb)
+  rd2qmd file.Rd -f latex           # Convert to LaTeX (.tex)
+  rd2qmd file.Rd -f man             # Convert to a man page (.3)
+  rd2qmd file.Rd -f mdast-json      # Convert to mdast JSON syntax tree (.json)
+  rd2qmd file.Rd -f pandoc-json     # Convert to Pandoc JSON AST (.json)
+  rd2qmd file.Rd -f mdx             # Convert to MDX for Docusaurus (.mdx)
+  rd2qmd file.Rd -f txt             # Convert to plain text (.txt)
   rd2qmd man/ -o docs/              # Convert directory (with alias resolution)
   rd2qmd man/ -o docs/ -j4          # Use 4 parallel jobs
   rd2qmd man/ --topic-index i.json  # Convert and generate topic index
+  rd2qmd man/ --reference-manual refman.tex  # All topics in one LaTeX document
   rd2qmd index man/                 # Generate topic index JSON to stdout
-  rd2qmd index man/ | jq '.topics[] | select(.lifecycle)'")]
+  rd2qmd index man/ | jq '.topics[] | select(.lifecycle)'
+  rd2qmd show mean                  # Show the help page for a topic in man/")]
 struct Cli {
     /// Subcommand (optional)
     #[command(subcommand)]
@@ -71,7 +294,7 @@ struct Cli {
     #[arg(short, long)]
     output: Option<PathBuf>,
 
-    /// Output format: qmd (Quarto) or md (standard Markdown)
+    /// Output format: qmd (Quarto), md (standard Markdown), rmd (R Markdown), html, ipynb, latex, man, mdast-json, pandoc-json, mdx, or txt
     #[arg(short, long, value_enum, default_value_t = OutputFormat::Qmd)]
     format: OutputFormat,
 
@@ -112,6 +335,11 @@ struct Cli {
     #[arg(long, conflicts_with = "unresolved_link_url")]
     no_unresolved_link_url: bool,
 
+    /// Don't give links to topics of the package a title (by default the topic
+    /// title and first description sentence, shown as a tooltip)
+    #[arg(long)]
+    no_link_titles: bool,
+
     /// R library path to search for external packages (can be specified multiple times)
     #[arg(long = "r-lib-path", value_name = "PATH")]
     r_lib_paths: Vec<PathBuf>,
@@ -149,6 +377,15 @@ struct Cli {
     #[arg(long)]
     no_exec_donttest: bool,
 
+    /// Don't label executable example chunks (by default they get `#| label: ex-<topic>-1`, ...)
+    #[arg(long)]
+    no_chunk_labels: bool,
+
+    /// Chunk option for executable example chunks, e.g. `error=true` (can be repeated)
+    /// Overrides `chunk_options` of the same name in the config file.
+    #[arg(long = "chunk-option", value_name = "NAME=VALUE", value_parser = parse_chunk_option)]
+    chunk_options: Vec<(String, ChunkValue)>,
+
     /// Include topics with \keyword{internal} in the output
     /// By default, internal topics are skipped (matching pkgdown behavior).
     #[arg(long)]
@@ -159,11 +396,62 @@ struct Cli {
     #[arg(long, value_enum, default_value_t = ArgumentsTableFormat::Grid)]
     arguments_table: ArgumentsTableFormat,
 
+    /// Equation rendering: latex, ascii (the Rd ascii alternative) or unicode
+    /// (default: unicode for man and txt, latex otherwise)
+    #[arg(long, value_enum)]
+    math: Option<MathOption>,
+
+    /// Quotes, dashes and ellipses in text, such as \sQuote{} and \dots: ascii, unicode or html
+    /// (default: unicode)
+    #[arg(long, value_enum)]
+    typography: Option<TypographyOption>,
+
+    /// Rendering of \value components: deflist, table or list (default: deflist)
+    #[arg(long, value_enum)]
+    value_items: Option<ItemsOption>,
+
+    /// Rendering of \describe items in the Format section, such as dataset columns:
+    /// deflist, table or list (default: deflist)
+    #[arg(long, value_enum)]
+    format_items: Option<ItemsOption>,
+
+    /// Table format for \tabular: auto (grid table only when cells contain lists or code),
+    /// pipe or grid (default: auto)
+    #[arg(long, value_enum)]
+    tabular_table: Option<TabularTableFormat>,
+
+    /// Header row for \tabular tables, which have none in Rd: first-row, empty or synthetic
+    /// (default: first-row)
+    #[arg(long, value_enum)]
+    tabular_header: Option<TabularHeaderOption>,
+
     /// Generate topic index JSON file (directory mode only)
     /// Contains topic names, files, titles, aliases, and lifecycle stages
     #[arg(long, value_name = "FILE")]
     topic_index: Option<PathBuf>,
 
+    /// Generate a Docusaurus sidebar category JSON file (directory mode only)
+    /// Doc ids are relative to the file's directory, so place it in the docs root
+    /// and `require()` it from sidebars.js.
+    #[arg(long, value_name = "FILE")]
+    sidebar: Option<PathBuf>,
+
+    /// Generate a reference index page grouped by the `reference` sections of the
+    /// package's _pkgdown.yml (directory mode only)
+    /// Topics that no section selects are reported, as pkgdown does.
+    #[arg(long, value_name = "FILE")]
+    reference_index: Option<PathBuf>,
+
+    /// Write a package home page, index.<ext>, from DESCRIPTION and README.md of the
+    /// package directory (directory mode only)
+    #[arg(long)]
+    home_page: bool,
+
+    /// Write all topics into a single LaTeX reference manual (directory mode only)
+    /// Links between topics become hyperref links. No other files are written.
+    #[arg(long, value_name = "FILE")]
+    reference_manual: Option<PathBuf>,
+
     /// Path to configuration file (default: _rd2qmd.toml in current directory)
     #[arg(long, value_name = "FILE")]
     config: Option<PathBuf>,
@@ -188,6 +476,13 @@ enum Commands {
     /// Creates a new configuration file with all options commented out.
     /// Includes schema directive for editor support (tombi, taplo, etc.)
     Init(InitArgs),
+
+    /// Show the help page for a topic in the terminal
+    ///
+    /// Looks the topic up through the alias index of the Rd directory,
+    /// renders it as wrapped text and pages it through $PAGER (default: less).
+    /// Styling and hyperlinks are used when writing to a terminal.
+    Show(ShowArgs),
 }
 
 /// Arguments for the index subcommand
@@ -210,6 +505,45 @@ struct IndexArgs {
     include_internal: bool,
 }
 
+/// Arguments for the show subcommand
+#[derive(Args, Debug)]
+struct ShowArgs {
+    /// Topic name or alias to show
+    topic: String,
+
+    /// Directory containing Rd files
+    #[arg(short, long, default_value = "man")]
+    dir: PathBuf,
+
+    /// Process directories recursively
+    #[arg(short, long)]
+    recursive: bool,
+
+    /// Line width for wrapping (default: terminal width from $COLUMNS, or 80)
+    #[arg(short, long)]
+    width: Option<usize>,
+
+    /// When to use ANSI styling and hyperlinks
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Print to stdout instead of using a pager
+    #[arg(long)]
+    no_pager: bool,
+}
+
+/// When to style terminal output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+enum ColorChoice {
+    /// Style output when stdout is a terminal and NO_COLOR is not set
+    #[default]
+    Auto,
+    /// Always style output
+    Always,
+    /// Never style output
+    Never,
+}
+
 /// Arguments for the init subcommand
 #[derive(Args, Debug)]
 struct InitArgs {
@@ -234,6 +568,7 @@ fn main() -> Result<()> {
         return match subcommand {
             Commands::Index(args) => run_index_command(&args),
             Commands::Init(args) => run_init_command(&args),
+            Commands::Show(args) => run_show_command(&args),
         };
     }
 
@@ -246,6 +581,7 @@ fn main() -> Result<()> {
     let use_frontmatter = merge_frontmatter(&cli, &config);
     let use_pagetitle = merge_pagetitle(&cli, &config);
     let unresolved_link_url = merge_unresolved_link_url(&cli, &config);
+    let link_titles = !cli.no_link_titles && config.links.titles.unwrap_or(true);
 
     // Regular conversion mode - input is required
     let input = match &cli.input {
@@ -256,17 +592,16 @@ fn main() -> Result<()> {
     };
 
     // Determine output extension and quarto_code_blocks based on format
-    let output_extension = match format {
-        OutputFormat::Qmd => "qmd",
-        OutputFormat::Md => "md",
-        OutputFormat::Rmd => "Rmd",
-    };
+    let output_extension = format.extension();
 
     // quarto_code_blocks: CLI > Config > auto (based on format)
     let quarto_code_blocks = cli
         .quarto_code_blocks
         .or(config.code.quarto_code_blocks)
-        .unwrap_or(matches!(format, OutputFormat::Qmd | OutputFormat::Rmd));
+        .unwrap_or(matches!(
+            format,
+            OutputFormat::Qmd | OutputFormat::Rmd | OutputFormat::Ipynb
+        ));
 
     // exec_dontrun: CLI > Config > false
     let exec_dontrun = if cli.exec_dontrun {
@@ -282,9 +617,24 @@ fn main() -> Result<()> {
         config.code.exec_donttest.unwrap_or(true)
     };
 
+    // Example chunk labels and options: CLI > Config > labels, no options
+    let (chunk_labels, chunk_options) = merge_chunk_options(&cli, &config);
+
     // Convert arguments table format: CLI > Config > Grid
     let arguments_format = merge_arguments_format(&cli, &config);
 
+    // Equation rendering: CLI > Config > format default
+    let math = merge_math(&cli, &config, format);
+
+    // Quotes, dashes and ellipses: CLI > Config > Unicode
+    let typography = merge_typography(&cli, &config);
+
+    // \tabular rendering: CLI > Config > auto format, first row as header
+    let (tabular_format, table_header) = merge_tabular(&cli, &config);
+
+    // Value and Format item lists: CLI > Config > definition list
+    let (value_items, format_items) = merge_items(&cli, &config);
+
     // include_internal: CLI > Config > false (skip internal by default)
     let include_internal = if cli.include_internal {
         true
@@ -292,19 +642,70 @@ fn main() -> Result<()> {
         config.output.include_internal.unwrap_or(false)
     };
 
+    // home_page: CLI > Config > false
+    let home_page = cli.home_page || config.output.home_page.unwrap_or(false);
+
+    if let Some(manual_path) = cli.reference_manual.as_deref() {
+        if !input.is_dir() {
+            anyhow::bail!("--reference-manual requires a directory of Rd files");
+        }
+        // The manual is always LaTeX, whatever the output format
+        let manual_format = OutputFormat::Latex;
+        let options = PackageConvertOptions {
+            parallel_jobs: cli.jobs,
+            unresolved_link_url,
+            link_titles,
+            exec_dontrun,
+            exec_donttest,
+            chunk_labels,
+            chunk_options,
+            include_internal,
+            callouts: manual_format.callouts(),
+            translate_html: manual_format.translate_html(),
+            heading_ids: manual_format.heading_ids(),
+            math: merge_math(&cli, &config, manual_format),
+            typography,
+            tabular_format,
+            table_header,
+            value_items,
+            format_items,
+            ..Default::default()
+        };
+        return write_reference_manual(
+            &input,
+            manual_path,
+            cli.recursive,
+            &options,
+            cli.verbose,
+            cli.quiet,
+        );
+    }
+
     if input.is_file() {
         // Single file conversion (no alias resolution)
         convert_single_file(
             &input,
             cli.output.as_deref(),
             output_extension,
+            format.writer_format(),
             use_frontmatter,
             use_pagetitle,
             quarto_code_blocks,
             unresolved_link_url.as_deref(),
             exec_dontrun,
             exec_donttest,
+            chunk_labels,
+            &chunk_options,
             arguments_format,
+            math,
+            typography,
+            tabular_format,
+            table_header,
+            value_items,
+            format_items,
+            format.callouts(),
+            format.translate_html(),
+            format.heading_ids(),
             cli.verbose,
             cli.quiet,
         )?;
@@ -317,16 +718,32 @@ fn main() -> Result<()> {
             &input,
             cli.output.as_deref(),
             output_extension,
+            format.writer_format(),
             cli.recursive,
             use_frontmatter,
             use_pagetitle,
             quarto_code_blocks,
             unresolved_link_url,
+            link_titles,
             external_link_options,
             exec_dontrun,
             exec_donttest,
+            chunk_labels,
+            &chunk_options,
             include_internal,
+            home_page,
+            math,
+            typography,
+            tabular_format,
+            table_header,
+            value_items,
+            format_items,
+            format.callouts(),
+            format.translate_html(),
+            format.heading_ids(),
             cli.topic_index.as_deref(),
+            cli.sidebar.as_deref(),
+            cli.reference_index.as_deref(),
             cli.verbose,
             cli.quiet,
             cli.jobs,
@@ -344,13 +761,25 @@ fn convert_single_file(
     input: &Path,
     output: Option<&Path>,
     output_extension: &str,
+    writer_format: WriterFormat,
     use_frontmatter: bool,
     use_pagetitle: bool,
     quarto_code_blocks: bool,
     unresolved_link_url: Option<&str>,
     exec_dontrun: bool,
     exec_donttest: bool,
+    chunk_labels: bool,
+    chunk_options: &ChunkOptions,
     arguments_format: ArgumentsFormat,
+    math: MathFormat,
+    typography: Typography,
+    tabular_format: TabularFormat,
+    table_header: TableHeader,
+    value_items: ItemsFormat,
+    format_items: ItemsFormat,
+    callouts: bool,
+    translate_html: bool,
+    heading_ids: bool,
     verbose: bool,
     quiet: bool,
 ) -> Result<()> {
@@ -378,16 +807,38 @@ fn convert_single_file(
         .quarto_code_blocks(quarto_code_blocks)
         .exec_dontrun(exec_dontrun)
         .exec_donttest(exec_donttest)
-        .arguments_format(arguments_format);
+        .chunk_labels(chunk_labels)
+        .chunk_options(chunk_options.clone())
+        .arguments_format(arguments_format)
+        .math(math)
+        .typography(typography)
+        .tabular_format(tabular_format)
+        .table_header(table_header)
+        .value_items(value_items)
+        .format_items(format_items)
+        .callouts(callouts)
+        .translate_html(translate_html)
+        .heading_ids(heading_ids)
+        .format(writer_format);
 
     if let Some(url) = unresolved_link_url {
         converter = converter.unresolved_link_url(url);
     }
+    // The MDX id and slug are the file basename, as in directory conversion
+    if let Some(stem) = input.file_stem().and_then(|s| s.to_str()) {
+        converter = converter.document_id(stem);
+    }
 
-    let qmd = converter
-        .convert()
+    let (qmd, diagnostics) = converter
+        .convert_with_diagnostics()
         .map_err(|e| anyhow::anyhow!("Parse error: {}", e))?;
 
+    if !quiet {
+        for diagnostic in &diagnostics {
+            eprintln!("Warning: {}: {}", input.display(), diagnostic);
+        }
+    }
+
     if let Some(parent) = output_path.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
@@ -409,16 +860,32 @@ fn convert_directory(
     input: &Path,
     output: Option<&Path>,
     output_extension: &str,
+    writer_format: WriterFormat,
     recursive: bool,
     use_frontmatter: bool,
     use_pagetitle: bool,
     quarto_code_blocks: bool,
     unresolved_link_url: Option<String>,
+    link_titles: bool,
     external_link_options: Option<ExternalLinkOptions>,
     exec_dontrun: bool,
     exec_donttest: bool,
+    chunk_labels: bool,
+    chunk_options: &ChunkOptions,
     include_internal: bool,
+    home_page: bool,
+    math: MathFormat,
+    typography: Typography,
+    tabular_format: TabularFormat,
+    table_header: TableHeader,
+    value_items: ItemsFormat,
+    format_items: ItemsFormat,
+    callouts: bool,
+    translate_html: bool,
+    heading_ids: bool,
     topic_index_path: Option<&Path>,
+    sidebar_path: Option<&Path>,
+    reference_index_path: Option<&Path>,
     verbose: bool,
     quiet: bool,
     jobs: Option<usize>,
@@ -460,9 +927,25 @@ fn convert_directory(
         parallel_jobs: jobs,
         unresolved_link_url,
         external_package_urls: None, // Will be set by convert_package_with_external_links
+        link_resolver: None,
+        link_titles,
         exec_dontrun,
         exec_donttest,
+        chunk_labels,
+        chunk_options: chunk_options.clone(),
         include_internal,
+        format: writer_format.clone(),
+        callouts,
+        translate_html,
+        heading_ids,
+        math,
+        typography,
+        tabular_format,
+        table_header,
+        value_items,
+        format_items,
+        assets_dir: Some(PathBuf::from("assets")),
+        home_page,
     };
 
     // Convert external link options
@@ -502,11 +985,18 @@ fn convert_directory(
 
     // Print output files
     if !quiet {
-        for path in &result.output_files {
+        for path in result.output_files.iter().chain(&result.home_page) {
             println!("{}", path.display());
         }
     }
 
+    // Report problems in the Rd source
+    if !quiet {
+        for (file, warning) in &result.warnings {
+            eprintln!("Warning: {}: {}", file.display(), warning);
+        }
+    }
+
     // Report errors
     for (file, error) in &result.failed_files {
         eprintln!("Error converting {}: {}", file.display(), error);
@@ -519,6 +1009,12 @@ fn convert_directory(
         }
     }
 
+    if verbose {
+        for path in &result.asset_files {
+            eprintln!("Copied figure: {}", path.display());
+        }
+    }
+
     if !quiet {
         let mut summary = format!(
             "Converted {} files, {} failed",
@@ -539,18 +1035,21 @@ fn convert_directory(
     }
 
     // Generate topic index if requested
-    if let Some(index_path) = topic_index_path {
-        if verbose {
-            eprintln!("Generating topic index...");
-        }
+    if topic_index_path.is_none() && sidebar_path.is_none() && reference_index_path.is_none() {
+        return Ok(());
+    }
+    if verbose {
+        eprintln!("Generating topic index...");
+    }
 
-        let index_options = TopicIndexOptions {
-            output_extension: output_extension.to_string(),
-            include_internal,
-        };
-        let index = generate_topic_index(&package, &index_options)
-            .with_context(|| "Failed to generate topic index")?;
+    let index_options = TopicIndexOptions {
+        output_extension: output_extension.to_string(),
+        include_internal,
+    };
+    let index = generate_topic_index(&package, &index_options)
+        .with_context(|| "Failed to generate topic index")?;
 
+    if let Some(index_path) = topic_index_path {
         let json = index
             .to_json()
             .with_context(|| "Failed to serialize topic index")?;
@@ -563,6 +1062,178 @@ fn convert_directory(
         }
     }
 
+    if let Some(sidebar_path) = sidebar_path {
+        let id_prefix = sidebar_id_prefix(sidebar_path, output.unwrap_or(input));
+        let json = index
+            .to_sidebar_category("Reference", &id_prefix)
+            .with_context(|| "Failed to serialize sidebar")?;
+
+        fs::write(sidebar_path, &json)
+            .with_context(|| format!("Failed to write sidebar: {}", sidebar_path.display()))?;
+
+        if !quiet {
+            eprintln!("Sidebar written to {}", sidebar_path.display());
+        }
+    }
+
+    if let Some(reference_path) = reference_index_path {
+        let link_prefix = sidebar_id_prefix(reference_path, output.unwrap_or(input));
+        let index_options = ReferenceIndexOptions {
+            link_prefix: &link_prefix,
+            format: &writer_format,
+            frontmatter: use_frontmatter,
+            heading_ids,
+        };
+        write_reference_index(&package, &index, reference_path, &index_options, quiet)?;
+    }
+
+    Ok(())
+}
+
+/// Options for writing the reference index page
+struct ReferenceIndexOptions<'a> {
+    /// Prefix of the links to topic files
+    link_prefix: &'a str,
+    format: &'a WriterFormat,
+    frontmatter: bool,
+    heading_ids: bool,
+}
+
+/// Write the reference index grouped by the sections of the package's
+/// _pkgdown.yml, reporting selectors that fail and topics that no section selects
+fn write_reference_index(
+    package: &RdPackage,
+    index: &TopicIndex,
+    path: &Path,
+    options: &ReferenceIndexOptions,
+    quiet: bool,
+) -> Result<()> {
+    // Without _pkgdown.yml, all topics form one section
+    let config_path = PkgdownReference::find(package.package_dir());
+    let reference = match &config_path {
+        Some(config_path) => PkgdownReference::from_file(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?,
+        None => PkgdownReference::default(),
+    };
+    let reference_index = ReferenceIndex::new(index, &reference);
+
+    if !quiet && let Some(config_path) = &config_path {
+        for problem in &reference_index.problems {
+            eprintln!("Warning: {}: {}", config_path.display(), problem);
+        }
+    }
+    if !quiet && !reference_index.missing_topics.is_empty() {
+        eprintln!(
+            "Warning: All topics must be included in the reference index\n  Missing topics: {}\n  Either add them to _pkgdown.yml or use @keywords internal",
+            reference_index.missing_topics.join(", ")
+        );
+    }
+
+    let writer_options = WriterOptions {
+        frontmatter: options.frontmatter.then(|| Frontmatter {
+            title: Some("Reference".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let root = reference_index.to_mdast(options.link_prefix, options.heading_ids);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let file =
+        fs::File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut out = std::io::BufWriter::new(file);
+    options
+        .format
+        .write_to(&root, &writer_options, &mut out)
+        .and_then(|_| out.flush())
+        .with_context(|| format!("Failed to write reference index: {}", path.display()))?;
+
+    if !quiet {
+        eprintln!("Reference index written to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Doc id prefix for sidebar items, and link prefix for the reference index: the
+/// output directory relative to the directory of the file (e.g. `reference/`), or
+/// none if it is not below it
+fn sidebar_id_prefix(sidebar_path: &Path, output_dir: &Path) -> String {
+    let sidebar_dir = sidebar_path.parent().unwrap_or(Path::new(""));
+    let Ok(relative) = output_dir.strip_prefix(sidebar_dir) else {
+        return String::new();
+    };
+    let prefix: Vec<&str> = relative
+        .components()
+        .filter_map(|c| match c {
+            std::path::Component::Normal(s) => s.to_str(),
+            _ => None,
+        })
+        .collect();
+    if prefix.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", prefix.join("/"))
+    }
+}
+
+/// Write all topics of a directory into one LaTeX reference manual
+fn write_reference_manual(
+    input: &Path,
+    output: &Path,
+    recursive: bool,
+    options: &PackageConvertOptions,
+    verbose: bool,
+    quiet: bool,
+) -> Result<()> {
+    let package = RdPackage::from_directory(input, recursive)
+        .with_context(|| format!("Failed to scan directory: {}", input.display()))?;
+
+    if package.files().is_empty() {
+        anyhow::bail!("No .Rd files found in {}", input.display());
+    }
+
+    // Title the manual after the package: the directory containing man/
+    let dir = input.canonicalize().unwrap_or_else(|_| input.to_path_buf());
+    let package_dir = match dir.file_name() {
+        Some(name) if name == "man" => dir.parent().unwrap_or(&dir),
+        _ => &dir,
+    };
+    let title = package_dir.file_name().and_then(|s| s.to_str());
+
+    let manual = package_to_latex(&package, options, title);
+
+    for (file, error) in &manual.failed_files {
+        eprintln!("Error converting {}: {}", file.display(), error);
+    }
+    if verbose {
+        for path in &manual.skipped_internal {
+            eprintln!("Skipped (internal): {}", path.display());
+        }
+    }
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    fs::write(output, &manual.document)
+        .with_context(|| format!("Failed to write: {}", output.display()))?;
+
+    if !quiet {
+        println!("{}", output.display());
+        eprintln!(
+            "Wrote {} topics to reference manual, {} failed",
+            manual.topic_count,
+            manual.failed_files.len()
+        );
+    }
+
+    if !manual.failed_files.is_empty() {
+        anyhow::bail!("{} files failed to convert", manual.failed_files.len());
+    }
+
     Ok(())
 }
 
@@ -622,11 +1293,7 @@ fn run_index_command(args: &IndexArgs) -> Result<()> {
         anyhow::bail!("Input path is not a directory: {}", args.input.display());
     }
 
-    let output_extension = match args.format {
-        OutputFormat::Qmd => "qmd",
-        OutputFormat::Md => "md",
-        OutputFormat::Rmd => "Rmd",
-    };
+    let output_extension = args.format.extension();
 
     let package = RdPackage::from_directory(&args.input, args.recursive)
         .with_context(|| format!("Failed to scan directory: {}", args.input.display()))?;
@@ -653,6 +1320,92 @@ fn run_index_command(args: &IndexArgs) -> Result<()> {
     Ok(())
 }
 
+/// Run the show subcommand: render a topic as text and page it
+fn run_show_command(args: &ShowArgs) -> Result<()> {
+    if !args.dir.is_dir() {
+        anyhow::bail!("Rd directory not found: {}", args.dir.display());
+    }
+
+    let package = RdPackage::from_directory(&args.dir, args.recursive)
+        .with_context(|| format!("Failed to scan directory: {}", args.dir.display()))?;
+
+    let basename = package.resolve_alias(&args.topic).with_context(|| {
+        format!(
+            "No documentation for '{}' in {}",
+            args.topic,
+            args.dir.display()
+        )
+    })?;
+
+    let path = package
+        .files()
+        .iter()
+        .find(|f| f.file_stem().and_then(|s| s.to_str()) == Some(basename))
+        .with_context(|| format!("Rd file not found for '{}'", basename))?;
+
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read: {}", path.display()))?;
+
+    let is_terminal = std::io::stdout().is_terminal();
+    let styled = match args.color {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => is_terminal && std::env::var_os("NO_COLOR").is_none(),
+    };
+    let width = args
+        .width
+        .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+        .unwrap_or(80);
+
+    let text = RdConverter::new(&content)
+        .frontmatter(false)
+        .math(MathFormat::Unicode)
+        .format(WriterFormat::Text(TextOptions {
+            width,
+            ansi: styled,
+            hyperlinks: styled,
+        }))
+        .convert()
+        .map_err(|e| anyhow::anyhow!("Parse error: {}", e))?;
+
+    if args.no_pager || !is_terminal {
+        print!("{}", text);
+        return Ok(());
+    }
+    page(&text)
+}
+
+/// Write text through the user's pager, falling back to stdout
+fn page(text: &str) -> Result<()> {
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "less -FRX".to_string());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+
+    let child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(_) => {
+            print!("{}", text);
+            return Ok(());
+        }
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may exit before reading everything (e.g. the user pressed q)
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child
+        .wait()
+        .with_context(|| format!("Failed to run pager: {}", pager))?;
+    Ok(())
+}
+
 /// Run the init subcommand: generate configuration file
 fn run_init_command(args: &InitArgs) -> Result<()> {
     // Handle --schema flag: output JSON schema to stdout
@@ -716,6 +1469,14 @@ fn merge_format(cli: &Cli, config: &Config) -> OutputFormat {
             return match fmt.to_lowercase().as_str() {
                 "md" => OutputFormat::Md,
                 "rmd" => OutputFormat::Rmd,
+                "html" => OutputFormat::Html,
+                "ipynb" => OutputFormat::Ipynb,
+                "latex" => OutputFormat::Latex,
+                "man" => OutputFormat::Man,
+                "mdast-json" => OutputFormat::MdastJson,
+                "pandoc-json" => OutputFormat::PandocJson,
+                "mdx" => OutputFormat::Mdx,
+                "txt" => OutputFormat::Txt,
                 _ => OutputFormat::Qmd,
             };
         }
@@ -776,6 +1537,155 @@ fn merge_arguments_format(cli: &Cli, config: &Config) -> ArgumentsFormat {
     }
 }
 
+/// Merge equation rendering
+fn merge_math(cli: &Cli, config: &Config, format: OutputFormat) -> MathFormat {
+    if let Some(math) = cli.math {
+        return math.into();
+    }
+    match config
+        .output
+        .math
+        .as_deref()
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("latex") => MathFormat::Latex,
+        Some("ascii") => MathFormat::Ascii,
+        Some("unicode") => MathFormat::Unicode,
+        _ => format.default_math(),
+    }
+}
+
+/// Merge typography of quotes, dashes and ellipses
+fn merge_typography(cli: &Cli, config: &Config) -> Typography {
+    if let Some(typography) = cli.typography {
+        return typography.into();
+    }
+    match config
+        .output
+        .typography
+        .as_deref()
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("ascii") => Typography::Ascii,
+        Some("html") => Typography::Html,
+        _ => Typography::Unicode,
+    }
+}
+
+/// Merge Value and Format item list rendering
+fn merge_items(cli: &Cli, config: &Config) -> (ItemsFormat, ItemsFormat) {
+    let from_config = |value: Option<&str>| match value.map(str::to_lowercase).as_deref() {
+        Some("table") => ItemsFormat::Table,
+        Some("list") => ItemsFormat::List,
+        _ => ItemsFormat::DefinitionList,
+    };
+    let value_items = match cli.value_items {
+        Some(option) => option.into(),
+        None => from_config(config.output.value_items.as_deref()),
+    };
+    let format_items = match cli.format_items {
+        Some(option) => option.into(),
+        None => from_config(config.output.format_items.as_deref()),
+    };
+    (value_items, format_items)
+}
+
+/// Merge example chunk labels and chunk options
+///
+/// Options given on the command line replace config options of the same name.
+fn merge_chunk_options(cli: &Cli, config: &Config) -> (bool, ChunkOptions) {
+    let labels = !cli.no_chunk_labels && config.code.chunk_labels.unwrap_or(true);
+
+    let to_options = |options: &BTreeMap<String, ChunkOptionValue>| {
+        options
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone().into()))
+            .collect::<Vec<_>>()
+    };
+    let mut options = ChunkOptions {
+        global: config
+            .code
+            .chunk_options
+            .as_ref()
+            .map(to_options)
+            .unwrap_or_default(),
+        topics: config
+            .code
+            .topic_chunk_options
+            .iter()
+            .flatten()
+            .map(|(topic, options)| (topic.clone(), to_options(options)))
+            .collect(),
+    };
+    for (name, value) in &cli.chunk_options {
+        match options
+            .global
+            .iter_mut()
+            .find(|(existing, _)| existing == name)
+        {
+            Some(option) => option.1 = value.clone(),
+            None => options.global.push((name.clone(), value.clone())),
+        }
+    }
+    (labels, options)
+}
+
+/// Parse a `--chunk-option NAME=VALUE` argument
+fn parse_chunk_option(arg: &str) -> std::result::Result<(String, ChunkValue), String> {
+    let (name, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got `{}`", arg))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("missing chunk option name in `{}`", arg));
+    }
+    let value = value.trim();
+    let value = match value {
+        "true" | "TRUE" => ChunkValue::Bool(true),
+        "false" | "FALSE" => ChunkValue::Bool(false),
+        _ => match value.parse::<f64>() {
+            Ok(number) => ChunkValue::Number(number),
+            Err(_) => ChunkValue::String(value.trim_matches('"').to_string()),
+        },
+    };
+    Ok((name.to_string(), value))
+}
+
+/// Merge \tabular table format and header row
+fn merge_tabular(cli: &Cli, config: &Config) -> (TabularFormat, TableHeader) {
+    let format = match cli.tabular_table {
+        Some(option) => option.into(),
+        None => match config
+            .output
+            .tabular_table
+            .as_deref()
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("pipe") => TabularFormat::PipeTable,
+            Some("grid") => TabularFormat::GridTable,
+            _ => TabularFormat::Auto,
+        },
+    };
+    let header = match cli.tabular_header {
+        Some(option) => option.into(),
+        None => match config
+            .output
+            .tabular_header
+            .as_deref()
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("empty") => TableHeader::Empty,
+            Some("synthetic") => TableHeader::Synthetic,
+            _ => TableHeader::FirstRow,
+        },
+    };
+    (format, header)
+}
+
 /// Merge external link options
 fn merge_external_link_options(cli: &Cli, config: &Config) -> Option<ExternalLinkOptions> {
     // CLI --no-external-links explicitly disables
@@ -833,6 +1743,7 @@ mod tests {
             quarto_code_blocks: None,
             unresolved_link_url: "https://rdrr.io/r/base/{topic}.html".to_string(),
             no_unresolved_link_url: false,
+            no_link_titles: false,
             r_lib_paths: vec![],
             cache_dir: None,
             no_external_links: false,
@@ -841,9 +1752,21 @@ mod tests {
             quiet: false,
             exec_dontrun: false,
             no_exec_donttest: false,
+            no_chunk_labels: false,
+            chunk_options: vec![],
             include_internal: false,
             arguments_table: ArgumentsTableFormat::Grid,
+            math: None,
+            typography: None,
+            value_items: None,
+            format_items: None,
+            tabular_table: None,
+            tabular_header: None,
             topic_index: None,
+            sidebar: None,
+            reference_index: None,
+            home_page: false,
+            reference_manual: None,
             config: None,
             no_config: false,
         }
@@ -884,6 +1807,154 @@ mod tests {
         assert_eq!(merge_format(&cli, &config), OutputFormat::Rmd);
     }
 
+    #[test]
+    fn test_merge_format_config_latex() {
+        let cli = default_cli();
+        let config = Config {
+            output: config::OutputConfig {
+                format: Some("latex".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let format = merge_format(&cli, &config);
+        assert_eq!(format, OutputFormat::Latex);
+        assert_eq!(format.extension(), "tex");
+        assert_eq!(format.writer_format(), WriterFormat::Latex);
+    }
+
+    #[test]
+    fn test_merge_format_config_mdast_json() {
+        let cli = default_cli();
+        let config = Config {
+            output: config::OutputConfig {
+                format: Some("mdast-json".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let format = merge_format(&cli, &config);
+        assert_eq!(format, OutputFormat::MdastJson);
+        assert_eq!(format.extension(), "json");
+        assert_eq!(format.writer_format(), WriterFormat::MdastJson);
+    }
+
+    #[test]
+    fn test_merge_format_config_pandoc_json() {
+        let cli = default_cli();
+        let config = Config {
+            output: config::OutputConfig {
+                format: Some("pandoc-json".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let format = merge_format(&cli, &config);
+        assert_eq!(format, OutputFormat::PandocJson);
+        assert_eq!(format.extension(), "json");
+        assert_eq!(format.writer_format(), WriterFormat::PandocJson);
+    }
+
+    #[test]
+    fn test_merge_format_config_mdx() {
+        let cli = default_cli();
+        let config = Config {
+            output: config::OutputConfig {
+                format: Some("mdx".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let format = merge_format(&cli, &config);
+        assert_eq!(format, OutputFormat::Mdx);
+        assert_eq!(format.extension(), "mdx");
+        assert_eq!(format.writer_format(), WriterFormat::Mdx);
+    }
+
+    #[test]
+    fn test_md_format_is_commonmark() {
+        let format = OutputFormat::Md;
+        assert_eq!(format.writer_format(), WriterFormat::CommonMark);
+        assert!(!format.translate_html());
+        assert!(!format.callouts());
+        assert!(!format.heading_ids());
+    }
+
+    #[test]
+    fn test_sidebar_id_prefix() {
+        assert_eq!(
+            sidebar_id_prefix(Path::new("docs/sidebar.json"), Path::new("docs/reference")),
+            "reference/"
+        );
+        assert_eq!(
+            sidebar_id_prefix(Path::new("docs/sidebar.json"), Path::new("docs")),
+            ""
+        );
+        assert_eq!(
+            sidebar_id_prefix(Path::new("sidebar.json"), Path::new("api/reference")),
+            "api/reference/"
+        );
+        // Output directory outside the sidebar's directory
+        assert_eq!(
+            sidebar_id_prefix(
+                Path::new("website/sidebar.json"),
+                Path::new("out/reference")
+            ),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_merge_format_config_man() {
+        let cli = default_cli();
+        let config = Config {
+            output: config::OutputConfig {
+                format: Some("man".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let format = merge_format(&cli, &config);
+        assert_eq!(format, OutputFormat::Man);
+        assert_eq!(format.extension(), "3");
+        assert_eq!(format.writer_format(), WriterFormat::Man);
+    }
+
+    #[test]
+    fn test_merge_format_config_txt() {
+        let cli = default_cli();
+        let config = Config {
+            output: config::OutputConfig {
+                format: Some("txt".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let format = merge_format(&cli, &config);
+        assert_eq!(format, OutputFormat::Txt);
+        assert_eq!(format.extension(), "txt");
+        assert_eq!(
+            format.writer_format(),
+            WriterFormat::Text(TextOptions::default())
+        );
+    }
+
+    #[test]
+    fn test_merge_format_config_html() {
+        let cli = default_cli();
+        let config = Config {
+            output: config::OutputConfig {
+                format: Some("html".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let format = merge_format(&cli, &config);
+        assert_eq!(format, OutputFormat::Html);
+        assert_eq!(format.extension(), "html");
+        assert_eq!(format.writer_format(), WriterFormat::Html);
+    }
+
     #[test]
     fn test_merge_frontmatter_no_config() {
         let cli = default_cli();
@@ -968,6 +2039,7 @@ mod tests {
         let config = Config {
             links: config::LinksConfig {
                 unresolved_url: Some("https://example.com/{topic}".to_string()),
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -982,6 +2054,7 @@ mod tests {
         let config = Config {
             links: config::LinksConfig {
                 unresolved_url: Some("https://example.com/{topic}".to_string()),
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -1033,6 +2106,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_merge_items() {
+        let mut cli = default_cli();
+        let mut config = Config::default();
+        assert_eq!(
+            merge_items(&cli, &config),
+            (ItemsFormat::DefinitionList, ItemsFormat::DefinitionList)
+        );
+
+        config.output.value_items = Some("table".to_string());
+        config.output.format_items = Some("list".to_string());
+        assert_eq!(
+            merge_items(&cli, &config),
+            (ItemsFormat::Table, ItemsFormat::List)
+        );
+
+        cli.format_items = Some(ItemsOption::Deflist);
+        assert_eq!(
+            merge_items(&cli, &config),
+            (ItemsFormat::Table, ItemsFormat::DefinitionList)
+        );
+    }
+
+    #[test]
+    fn test_merge_tabular() {
+        let mut cli = default_cli();
+        let mut config = Config::default();
+        assert_eq!(
+            merge_tabular(&cli, &config),
+            (TabularFormat::Auto, TableHeader::FirstRow)
+        );
+
+        config.output.tabular_table = Some("grid".to_string());
+        config.output.tabular_header = Some("synthetic".to_string());
+        assert_eq!(
+            merge_tabular(&cli, &config),
+            (TabularFormat::GridTable, TableHeader::Synthetic)
+        );
+
+        cli.tabular_table = Some(TabularTableFormat::Pipe);
+        cli.tabular_header = Some(TabularHeaderOption::Empty);
+        assert_eq!(
+            merge_tabular(&cli, &config),
+            (TabularFormat::PipeTable, TableHeader::Empty)
+        );
+    }
+
+    #[test]
+    fn test_merge_math() {
+        let mut cli = default_cli();
+        let mut config = Config::default();
+        // Format defaults
+        assert_eq!(
+            merge_math(&cli, &config, OutputFormat::Qmd),
+            MathFormat::Latex
+        );
+        assert_eq!(
+            merge_math(&cli, &config, OutputFormat::Man),
+            MathFormat::Unicode
+        );
+        // Config overrides the format default
+        config.output.math = Some("ascii".to_string());
+        assert_eq!(
+            merge_math(&cli, &config, OutputFormat::Man),
+            MathFormat::Ascii
+        );
+        // CLI overrides config
+        cli.math = Some(MathOption::Latex);
+        assert_eq!(
+            merge_math(&cli, &config, OutputFormat::Man),
+            MathFormat::Latex
+        );
+    }
+
+    #[test]
+    fn test_merge_typography() {
+        let mut cli = default_cli();
+        let mut config = Config::default();
+        assert_eq!(merge_typography(&cli, &config), Typography::Unicode);
+        config.output.typography = Some("ASCII".to_string());
+        assert_eq!(merge_typography(&cli, &config), Typography::Ascii);
+        cli.typography = Some(TypographyOption::Html);
+        assert_eq!(merge_typography(&cli, &config), Typography::Html);
+    }
+
     #[test]
     fn test_merge_external_link_options_disabled_by_cli() {
         let mut cli = default_cli();
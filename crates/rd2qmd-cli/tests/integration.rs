@@ -61,6 +61,194 @@ fn test_simple_to_md() {
     insta::assert_snapshot!("simple_md", output);
 }
 
+#[test]
+fn test_simple_to_html() {
+    let output = convert_fixture("formatting", &["-f", "html", "--no-frontmatter"]);
+    insta::assert_snapshot!("formatting_html", output);
+}
+
+#[test]
+fn test_example_control_to_ipynb() {
+    let output = convert_fixture("example_control", &["-f", "ipynb"]);
+    insta::assert_snapshot!("example_control_ipynb", output);
+}
+
+#[test]
+fn test_simple_to_man() {
+    let output = convert_fixture("simple", &["-f", "man"]);
+    insta::assert_snapshot!("simple_man", output);
+}
+
+#[test]
+fn test_simple_to_latex() {
+    let output = convert_fixture("simple", &["-f", "latex"]);
+    insta::assert_snapshot!("simple_latex", output);
+}
+
+#[test]
+fn test_reference_manual() {
+    let output = std::env::temp_dir().join(format!(
+        "rd2qmd_test_refman_{}_{}.tex",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+
+    let status = Command::new(rd2qmd_binary())
+        .arg(fixtures_dir())
+        .arg("--reference-manual")
+        .arg(&output)
+        .arg("--quiet")
+        .status()
+        .expect("Failed to run rd2qmd --reference-manual");
+    assert!(status.success(), "rd2qmd --reference-manual failed");
+
+    let content = fs::read_to_string(&output).expect("Failed to read reference manual");
+    let _ = fs::remove_file(&output);
+
+    assert!(content.starts_with("\\documentclass{article}"));
+    assert!(content.contains("\\tableofcontents"));
+    for topic in [
+        "example_control",
+        "examplesif",
+        "formatting",
+        "simple",
+        "with_links",
+    ] {
+        assert!(
+            content.contains(&format!("\\label{{topic:{}}}", topic)),
+            "missing topic {}",
+            topic
+        );
+    }
+    // Links to topics in the package become hyperref references
+    assert!(content.contains("\\hyperref[topic:simple]"));
+    assert!(content.ends_with("\\end{document}\n"));
+}
+
+#[test]
+fn test_reference_manual_uses_conversion_options() {
+    let dir = std::env::temp_dir().join(format!(
+        "rd2qmd_test_refman_options_{}_{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    fs::create_dir_all(&dir).expect("Failed to create input dir");
+    fs::write(
+        dir.join("quotes.Rd"),
+        "\\name{quotes}\n\\alias{quotes}\n\\title{Quotes}\n\
+         \\description{A \\dQuote{quoted} word and \\eqn{\\alpha}.}\n\
+         \\value{\n\\item{a}{First.}\n}\n",
+    )
+    .expect("Failed to write Rd file");
+    let output = dir.join("manual.tex");
+
+    let status = Command::new(rd2qmd_binary())
+        .arg(&dir)
+        .arg("--reference-manual")
+        .arg(&output)
+        .args(["--typography", "ascii", "--math", "unicode"])
+        .args(["--value-items", "list", "--quiet"])
+        .status()
+        .expect("Failed to run rd2qmd --reference-manual");
+    assert!(status.success(), "rd2qmd --reference-manual failed");
+
+    let content = fs::read_to_string(&output).expect("Failed to read reference manual");
+    let _ = fs::remove_dir_all(&dir);
+
+    assert!(content.contains("A \"quoted\" word and \u{3b1}."));
+    assert!(content.contains("\\item \\textbf{\\texttt{a}}: First."));
+}
+
+#[test]
+fn test_formatting_to_mdx() {
+    let output = convert_fixture("formatting", &["-f", "mdx"]);
+    insta::assert_snapshot!("formatting_mdx", output);
+}
+
+#[test]
+fn test_mdx_sidebar() {
+    let docs = std::env::temp_dir().join(format!(
+        "rd2qmd_test_docs_{}_{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    let sidebar = docs.join("reference-sidebar.json");
+
+    let status = Command::new(rd2qmd_binary())
+        .arg(fixtures_dir())
+        .args(["-f", "mdx", "-o"])
+        .arg(docs.join("reference"))
+        .arg("--sidebar")
+        .arg(&sidebar)
+        .arg("--quiet")
+        .status()
+        .expect("Failed to run rd2qmd --sidebar");
+    assert!(status.success(), "rd2qmd --sidebar failed");
+
+    let content = fs::read_to_string(&sidebar).expect("Failed to read sidebar");
+    let simple = fs::read_to_string(docs.join("reference/simple.mdx")).expect("Failed to read mdx");
+    let _ = fs::remove_dir_all(&docs);
+
+    let parsed: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(parsed["type"], "category");
+    assert_eq!(parsed["label"], "Reference");
+    let items = parsed["items"].as_array().unwrap();
+    assert_eq!(items.len(), 5);
+    assert!(items.contains(&serde_json::json!({
+        "type": "doc",
+        "id": "reference/simple",
+        "label": "simple"
+    })));
+    // Doc ids in the sidebar match the frontmatter of the converted files
+    assert!(simple.contains("id: \"simple\"\nsidebar_label: \"simple\"\nslug: \"simple\"\n"));
+}
+
+#[test]
+fn test_reference_index() {
+    let docs = std::env::temp_dir().join(format!(
+        "rd2qmd_test_docs_{}_{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    let index_path = docs.join("reference-index.qmd");
+
+    let output = Command::new(rd2qmd_binary())
+        .arg(fixtures_dir())
+        .arg("-o")
+        .arg(docs.join("reference"))
+        .arg("--reference-index")
+        .arg(&index_path)
+        .output()
+        .expect("Failed to run rd2qmd --reference-index");
+    assert!(output.status.success(), "rd2qmd --reference-index failed");
+
+    let content = fs::read_to_string(&index_path).expect("Failed to read reference index");
+    let _ = fs::remove_dir_all(&docs);
+
+    // Sections of tests/fixtures/_pkgdown.yml, linking into the output directory
+    insta::assert_snapshot!("reference_index", content);
+
+    // with_links is in no section
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Missing topics: with_links\n"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_simple_to_pandoc_json() {
+    let output = convert_fixture("simple", &["-f", "pandoc-json"]);
+    insta::assert_snapshot!("simple_pandoc_json", output);
+}
+
+#[test]
+fn test_with_links_to_mdast_json() {
+    let output = convert_fixture("with_links", &["-f", "mdast-json"]);
+    insta::assert_snapshot!("with_links_mdast_json", output);
+}
+
 #[test]
 fn test_simple_no_frontmatter() {
     let output = convert_fixture("simple", &["--no-frontmatter"]);
@@ -91,6 +279,15 @@ fn test_example_control() {
     insta::assert_snapshot!("example_control_qmd", output);
 }
 
+#[test]
+fn test_example_control_to_rmd() {
+    let output = convert_fixture(
+        "example_control",
+        &["-f", "rmd", "--chunk-option", "error=true"],
+    );
+    insta::assert_snapshot!("example_control_rmd", output);
+}
+
 #[test]
 fn test_examplesif() {
     let output = convert_fixture("examplesif", &[]);
@@ -166,3 +363,38 @@ fn test_init_schema() {
     let schema = String::from_utf8(output.stdout).expect("Invalid UTF-8");
     insta::assert_snapshot!("init_schema_json", schema);
 }
+
+#[test]
+fn test_show_topic() {
+    let output = Command::new(rd2qmd_binary())
+        .arg("show")
+        .arg("formatting")
+        .arg("--dir")
+        .arg(fixtures_dir())
+        .arg("--color")
+        .arg("never")
+        .arg("--width")
+        .arg("60")
+        .output()
+        .expect("Failed to run rd2qmd show");
+
+    assert!(output.status.success(), "rd2qmd show failed");
+
+    let text = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    insta::assert_snapshot!("show_formatting", text);
+}
+
+#[test]
+fn test_show_unknown_topic() {
+    let output = Command::new(rd2qmd_binary())
+        .arg("show")
+        .arg("no_such_topic")
+        .arg("--dir")
+        .arg(fixtures_dir())
+        .output()
+        .expect("Failed to run rd2qmd show");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No documentation for 'no_such_topic'"));
+}
@@ -11,16 +11,22 @@
 //! arguments, value, details, examples, etc.) and inline markup (links, code,
 //! emphasis, math, lists, tables) into their mdast equivalents.
 
+mod link;
+mod math;
+
+pub use link::{DefaultLinkResolver, LinkResolver, ResolvedLink};
+
 #[cfg(feature = "roxygen")]
 use crate::roxygen_code_block::try_match_roxygen_code_block;
-use rd_parser::{
-    DescribeItem, FigureOptions, RdDocument, RdNode, RdSection, SectionTag, SpecialChar,
-};
+use math::latex_to_unicode;
+use rd_parser::{FigureOptions, RdDocument, RdNode, RdSection, SectionTag, SpecialChar};
 use rd2qmd_mdast::{
-    Align, DefinitionDescription, DefinitionList, DefinitionTerm, Html, Image, Node, Root, Table,
-    TableCell, TableRow,
+    Align, Attributes, DefinitionDescription, DefinitionList, DefinitionTerm, Html, Image, Node,
+    Root, Table, TableCell, TableRow, Typography, WriterOptions, mdast_to_qmd, translate_raw_html,
 };
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 use tabled::settings::Style;
 use tabled::settings::style::HorizontalLine;
 
@@ -34,25 +40,173 @@ pub enum ArgumentsFormat {
     GridTable,
 }
 
+/// Rendering of `\value` components and `\format` `\describe` items
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ItemsFormat {
+    /// Pandoc definition list (default)
+    #[default]
+    DefinitionList,
+    /// Two-column table, in the Arguments table format (grid or pipe)
+    Table,
+    /// Bullet list with the term in bold
+    List,
+}
+
+/// Table format for `\tabular`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TabularFormat {
+    /// Pipe table, or a grid table when a cell contains lists or code (default)
+    #[default]
+    Auto,
+    /// Pipe table - block content in cells is flattened with `<br>` separators
+    PipeTable,
+    /// Pandoc grid table - supports block elements (lists, code) in cells
+    GridTable,
+}
+
+/// Header row of `\tabular` tables
+///
+/// Rd tables have no header, but pipe tables require one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TableHeader {
+    /// Use the first row as the header (default)
+    #[default]
+    FirstRow,
+    /// No header: grid tables are headerless and pipe tables get an empty header row
+    Empty,
+    /// Numbered column headers ("Column 1", "Column 2", ...)
+    Synthetic,
+}
+
+/// Problem in the Rd source found during conversion
+///
+/// Output is still produced; diagnostics point at markup that may not render
+/// as the author intended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// How `\eqn` and `\deqn` equations are rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MathFormat {
+    /// LaTeX math nodes, for MathJax/KaTeX or LaTeX output (default)
+    #[default]
+    Latex,
+    /// The ascii argument of `\eqn{latex}{ascii}` as text, falling back to the
+    /// Unicode rendering when there is none
+    Ascii,
+    /// Unicode text converted from a common LaTeX subset (Greek letters,
+    /// sub/superscripts, `\frac`, `\sum`, operators), for output without math rendering
+    Unicode,
+}
+
+/// Rd output format that `\if` and `\ifelse` conditionals are evaluated against
+///
+/// Conditionals select content for one of R's own renderers (`Rd2HTML`,
+/// `Rd2latex`, `Rd2txt` and `Rd2ex`). Markdown is rendered to HTML in the end,
+/// so Markdown-based output uses the HTML branch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RdFormat {
+    #[default]
+    Html,
+    Latex,
+    Text,
+    Example,
+}
+
+impl RdFormat {
+    /// Format name as written in `\if{}`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RdFormat::Html => "html",
+            RdFormat::Latex => "latex",
+            RdFormat::Text => "text",
+            RdFormat::Example => "example",
+        }
+    }
+
+    /// Whether the format argument of a conditional selects this format
+    ///
+    /// As in "Writing R Extensions", the argument is a comma-separated list of
+    /// formats, `TRUE` and `FALSE` always and never match, and a format preceded
+    /// by `!` matches every other format.
+    pub fn matches(self, formats: &str) -> bool {
+        formats
+            .split(',')
+            .map(str::trim)
+            .filter(|format| !format.is_empty())
+            .any(|format| match format.strip_prefix('!') {
+                Some(negated) => !self.is(negated.trim()),
+                None => self.is(format),
+            })
+    }
+
+    fn is(self, format: &str) -> bool {
+        match format {
+            "TRUE" => true,
+            "FALSE" => false,
+            format => format == self.as_str(),
+        }
+    }
+}
+
+/// Value of a knitr/Quarto chunk option
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChunkValue {
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl ChunkValue {
+    /// Value as written in a `#| name: value` chunk option line (YAML)
+    pub fn to_yaml(&self) -> String {
+        match self {
+            ChunkValue::Bool(b) => b.to_string(),
+            ChunkValue::Number(n) => n.to_string(),
+            ChunkValue::String(s) => {
+                format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+        }
+    }
+}
+
+/// Chunk options for executable example chunks, such as `error: true` as pkgdown uses
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChunkOptions {
+    /// Options for every topic, in order
+    pub global: Vec<(String, ChunkValue)>,
+    /// Options for single topics (by `\name{}`), overriding global options of the same name
+    pub topics: HashMap<String, Vec<(String, ChunkValue)>>,
+}
+
+impl ChunkOptions {
+    /// Options that apply to a topic
+    pub fn for_topic(&self, topic: &str) -> Vec<(String, ChunkValue)> {
+        let mut options = self.global.clone();
+        for (name, value) in self.topics.get(topic).into_iter().flatten() {
+            match options.iter_mut().find(|(existing, _)| existing == name) {
+                Some(option) => option.1 = value.clone(),
+                None => options.push((name.clone(), value.clone())),
+            }
+        }
+        options
+    }
+}
+
 /// Options for Rd to mdast conversion
 #[derive(Debug, Clone)]
 pub struct RdToMdastOptions {
-    /// File extension for internal links (e.g., "qmd", "md", "html")
-    /// If None, internal links become inline code instead of hyperlinks
-    pub link_extension: Option<String>,
-    /// Alias map: maps alias names to Rd file basenames (without extension)
-    /// Used to resolve \link{alias} to the correct target file
-    pub alias_map: Option<HashMap<String, String>>,
-    /// URL pattern for unresolved links (fallback to base R documentation)
-    /// Use `{topic}` as placeholder for the topic name.
-    /// Example: `https://rdrr.io/r/base/{topic}.html`
-    /// If None, unresolved links become inline code instead of hyperlinks
-    pub unresolved_link_url: Option<String>,
-    /// External package URL map: package name -> reference documentation base URL
-    /// Used to resolve `\link[pkg]{topic}` to external package documentation
-    /// Example: `"dplyr" -> "https://dplyr.tidyverse.org/reference"`
-    /// The full URL is constructed as `{base_url}/{topic}.html`
-    pub external_package_urls: Option<HashMap<String, String>>,
+    /// Resolves `\link{}` and `\linkS4class{}` targets to URLs
+    /// The default resolver links nothing, so links become inline code.
+    pub link_resolver: Arc<dyn LinkResolver>,
     /// Make \dontrun{} example code executable (default: false, shown as non-executable)
     /// This matches pkgdown's semantics: \dontrun{} means "never run this code"
     pub exec_dontrun: bool,
@@ -64,23 +218,62 @@ pub struct RdToMdastOptions {
     /// When true, \dontshow{} content is output with #| include: false
     /// When false, \dontshow{} content is skipped entirely
     pub quarto_code_blocks: bool,
+    /// Label executable example chunks `ex-<topic>-1`, `ex-<topic>-2`, ... (`#| label:`,
+    /// default: true). Only applies with `quarto_code_blocks`.
+    pub chunk_labels: bool,
+    /// Options added to executable example chunks (`#| error: true`)
+    /// Only applies with `quarto_code_blocks`.
+    pub chunk_options: ChunkOptions,
     /// Format for the Arguments section
     /// GfmTable (default): GFM pipe table, limited to inline content
     /// GridTable: Pandoc grid table, supports block elements in cells
     pub arguments_format: ArgumentsFormat,
+    /// Rendering of `\value` components (`\item{name}{description}`)
+    pub value_items: ItemsFormat,
+    /// Rendering of `\describe` items in the Format section, such as dataset columns
+    pub format_items: ItemsFormat,
+    /// Table format for `\tabular`
+    pub tabular_format: TabularFormat,
+    /// Header row for `\tabular` tables
+    pub table_header: TableHeader,
+    /// Wrap the Note section and warning-like custom sections (Warning, Caution,
+    /// Important, Tip) in Quarto callout divs (`::: {.callout-note}`)
+    pub callouts: bool,
+    /// Give section headings and argument rows pkgdown-compatible anchors
+    /// (`## Arguments {#arguments}`, `[]{#arg-na-rm}`)
+    pub heading_ids: bool,
+    /// Translate `\out{}` HTML to mdast (divs, anchors, tables, ...) instead of
+    /// keeping it as raw HTML, for targets that cannot pass raw HTML through
+    pub translate_html: bool,
+    /// Rd format whose `\if`/`\ifelse` branches are rendered (default: HTML)
+    pub rd_format: RdFormat,
+    /// Rendering of `\eqn` and `\deqn` (default: LaTeX)
+    pub math: MathFormat,
+    /// Quotes, dashes and ellipses in text (default: Unicode)
+    /// With ASCII, `\sQuote`/`\dQuote` use straight quotes and `\enc` its fallback.
+    pub typography: Typography,
 }
 
 impl Default for RdToMdastOptions {
     fn default() -> Self {
         Self {
-            link_extension: None,
-            alias_map: None,
-            unresolved_link_url: None,
-            external_package_urls: None,
+            link_resolver: Arc::new(DefaultLinkResolver::default()),
             exec_dontrun: false,
             exec_donttest: true, // pkgdown-compatible: \donttest{} is executable by default
             quarto_code_blocks: true,
+            chunk_labels: true,
+            chunk_options: ChunkOptions::default(),
             arguments_format: ArgumentsFormat::default(),
+            value_items: ItemsFormat::default(),
+            format_items: ItemsFormat::default(),
+            tabular_format: TabularFormat::default(),
+            table_header: TableHeader::default(),
+            callouts: false,
+            heading_ids: false,
+            translate_html: false,
+            rd_format: RdFormat::default(),
+            math: MathFormat::default(),
+            typography: Typography::default(),
         }
     }
 }
@@ -105,8 +298,20 @@ pub fn rd_to_mdast(doc: &RdDocument) -> Root {
 /// Use [`RdToMdastOptions`] to control link resolution, code block behavior,
 /// and table formatting.
 pub fn rd_to_mdast_with_options(doc: &RdDocument, options: &RdToMdastOptions) -> Root {
+    rd_to_mdast_with_diagnostics(doc, options).0
+}
+
+/// Convert an Rd document to mdast, also returning diagnostics
+///
+/// Like [`rd_to_mdast_with_options`], but reports problems in the Rd source
+/// (such as a `\tabular` format that does not match the number of columns).
+pub fn rd_to_mdast_with_diagnostics(
+    doc: &RdDocument,
+    options: &RdToMdastOptions,
+) -> (Root, Vec<Diagnostic>) {
     let mut converter = Converter::new(options.clone());
-    converter.convert_document(doc)
+    let root = converter.convert_document(doc);
+    (root, converter.diagnostics)
 }
 
 /// Converter state
@@ -115,6 +320,12 @@ struct Converter {
     section_depth: u8,
     /// Conversion options
     options: RdToMdastOptions,
+    /// Problems found so far
+    diagnostics: Vec<Diagnostic>,
+    /// Rendering of item lists in the current section
+    items_format: ItemsFormat,
+    /// Topic name (`\name{}`), used for example chunk labels and options
+    topic: String,
 }
 
 impl Converter {
@@ -122,12 +333,32 @@ impl Converter {
         Self {
             section_depth: 1,
             options,
+            diagnostics: Vec::new(),
+            items_format: ItemsFormat::DefinitionList,
+            topic: String::new(),
+        }
+    }
+
+    /// Heading with an anchor id when heading ids are enabled
+    fn heading(&self, depth: u8, children: Vec<Node>, id: String) -> Node {
+        if self.options.heading_ids && !id.is_empty() {
+            Node::heading_with_id(depth, children, id)
+        } else {
+            Node::heading(depth, children)
         }
     }
 
+    fn diagnostic(&mut self, message: String) {
+        self.diagnostics.push(Diagnostic { message });
+    }
+
     fn convert_document(&mut self, doc: &RdDocument) -> Root {
         let mut children = Vec::new();
 
+        if let Some(name) = doc.get_section(&SectionTag::Name) {
+            self.topic = self.extract_text(&name.content).trim().to_string();
+        }
+
         // Extract title first
         if let Some(title) = doc.get_section(&SectionTag::Title) {
             let title_text = self.extract_text(&title.content);
@@ -153,15 +384,22 @@ impl Converter {
 
         for tag in &section_order {
             if let Some(section) = doc.get_section(tag) {
-                children.extend(self.convert_section(section));
+                let nodes = self.convert_section(section);
+                if *tag == SectionTag::Note {
+                    children.extend(self.callout("Note", nodes));
+                } else {
+                    children.extend(nodes);
+                }
             }
         }
 
         // Handle custom sections (before Examples, in original Rd order)
         for section in &doc.sections {
             if let SectionTag::Section(title) = &section.tag {
-                children.push(Node::heading(2, vec![Node::text(title.clone())]));
-                children.extend(self.convert_content(&section.content));
+                let mut nodes =
+                    vec![self.heading(2, vec![Node::text(title.clone())], make_slug(title))];
+                nodes.extend(self.convert_content(&section.content));
+                children.extend(self.callout(title, nodes));
             }
         }
 
@@ -170,9 +408,21 @@ impl Converter {
             children.extend(self.convert_section(section));
         }
 
+        if self.options.translate_html {
+            children = translate_raw_html(children);
+        }
         Root::new(children)
     }
 
+    /// Wrap a section in a callout div if callouts are enabled and the section
+    /// title is a callout type; the section heading becomes the callout title
+    fn callout(&self, title: &str, nodes: Vec<Node>) -> Vec<Node> {
+        match callout_class(title).filter(|_| self.options.callouts) {
+            Some(class) => vec![Node::div(Attributes::class(class), nodes)],
+            None => nodes,
+        }
+    }
+
     fn convert_section(&mut self, section: &RdSection) -> Vec<Node> {
         let mut nodes = Vec::new();
 
@@ -192,7 +442,11 @@ impl Converter {
             _ => return nodes, // Skip name, alias, etc.
         };
 
-        nodes.push(Node::heading(2, vec![Node::text(heading_text)]));
+        nodes.push(self.heading(
+            2,
+            vec![Node::text(heading_text)],
+            section_id(&section.tag).to_string(),
+        ));
 
         // Special handling for specific sections
         match &section.tag {
@@ -203,11 +457,24 @@ impl Converter {
             }
             SectionTag::Examples => {
                 // Examples section - may contain regular code, \dontrun{}, and \donttest{}
-                nodes.extend(self.convert_examples(&section.content));
+                let mut examples = self.convert_examples(&section.content);
+                if self.options.quarto_code_blocks {
+                    self.add_chunk_options(&mut examples);
+                }
+                nodes.extend(examples);
             }
             SectionTag::Arguments => {
                 nodes.extend(self.convert_arguments(&section.content));
             }
+            SectionTag::Value | SectionTag::Format => {
+                self.items_format = if section.tag == SectionTag::Value {
+                    self.options.value_items
+                } else {
+                    self.options.format_items
+                };
+                nodes.extend(self.convert_content(&section.content));
+                self.items_format = ItemsFormat::DefinitionList;
+            }
             _ => {
                 nodes.extend(self.convert_content(&section.content));
             }
@@ -351,6 +618,28 @@ impl Converter {
         result
     }
 
+    /// Prepend the chunk label and configured chunk options to executable example chunks
+    fn add_chunk_options(&self, nodes: &mut [Node]) {
+        let options = self.options.chunk_options.for_topic(&self.topic);
+        let slug = make_slug(&self.topic);
+        let mut index = 0;
+        for node in nodes {
+            let Node::Code(code) = node else { continue };
+            if code.meta.as_deref() != Some("executable") {
+                continue;
+            }
+            index += 1;
+            let mut directives = String::new();
+            if self.options.chunk_labels && !slug.is_empty() {
+                directives.push_str(&format!("#| label: ex-{}-{}\n", slug, index));
+            }
+            for (name, value) in &options {
+                directives.push_str(&format!("#| {}: {}\n", name, value.to_yaml()));
+            }
+            code.value.insert_str(0, &directives);
+        }
+    }
+
     fn convert_arguments(&mut self, content: &[RdNode]) -> Vec<Node> {
         match self.options.arguments_format {
             ArgumentsFormat::PipeTable => self.convert_arguments_pipe(content),
@@ -380,10 +669,18 @@ impl Converter {
             if let RdNode::Item { label, content } = node
                 && let Some(label_nodes) = label
             {
-                // Argument name as inline code
+                // Argument name as inline code, after an empty anchor span
                 let term_text = self.extract_text(label_nodes);
+                let mut arg_children = vec![Node::inline_code(term_text.trim())];
+                if self.options.heading_ids {
+                    let anchor = Attributes {
+                        id: Some(argument_id(&term_text)),
+                        ..Default::default()
+                    };
+                    arg_children.insert(0, Node::span(anchor, vec![]));
+                }
                 let arg_cell = Node::TableCell(TableCell {
-                    children: vec![Node::inline_code(term_text.trim())],
+                    children: arg_children,
                 });
 
                 // Convert description to flat inline content for GFM table cell
@@ -423,7 +720,10 @@ impl Converter {
             {
                 // Argument name with backticks for inline code
                 let term_text = self.extract_text(label_nodes);
-                let arg_text = format!("`{}`", term_text.trim());
+                let mut arg_text = format!("`{}`", term_text.trim());
+                if self.options.heading_ids {
+                    arg_text.insert_str(0, &format!("[]{{#{}}}", argument_id(&term_text)));
+                }
 
                 // Convert description to Markdown text for grid table
                 let desc_text = self.convert_to_markdown_text(content);
@@ -456,159 +756,29 @@ impl Converter {
 
     /// Convert RdNode content to Markdown text for use in grid table cells.
     ///
-    /// # Why this exists (separate from the main writer)
-    ///
-    /// Grid tables are built using the `tabled` library, which operates on raw strings.
-    /// Unlike the main mdast writer (`rd2qmd_mdast::writer`), which writes to a single
-    /// global output string, we need to convert AST subtrees to standalone markdown strings
-    /// for each table cell.
-    ///
-    /// The main writer cannot easily produce markdown for a subtree because:
-    /// 1. It maintains global state (line position, blank line tracking)
-    /// 2. It writes directly to an output buffer, not returning strings
-    ///
-    /// Ideally, we could refactor to either:
-    /// - Make the writer able to serialize subtrees to strings
-    /// - Use a table library that accepts AST nodes directly
-    ///
-    /// For now, `nodes_to_markdown` and `inline_nodes_to_markdown` exist as a separate
-    /// code path specifically for grid table cell content.
+    /// Grid tables are built using the `tabled` library, which operates on raw
+    /// strings, so each cell is written separately with the mdast writer. Text in
+    /// cells is escaped and typeset like the rest of the document.
     fn convert_to_markdown_text(&mut self, content: &[RdNode]) -> String {
-        let nodes = self.convert_content(content);
-        self.nodes_to_markdown(&nodes)
-    }
-
-    /// Convert mdast nodes to Markdown text string (for grid table cells).
-    /// See `convert_to_markdown_text` for why this exists.
-    fn nodes_to_markdown(&self, nodes: &[Node]) -> String {
-        let mut result = String::new();
-
-        for (i, node) in nodes.iter().enumerate() {
-            if i > 0 {
-                result.push_str("\n\n");
-            }
-
-            match node {
-                Node::Paragraph(p) => {
-                    result.push_str(&self.inline_nodes_to_markdown(&p.children));
-                }
-                Node::List(l) => {
-                    // Lists need a blank line before them in grid tables
-                    if i > 0 && !result.ends_with("\n\n") {
-                        result.push('\n');
-                    }
-                    for (j, item) in l.children.iter().enumerate() {
-                        if j > 0 {
-                            result.push('\n');
-                        }
-                        if let Node::ListItem(li) = item {
-                            let marker = if l.ordered {
-                                format!("{}. ", j + 1)
-                            } else {
-                                "- ".to_string()
-                            };
-                            result.push_str(&marker);
-                            // Get first paragraph content
-                            for child in &li.children {
-                                if let Node::Paragraph(p) = child {
-                                    result.push_str(&self.inline_nodes_to_markdown(&p.children));
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                }
-                Node::Code(c) => {
-                    result.push_str("```");
-                    if let Some(lang) = &c.lang {
-                        result.push_str(lang);
-                    }
-                    result.push('\n');
-                    result.push_str(&c.value);
-                    result.push_str("\n```");
-                }
-                _ => {
-                    // For other nodes, try to extract text
-                    if let Some(text) = self.node_to_text(node) {
-                        result.push_str(&text);
-                    }
-                }
-            }
+        let mut nodes = self.convert_content(content);
+        if self.options.translate_html {
+            nodes = translate_raw_html(nodes);
         }
-
-        result
+        self.nodes_to_markdown(nodes)
     }
 
-    /// Convert inline mdast nodes to Markdown text (for grid table cells).
+    /// Write mdast nodes as Markdown text for a grid table cell.
     ///
-    /// This handles inline elements like text, code, emphasis, links, and images.
-    /// See `convert_to_markdown_text` for why this separate code path exists.
-    fn inline_nodes_to_markdown(&self, nodes: &[Node]) -> String {
-        let mut result = String::new();
-
-        for node in nodes {
-            match node {
-                Node::Text(t) => result.push_str(&t.value),
-                Node::InlineCode(c) => {
-                    result.push('`');
-                    result.push_str(&c.value);
-                    result.push('`');
-                }
-                Node::Emphasis(e) => {
-                    result.push('*');
-                    result.push_str(&self.inline_nodes_to_markdown(&e.children));
-                    result.push('*');
-                }
-                Node::Strong(s) => {
-                    result.push_str("**");
-                    result.push_str(&self.inline_nodes_to_markdown(&s.children));
-                    result.push_str("**");
-                }
-                Node::Link(l) => {
-                    result.push('[');
-                    result.push_str(&self.inline_nodes_to_markdown(&l.children));
-                    result.push_str("](");
-                    result.push_str(&l.url);
-                    result.push(')');
-                }
-                Node::InlineMath(m) => {
-                    result.push('$');
-                    result.push_str(&m.value);
-                    result.push('$');
-                }
-                Node::Image(img) => {
-                    result.push_str("![");
-                    result.push_str(&img.alt);
-                    result.push_str("](");
-                    result.push_str(&img.url);
-                    if let Some(title) = &img.title {
-                        result.push_str(" \"");
-                        result.push_str(title);
-                        result.push('"');
-                    }
-                    result.push(')');
-                }
-                Node::Break => result.push_str("  \n"),
-                Node::Html(h) => result.push_str(&h.value),
-                _ => {
-                    if let Some(text) = self.node_to_text(node) {
-                        result.push_str(&text);
-                    }
-                }
-            }
-        }
-
-        result
-    }
-
-    /// Extract plain text from a node (for fallback).
-    fn node_to_text(&self, node: &Node) -> Option<String> {
-        match node {
-            Node::Text(t) => Some(t.value.clone()),
-            Node::InlineCode(c) => Some(format!("`{}`", c.value)),
-            Node::Paragraph(p) => Some(self.inline_nodes_to_markdown(&p.children)),
-            _ => None,
-        }
+    /// Grid tables are only written for Pandoc Markdown, so the cell is written
+    /// with the Pandoc flavor and the typography of the document.
+    fn nodes_to_markdown(&self, nodes: Vec<Node>) -> String {
+        let options = WriterOptions {
+            typography: self.options.typography,
+            ..Default::default()
+        };
+        mdast_to_qmd(&Root::new(nodes), &options)
+            .trim_end()
+            .to_string()
     }
 
     /// Flatten block content to inline nodes for GFM table cells.
@@ -669,13 +839,28 @@ impl Converter {
     fn convert_content(&mut self, nodes: &[RdNode]) -> Vec<Node> {
         let mut result = Vec::new();
         let mut current_para: Vec<Node> = Vec::new();
+        self.convert_content_into(nodes, &mut current_para, &mut result);
+        self.flush_paragraph(&mut current_para, &mut result);
+        result
+    }
+
+    /// Convert block content, accumulating inline nodes into `current_para`
+    ///
+    /// The active branch of `\if`/`\ifelse` is spliced into the surrounding
+    /// content, so it may hold block markup or continue the current paragraph.
+    fn convert_content_into(
+        &mut self,
+        nodes: &[RdNode],
+        current_para: &mut Vec<Node>,
+        result: &mut Vec<Node>,
+    ) {
         let mut i = 0;
 
         while i < nodes.len() {
             // Try to match roxygen2 markdown code block pattern
             #[cfg(feature = "roxygen")]
             if let Some(code_block) = try_match_roxygen_code_block(&nodes[i..]) {
-                self.flush_paragraph(&mut current_para, &mut result);
+                self.flush_paragraph(current_para, result);
                 result.push(Node::code(code_block.language, code_block.code));
                 i += code_block.nodes_consumed;
                 continue;
@@ -685,44 +870,88 @@ impl Converter {
             match node {
                 // Block-level nodes flush the current paragraph
                 RdNode::Itemize(items) => {
-                    self.flush_paragraph(&mut current_para, &mut result);
+                    self.flush_paragraph(current_para, result);
                     result.push(self.convert_list(items, false));
                 }
                 RdNode::Enumerate(items) => {
-                    self.flush_paragraph(&mut current_para, &mut result);
+                    self.flush_paragraph(current_para, result);
                     result.push(self.convert_list(items, true));
                 }
                 RdNode::Describe(items) => {
-                    self.flush_paragraph(&mut current_para, &mut result);
-                    result.push(self.convert_describe(items));
+                    self.flush_paragraph(current_para, result);
+                    let entries = items
+                        .iter()
+                        .map(|item| {
+                            (
+                                self.convert_inline_nodes(&item.term),
+                                item.description.as_slice(),
+                            )
+                        })
+                        .collect();
+                    result.extend(self.convert_items(entries, "Name"));
+                }
+                RdNode::Item { label: Some(_), .. } => {
+                    self.flush_paragraph(current_para, result);
+                    // Consecutive \item{name}{description} entries (\value components)
+                    // form one list
+                    let mut entries = Vec::new();
+                    let mut last = i;
+                    for (j, next) in nodes.iter().enumerate().skip(i) {
+                        match next {
+                            RdNode::Item {
+                                label: Some(label),
+                                content,
+                            } => {
+                                entries.push((self.item_label(label), content.as_slice()));
+                                last = j;
+                            }
+                            RdNode::Text(s) if s.trim().is_empty() => {}
+                            _ => break,
+                        }
+                    }
+                    result.extend(self.convert_items(entries, "Component"));
+                    i = last;
                 }
                 RdNode::Tabular { alignment, rows } => {
-                    self.flush_paragraph(&mut current_para, &mut result);
+                    self.flush_paragraph(current_para, result);
                     result.push(self.convert_table(alignment, rows));
                 }
                 RdNode::Subsection { title, content } => {
-                    self.flush_paragraph(&mut current_para, &mut result);
+                    self.flush_paragraph(current_para, result);
                     self.section_depth += 1;
                     let depth = (self.section_depth + 1).min(6);
-                    result.push(Node::heading(depth, self.convert_inline_nodes(title)));
+                    let id = make_slug(&self.extract_text(title));
+                    result.push(self.heading(depth, self.convert_inline_nodes(title), id));
                     result.extend(self.convert_content(content));
                     self.section_depth -= 1;
                 }
                 RdNode::Section { title, content } => {
-                    self.flush_paragraph(&mut current_para, &mut result);
+                    self.flush_paragraph(current_para, result);
                     self.section_depth += 1;
                     let depth = (self.section_depth + 1).min(6);
-                    result.push(Node::heading(depth, self.convert_inline_nodes(title)));
+                    let id = make_slug(&self.extract_text(title));
+                    result.push(self.heading(depth, self.convert_inline_nodes(title), id));
                     result.extend(self.convert_content(content));
                     self.section_depth -= 1;
                 }
                 RdNode::Preformatted(code) => {
-                    self.flush_paragraph(&mut current_para, &mut result);
+                    self.flush_paragraph(current_para, result);
                     result.push(Node::code(None, code.clone()));
                 }
-                RdNode::Deqn { latex, ascii: _ } => {
-                    self.flush_paragraph(&mut current_para, &mut result);
-                    result.push(Node::math(latex.clone()));
+                RdNode::Deqn { latex, ascii } => {
+                    self.flush_paragraph(current_para, result);
+                    result.push(match self.equation_text(latex, ascii.as_deref()) {
+                        // Multi-line ascii renderings keep their layout
+                        Some(text) if text.contains('\n') => Node::code(None, text),
+                        Some(text) => Node::paragraph(vec![Node::text(text)]),
+                        None => Node::math(latex.clone()),
+                    });
+                }
+
+                RdNode::If { .. } | RdNode::IfElse { .. } => {
+                    if let Some(content) = self.conditional_content(node) {
+                        self.convert_content_into(content, current_para, result);
+                    }
                 }
 
                 // Inline nodes accumulate in current paragraph
@@ -731,37 +960,114 @@ impl Converter {
                     let parts: Vec<&str> = s.split("\n\n").collect();
                     for (j, part) in parts.iter().enumerate() {
                         if j > 0 {
-                            self.flush_paragraph(&mut current_para, &mut result);
+                            self.flush_paragraph(current_para, result);
                         }
                         if !part.trim().is_empty() {
                             current_para.push(Node::text(normalize_whitespace(part)));
                         }
                     }
                 }
-                _ => {
-                    if let Some(inline) = self.convert_inline_node(node) {
-                        current_para.push(inline);
+                _ => match self.convert_inline_node(node) {
+                    // Block-level HTML from \out{} is a raw block of its own
+                    Some(Node::RawInline(raw)) if is_block_html(&raw.value) => {
+                        self.flush_paragraph(current_para, result);
+                        result.push(Node::RawBlock(raw));
                     }
-                }
+                    Some(inline) => current_para.push(inline),
+                    None => {}
+                },
             }
             i += 1;
         }
-
-        self.flush_paragraph(&mut current_para, &mut result);
-        result
     }
 
     fn flush_paragraph(&self, para: &mut Vec<Node>, result: &mut Vec<Node>) {
-        if !para.is_empty() {
-            result.push(Node::paragraph(std::mem::take(para)));
+        if para.is_empty() {
+            return;
+        }
+        let children = std::mem::take(para);
+        // A paragraph made only of \out{} HTML is a raw block
+        let raw_only = children.iter().all(|node| match node {
+            Node::RawInline(r) => r.format == "html",
+            Node::Text(t) => t.value.trim().is_empty(),
+            _ => false,
+        });
+        if raw_only
+            && children
+                .iter()
+                .any(|node| matches!(node, Node::RawInline(_)))
+        {
+            let html: String = children
+                .iter()
+                .filter_map(|node| match node {
+                    Node::RawInline(r) => Some(r.value.as_str()),
+                    _ => None,
+                })
+                .collect();
+            result.push(Node::raw_block("html", html));
+        } else {
+            result.push(Node::paragraph(children));
         }
     }
 
     fn convert_inline_nodes(&self, nodes: &[RdNode]) -> Vec<Node> {
-        nodes
-            .iter()
-            .filter_map(|n| self.convert_inline_node(n))
-            .collect()
+        let mut result = Vec::new();
+        for node in nodes {
+            if let Some(content) = self.conditional_content(node) {
+                result.extend(self.convert_inline_nodes(content));
+            } else if let Some(inline) = self.convert_inline_node(node) {
+                result.push(inline);
+            }
+        }
+        result
+    }
+
+    /// Text rendering of an equation, or `None` to keep it as LaTeX math
+    fn equation_text(&self, latex: &str, ascii: Option<&str>) -> Option<String> {
+        match self.options.math {
+            MathFormat::Latex => None,
+            MathFormat::Ascii => Some(match ascii {
+                Some(ascii) => ascii.trim().to_string(),
+                None => latex_to_unicode(latex),
+            }),
+            MathFormat::Unicode => Some(latex_to_unicode(latex)),
+        }
+    }
+
+    /// Active branch of an `\if` or `\ifelse` node for the target Rd format
+    ///
+    /// Returns `None` for other nodes, and empty content for an inactive `\if`.
+    fn conditional_content<'a>(&self, node: &'a RdNode) -> Option<&'a [RdNode]> {
+        match node {
+            RdNode::If { format, content } => Some(if self.options.rd_format.matches(format) {
+                content
+            } else {
+                &[]
+            }),
+            RdNode::IfElse {
+                format,
+                then_content,
+                else_content,
+            } => Some(if self.options.rd_format.matches(format) {
+                then_content
+            } else {
+                else_content
+            }),
+            _ => None,
+        }
+    }
+
+    /// Link to a topic as inline code, or plain inline code if it isn't resolved
+    fn resolve_link(&self, package: Option<&str>, topic: &str, display: String) -> Node {
+        let code = Node::inline_code(display);
+        match self.options.link_resolver.resolve(package, topic) {
+            Some(ResolvedLink {
+                url,
+                title: Some(title),
+            }) => Node::link_with_title(url, title, vec![code]),
+            Some(ResolvedLink { url, title: None }) => Node::link(url, vec![code]),
+            None => code,
+        }
     }
 
     fn convert_inline_node(&self, node: &RdNode) -> Option<Node> {
@@ -790,63 +1096,14 @@ impl Converter {
                 topic,
                 text,
             } => {
-                // Determine display text
-                let display_text = if let Some(text_nodes) = text {
-                    self.extract_text(text_nodes)
-                } else {
-                    topic.clone()
+                // Display text: the link text, pkg::topic for \link[pkg]{topic},
+                // or the topic
+                let display = match (text, package) {
+                    (Some(text_nodes), _) => self.extract_text(text_nodes),
+                    (None, Some(pkg)) => format!("{}::{}", pkg, topic),
+                    (None, None) => topic.clone(),
                 };
-
-                match (package, &self.options.link_extension) {
-                    // External package link
-                    (Some(pkg), _) => {
-                        // Package name is already parsed by the parser (no colon)
-                        // For \link[pkg]{topic}: text is None, use pkg::topic format
-                        // For \link[pkg:bar]{foo}: text is Some, use the display_text
-                        let display = if text.is_some() {
-                            display_text
-                        } else {
-                            format!("{}::{}", pkg, topic)
-                        };
-
-                        // Check if we have a URL for this external package
-                        if let Some(base_url) = self
-                            .options
-                            .external_package_urls
-                            .as_ref()
-                            .and_then(|map| map.get(pkg.as_str()))
-                        {
-                            let url = format!("{}/{}.html", base_url.trim_end_matches('/'), topic);
-                            Some(Node::link(url, vec![Node::inline_code(display)]))
-                        } else {
-                            // No URL found - just inline code
-                            Some(Node::inline_code(display))
-                        }
-                    }
-                    // Internal link with extension configured - create hyperlink
-                    (None, Some(ext)) => {
-                        // Resolve alias to target file using alias_map
-                        if let Some(target_file) = self
-                            .options
-                            .alias_map
-                            .as_ref()
-                            .and_then(|map| map.get(topic))
-                        {
-                            // Found in local package - create relative link
-                            let url = format!("{}.{}", target_file, ext);
-                            Some(Node::link(url, vec![Node::inline_code(display_text)]))
-                        } else if let Some(pattern) = &self.options.unresolved_link_url {
-                            // Not found - use fallback URL pattern
-                            let url = pattern.replace("{topic}", topic);
-                            Some(Node::link(url, vec![Node::inline_code(display_text)]))
-                        } else {
-                            // No fallback configured - just inline code
-                            Some(Node::inline_code(display_text))
-                        }
-                    }
-                    // Internal link without extension - just inline code
-                    (None, None) => Some(Node::inline_code(display_text)),
-                }
+                Some(self.resolve_link(package.as_deref(), topic, display))
             }
             RdNode::Url(url) => Some(Node::link(url.clone(), vec![Node::text(url.clone())])),
             RdNode::Doi(id) => {
@@ -861,55 +1118,17 @@ impl Converter {
                 } else {
                     format!("{}-class", classname)
                 };
-
-                match (package, &self.options.link_extension) {
-                    // External package link
-                    (Some(pkg), _) => {
-                        if let Some(base_url) = self
-                            .options
-                            .external_package_urls
-                            .as_ref()
-                            .and_then(|map| map.get(pkg.as_str()))
-                        {
-                            // Link to classname-class topic
-                            let url = format!(
-                                "{}/{}-class.html",
-                                base_url.trim_end_matches('/'),
-                                classname
-                            );
-                            Some(Node::link(url, vec![Node::inline_code(display)]))
-                        } else {
-                            Some(Node::inline_code(display))
-                        }
-                    }
-                    // Internal link with extension configured
-                    (None, Some(ext)) => {
-                        // The topic name is classname-class
-                        let topic = format!("{}-class", classname);
-                        if let Some(target_file) = self
-                            .options
-                            .alias_map
-                            .as_ref()
-                            .and_then(|map| map.get(&topic))
-                        {
-                            let url = format!("{}.{}", target_file, ext);
-                            Some(Node::link(url, vec![Node::inline_code(display)]))
-                        } else if let Some(pattern) = &self.options.unresolved_link_url {
-                            let url = pattern.replace("{topic}", &topic);
-                            Some(Node::link(url, vec![Node::inline_code(display)]))
-                        } else {
-                            Some(Node::inline_code(display))
-                        }
-                    }
-                    // No extension - just inline code
-                    (None, None) => Some(Node::inline_code(display)),
-                }
+                let topic = format!("{}-class", classname);
+                Some(self.resolve_link(package.as_deref(), &topic, display))
             }
-            // Use UTF-8 encoded text for Markdown/HTML output
-            RdNode::Enc {
-                encoded,
-                fallback: _,
-            } => Some(Node::text(encoded.clone())),
+            // UTF-8 encoded text, or the ASCII fallback for ASCII typography
+            RdNode::Enc { encoded, fallback } => Some(Node::text(
+                if self.options.typography == Typography::Ascii {
+                    fallback.clone()
+                } else {
+                    encoded.clone()
+                },
+            )),
             RdNode::Email(email) => {
                 let mailto = format!("mailto:{}", email);
                 Some(Node::link(mailto, vec![Node::text(email.clone())]))
@@ -920,8 +1139,13 @@ impl Converter {
                 Some(Node::inline_code(text))
             }
             RdNode::Var(name) => Some(Node::emphasis(vec![Node::text(name.clone())])),
-            RdNode::Eqn { latex, ascii: _ } => Some(Node::inline_math(latex.clone())),
-            RdNode::Special(ch) => Some(Node::text(special_char_to_string(*ch))),
+            RdNode::Eqn { latex, ascii } => {
+                Some(match self.equation_text(latex, ascii.as_deref()) {
+                    Some(text) => Node::text(text),
+                    None => Node::inline_math(latex.clone()),
+                })
+            }
+            RdNode::Special(ch) => Some(Node::text(self.special_char_text(*ch))),
             RdNode::LineBreak => Some(Node::Break),
             RdNode::Samp(children) => {
                 let text = self.extract_text(children);
@@ -929,11 +1153,17 @@ impl Converter {
             }
             RdNode::SQuote(children) => {
                 let text = self.extract_text(children);
-                Some(Node::text(format!("'{}'", text)))
+                Some(Node::text(match self.options.typography {
+                    Typography::Ascii => format!("'{}'", text),
+                    _ => format!("\u{2018}{}\u{2019}", text),
+                }))
             }
             RdNode::DQuote(children) => {
                 let text = self.extract_text(children);
-                Some(Node::text(format!("\"{}\"", text)))
+                Some(Node::text(match self.options.typography {
+                    Typography::Ascii => format!("\"{}\"", text),
+                    _ => format!("\u{201C}{}\u{201D}", text),
+                }))
             }
             RdNode::Acronym(s) => Some(Node::text(s.clone())),
             RdNode::Abbr(s) => Some(Node::text(s.clone())),
@@ -946,39 +1176,16 @@ impl Converter {
                 let text = self.extract_text(children);
                 Some(Node::inline_code(text))
             }
-            RdNode::If { format, content } => {
-                // For markdown/html output, include content if format matches
-                if format == "html" || format == "text" {
-                    let inline = self.convert_inline_nodes(content);
-                    if inline.len() == 1 {
-                        inline.into_iter().next()
-                    } else {
-                        Some(Node::paragraph(inline))
-                    }
-                } else {
-                    None
-                }
-            }
-            RdNode::IfElse {
-                format,
-                then_content,
-                else_content,
-            } => {
-                let content = if format == "html" || format == "text" {
-                    then_content
-                } else {
-                    else_content
-                };
-                let inline = self.convert_inline_nodes(content);
-                if inline.len() == 1 {
-                    inline.into_iter().next()
-                } else {
-                    Some(Node::paragraph(inline))
+            RdNode::If { .. } | RdNode::IfElse { .. } => {
+                let content = self.conditional_content(node)?;
+                let mut inline = self.convert_inline_nodes(content);
+                match inline.len() {
+                    0 => None,
+                    1 => inline.pop(),
+                    _ => Some(Node::paragraph(inline)),
                 }
             }
-            RdNode::Out(html) => Some(Node::Html(Html {
-                value: html.clone(),
-            })),
+            RdNode::Out(html) => Some(Node::raw_inline("html", html.clone())),
             RdNode::Figure { file, options } => {
                 let alt = match options {
                     Some(FigureOptions::AltText(text)) => text.clone(),
@@ -987,10 +1194,20 @@ impl Converter {
                     }
                     None => file.clone(),
                 };
+                // Expert-form image size becomes image attributes ({width="100"})
+                let mut attributes = Attributes::default();
+                if let Some(FigureOptions::ExpertOptions(attrs)) = options {
+                    for name in ["width", "height"] {
+                        if let Some(value) = Self::extract_attr_from_attrs(attrs, name) {
+                            attributes.attributes.push((name.to_string(), value));
+                        }
+                    }
+                }
                 Some(Node::Image(Image {
                     url: file.clone(),
                     title: None,
                     alt,
+                    attributes,
                 }))
             }
             RdNode::Method { generic, class: _ } => Some(Node::text(format!("{}()", generic))),
@@ -1024,50 +1241,261 @@ impl Converter {
         Node::list(ordered, list_items)
     }
 
-    fn convert_describe(&self, items: &[DescribeItem]) -> Node {
-        let mut children = Vec::new();
+    /// Label of a `\value` component: inline code unless it has its own markup
+    fn item_label(&self, label: &[RdNode]) -> Vec<Node> {
+        if label.iter().all(|node| matches!(node, RdNode::Text(_))) {
+            vec![Node::inline_code(self.extract_text(label).trim())]
+        } else {
+            self.convert_inline_nodes(label)
+        }
+    }
+
+    /// Render term/description items in the current section's items format
+    fn convert_items(
+        &mut self,
+        entries: Vec<(Vec<Node>, &[RdNode])>,
+        term_header: &str,
+    ) -> Vec<Node> {
+        let format = self.items_format;
+        // Nested \describe lists in descriptions stay definition lists
+        self.items_format = ItemsFormat::DefinitionList;
+        let nodes = match format {
+            ItemsFormat::DefinitionList => vec![self.items_definition_list(entries)],
+            ItemsFormat::List => self.items_list(entries),
+            ItemsFormat::Table => match self.options.arguments_format {
+                ArgumentsFormat::PipeTable => self.items_pipe_table(entries, term_header),
+                ArgumentsFormat::GridTable => self.items_grid_table(entries, term_header),
+            },
+        };
+        self.items_format = format;
+        nodes
+    }
+
+    /// Description of an item: a paragraph for inline content, blocks otherwise
+    fn item_description(&mut self, content: &[RdNode]) -> Vec<Node> {
+        if has_block_content(content) {
+            return self.convert_content(content);
+        }
+        let desc = self.convert_inline_nodes(content);
+        if desc.is_empty() {
+            vec![]
+        } else {
+            vec![Node::paragraph(desc)]
+        }
+    }
 
-        for item in items {
-            let term = self.convert_inline_nodes(&item.term);
-            let desc = self.convert_inline_nodes(&item.description);
+    fn items_definition_list(&mut self, entries: Vec<(Vec<Node>, &[RdNode])>) -> Node {
+        let mut children = Vec::new();
 
+        for (term, description) in entries {
             children.push(Node::DefinitionTerm(DefinitionTerm { children: term }));
             children.push(Node::DefinitionDescription(DefinitionDescription {
-                children: if desc.is_empty() {
-                    vec![]
-                } else {
-                    vec![Node::paragraph(desc)]
-                },
+                children: self.item_description(description),
             }));
         }
 
         Node::DefinitionList(DefinitionList { children })
     }
 
-    fn convert_table(&self, alignment: &str, rows: &[Vec<Vec<RdNode>>]) -> Node {
-        let align: Vec<Option<Align>> = alignment
+    fn items_list(&mut self, entries: Vec<(Vec<Node>, &[RdNode])>) -> Vec<Node> {
+        if entries.is_empty() {
+            return vec![];
+        }
+        let list_items = entries
+            .into_iter()
+            .map(|(term, description)| {
+                let mut children = self.item_description(description);
+                // The term leads the first paragraph: "**term**: description"
+                let mut lead = vec![Node::strong(term)];
+                match children.first_mut() {
+                    Some(Node::Paragraph(p)) => {
+                        lead.push(Node::text(": "));
+                        lead.append(&mut p.children);
+                        p.children = lead;
+                    }
+                    _ => children.insert(0, Node::paragraph(lead)),
+                }
+                Node::list_item(children)
+            })
+            .collect();
+        vec![Node::list(false, list_items)]
+    }
+
+    /// Items as a pipe table; block content in descriptions is flattened
+    fn items_pipe_table(
+        &mut self,
+        entries: Vec<(Vec<Node>, &[RdNode])>,
+        term_header: &str,
+    ) -> Vec<Node> {
+        if entries.is_empty() {
+            return vec![];
+        }
+        let mut rows = vec![Node::TableRow(TableRow {
+            children: vec![
+                Node::TableCell(TableCell {
+                    children: vec![Node::text(term_header)],
+                }),
+                Node::TableCell(TableCell {
+                    children: vec![Node::text("Description")],
+                }),
+            ],
+        })];
+
+        for (term, description) in entries {
+            let desc_cell = Node::TableCell(TableCell {
+                children: self.flatten_for_table_cell(description),
+            });
+            rows.push(Node::TableRow(TableRow {
+                children: vec![Node::TableCell(TableCell { children: term }), desc_cell],
+            }));
+        }
+
+        vec![Node::Table(Table {
+            align: vec![Some(Align::Left), Some(Align::Left)],
+            children: rows,
+        })]
+    }
+
+    /// Items as a Pandoc grid table, which keeps block content in descriptions
+    fn items_grid_table(
+        &mut self,
+        entries: Vec<(Vec<Node>, &[RdNode])>,
+        term_header: &str,
+    ) -> Vec<Node> {
+        use tabled::builder::Builder;
+
+        if entries.is_empty() {
+            return vec![];
+        }
+        let mut builder = Builder::default();
+        builder.push_record([term_header, "Description"]);
+
+        for (term, description) in entries {
+            let term_text = self.nodes_to_markdown(vec![Node::paragraph(term)]);
+            let desc_text = self.convert_to_markdown_text(description);
+            builder.push_record([term_text.trim().to_string(), desc_text.trim().to_string()]);
+        }
+
+        let grid_style = Style::ascii().horizontals([(
+            1,
+            HorizontalLine::new('=')
+                .left('+')
+                .right('+')
+                .intersection('+'),
+        )]);
+        let grid_table = builder.build().with(grid_style).to_string();
+
+        vec![Node::Html(Html { value: grid_table })]
+    }
+
+    fn convert_table(&mut self, alignment: &str, rows: &[Vec<Vec<RdNode>>]) -> Node {
+        // A trailing `\cr` leaves an empty last row
+        let rows = match rows.split_last() {
+            Some((last, rest)) if last.iter().all(|cell| is_blank(cell)) => rest,
+            _ => rows,
+        };
+        let align = self.table_alignment(alignment, rows);
+        let grid = match self.options.tabular_format {
+            TabularFormat::PipeTable => false,
+            TabularFormat::GridTable => true,
+            TabularFormat::Auto => rows.iter().flatten().any(|cell| has_block_content(cell)),
+        };
+        if grid {
+            self.convert_table_grid(&align, rows)
+        } else {
+            self.convert_table_pipe(align, rows)
+        }
+    }
+
+    /// Column alignments from the `\tabular` format, checked against the table
+    ///
+    /// Mismatches are reported as diagnostics; missing columns get the default
+    /// alignment and extra format letters are dropped.
+    fn table_alignment(
+        &mut self,
+        alignment: &str,
+        rows: &[Vec<Vec<RdNode>>],
+    ) -> Vec<Option<Align>> {
+        // Vertical rules are LaTeX syntax that some packages use; they have no column
+        let letters: Vec<char> = alignment
             .chars()
-            .map(|c| match c {
+            .filter(|c| !c.is_whitespace() && *c != '|')
+            .collect();
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if letters.len() != columns {
+            self.diagnostic(format!(
+                "\\tabular format \"{}\" has {} columns, but the table has {}",
+                alignment,
+                letters.len(),
+                columns
+            ));
+        }
+
+        let mut align = Vec::with_capacity(columns);
+        for c in letters {
+            align.push(match c {
                 'l' => Some(Align::Left),
                 'c' => Some(Align::Center),
                 'r' => Some(Align::Right),
-                _ => None,
-            })
-            .collect();
+                _ => {
+                    self.diagnostic(format!(
+                        "\\tabular format \"{}\" has unknown alignment '{}'",
+                        alignment, c
+                    ));
+                    None
+                }
+            });
+        }
+        align.resize(columns, None);
+        align
+    }
 
-        let table_rows: Vec<Node> = rows
-            .iter()
-            .map(|row| {
-                let cells: Vec<Node> = row
-                    .iter()
-                    .map(|cell| {
-                        let children = self.convert_inline_nodes(cell);
-                        Node::TableCell(TableCell { children })
-                    })
-                    .collect();
-                Node::TableRow(TableRow { children: cells })
-            })
-            .collect();
+    /// Header cells for `\tabular`, or `None` to use the first row
+    fn table_header_cells(&self, columns: usize) -> Option<Vec<String>> {
+        match self.options.table_header {
+            TableHeader::FirstRow => None,
+            TableHeader::Empty => Some(vec![String::new(); columns]),
+            TableHeader::Synthetic => {
+                Some((1..=columns).map(|i| format!("Column {}", i)).collect())
+            }
+        }
+    }
+
+    fn convert_table_pipe(&mut self, align: Vec<Option<Align>>, rows: &[Vec<Vec<RdNode>>]) -> Node {
+        let columns = align.len();
+        let mut table_rows = Vec::new();
+
+        if let Some(header) = self.table_header_cells(columns) {
+            let cells = header
+                .into_iter()
+                .map(|text| {
+                    let children = if text.is_empty() {
+                        Vec::new()
+                    } else {
+                        vec![Node::text(text)]
+                    };
+                    Node::TableCell(TableCell { children })
+                })
+                .collect();
+            table_rows.push(Node::TableRow(TableRow { children: cells }));
+        }
+
+        for row in rows {
+            let mut cells: Vec<Node> = row
+                .iter()
+                .map(|cell| {
+                    // Lists and code are flattened, as pipe table cells are single lines
+                    let children = if has_block_content(cell) {
+                        self.flatten_for_table_cell(cell)
+                    } else {
+                        self.convert_inline_nodes(cell)
+                    };
+                    Node::TableCell(TableCell { children })
+                })
+                .collect();
+            cells.resize_with(columns, || Node::TableCell(TableCell { children: vec![] }));
+            table_rows.push(Node::TableRow(TableRow { children: cells }));
+        }
 
         Node::Table(Table {
             align,
@@ -1075,6 +1503,45 @@ impl Converter {
         })
     }
 
+    /// Convert `\tabular` to a Pandoc grid table, which allows lists and code in cells
+    fn convert_table_grid(&mut self, align: &[Option<Align>], rows: &[Vec<Vec<RdNode>>]) -> Node {
+        use tabled::builder::Builder;
+
+        let columns = align.len();
+        let mut builder = Builder::default();
+        let has_header = self.options.table_header != TableHeader::Empty;
+        if let Some(header) = self.table_header_cells(columns).filter(|_| has_header) {
+            builder.push_record(header);
+        }
+        for row in rows {
+            let mut cells: Vec<String> = row
+                .iter()
+                .map(|cell| self.convert_to_markdown_text(cell).trim().to_string())
+                .collect();
+            cells.resize(columns, String::new());
+            builder.push_record(cells);
+        }
+
+        let mut table = builder.build();
+        let grid_table = if has_header {
+            let header_style = Style::ascii().horizontals([(
+                1,
+                HorizontalLine::new('=')
+                    .left('+')
+                    .right('+')
+                    .intersection('+'),
+            )]);
+            table.with(header_style).to_string()
+        } else {
+            table.with(Style::ascii()).to_string()
+        };
+
+        // Output as raw text (will be rendered as grid table by Pandoc)
+        Node::Html(Html {
+            value: align_grid_table(&grid_table, align, has_header),
+        })
+    }
+
     /// Extract alt text from HTML attributes string (Expert form).
     ///
     /// This function handles the expert form where the parser has already stripped
@@ -1082,24 +1549,41 @@ impl Converter {
     ///
     /// Reference: https://cran.r-project.org/doc/manuals/r-devel/R-exts.html#Figures
     fn extract_alt_from_attrs(attrs: &str) -> Option<String> {
-        if attrs.is_empty() {
-            return None;
-        }
-        // Try single quotes: alt='...'
-        if let Some(start) = attrs.find("alt='") {
-            let after_quote = &attrs[start + 5..];
-            if let Some(end) = after_quote.find('\'') {
-                return Some(after_quote[..end].to_string());
-            }
-        }
-        // Try double quotes: alt="..."
-        if let Some(start) = attrs.find("alt=\"") {
-            let after_quote = &attrs[start + 5..];
-            if let Some(end) = after_quote.find('"') {
-                return Some(after_quote[..end].to_string());
+        Self::extract_attr_from_attrs(attrs, "alt")
+    }
+
+    /// Extract an attribute value from an expert-form attributes string.
+    ///
+    /// Values may be single-quoted, double-quoted or bare (`width=100`).
+    fn extract_attr_from_attrs(attrs: &str, name: &str) -> Option<String> {
+        let key = format!("{}=", name);
+        let mut search = 0;
+        while let Some(pos) = attrs[search..].find(&key) {
+            let start = search + pos;
+            search = start + key.len();
+            // Match whole attribute names only (not `data-alt=`)
+            if attrs[..start]
+                .chars()
+                .next_back()
+                .is_some_and(|c| !c.is_whitespace())
+            {
+                continue;
             }
+            let value = &attrs[search..];
+            return match value.chars().next() {
+                Some(quote @ ('\'' | '"')) => {
+                    let value = &value[1..];
+                    value.find(quote).map(|end| value[..end].to_string())
+                }
+                _ => Some(
+                    value
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string(),
+                ),
+            };
         }
-        // No alt attribute found
         None
     }
 
@@ -1188,6 +1672,11 @@ impl Converter {
                 }
                 RdNode::Special(ch) => result.push_str(special_char_to_string(*ch)),
                 RdNode::LineBreak => result.push('\n'),
+                RdNode::If { .. } | RdNode::IfElse { .. } => {
+                    if let Some(content) = self.conditional_content(&nodes[i]) {
+                        result.push_str(&self.extract_text(content));
+                    }
+                }
                 _ => {}
             }
             i += 1;
@@ -1195,6 +1684,20 @@ impl Converter {
         result
     }
 
+    /// Special character in text; code always uses [`special_char_to_string`]
+    ///
+    /// Writers turn the Unicode characters into HTML entities for HTML typography.
+    fn special_char_text(&self, ch: SpecialChar) -> &'static str {
+        match (self.options.typography, ch) {
+            (Typography::Ascii, SpecialChar::EnDash) => "--",
+            (Typography::Ascii, SpecialChar::EmDash) => "---",
+            (Typography::Ascii, SpecialChar::Lsqb | SpecialChar::Rsqb) => "'",
+            (Typography::Ascii, SpecialChar::Ldqb | SpecialChar::Rdqb) => "\"",
+            (Typography::Unicode | Typography::Html, SpecialChar::Dots) => "\u{2026}",
+            (_, ch) => special_char_to_string(ch),
+        }
+    }
+
     /// Try to format a method as an infix expression (e.g., `e1 + e2` instead of `+(e1, e2)`)
     fn try_format_infix_method(
         &self,
@@ -1411,7 +1914,7 @@ fn format_infix_call(operator: &str, args: &[String]) -> Option<String> {
     }
 }
 
-fn special_char_to_string(ch: SpecialChar) -> &'static str {
+pub(crate) fn special_char_to_string(ch: SpecialChar) -> &'static str {
     match ch {
         SpecialChar::R => "R",
         SpecialChar::Dots => "...",
@@ -1428,6 +1931,142 @@ fn special_char_to_string(ch: SpecialChar) -> &'static str {
     }
 }
 
+/// pkgdown anchor of a standard section
+fn section_id(tag: &SectionTag) -> &'static str {
+    match tag {
+        SectionTag::Description => "description",
+        SectionTag::Usage => "ref-usage",
+        SectionTag::Arguments => "arguments",
+        SectionTag::Value => "value",
+        SectionTag::Details => "details",
+        SectionTag::Note => "note",
+        SectionTag::SeeAlso => "see-also",
+        SectionTag::Examples => "ref-examples",
+        SectionTag::References => "references",
+        SectionTag::Author => "author",
+        SectionTag::Format => "format",
+        SectionTag::Source => "source",
+        _ => "",
+    }
+}
+
+/// pkgdown anchor of an argument: `na.rm` becomes `arg-na-rm`
+fn argument_id(name: &str) -> String {
+    let slug = make_slug(name);
+    if slug.is_empty() {
+        // `...` has no letters
+        "arg-dots".to_string()
+    } else {
+        format!("arg-{}", slug)
+    }
+}
+
+/// Anchor slug as pkgdown makes it: lowercase, with runs of other characters
+/// than ASCII letters, digits and `_` replaced by `-`
+pub fn make_slug(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Whether `\tabular` cell content needs block rendering (lists, code, paragraphs)
+fn has_block_content(cell: &[RdNode]) -> bool {
+    cell.iter().any(|node| match node {
+        RdNode::Itemize(_)
+        | RdNode::Enumerate(_)
+        | RdNode::Describe(_)
+        | RdNode::Preformatted(_)
+        | RdNode::Tabular { .. }
+        | RdNode::Deqn { .. } => true,
+        RdNode::Text(s) => s.trim().contains("\n\n"),
+        _ => false,
+    })
+}
+
+/// Whether `\tabular` cell content is only whitespace
+fn is_blank(cell: &[RdNode]) -> bool {
+    cell.iter()
+        .all(|node| matches!(node, RdNode::Text(s) if s.trim().is_empty()))
+}
+
+/// Mark column alignment with colons in a grid table, on the header separator or,
+/// for headerless tables, on the top border (as Pandoc reads it)
+fn align_grid_table(table: &str, align: &[Option<Align>], has_header: bool) -> String {
+    let target = if has_header {
+        table.lines().position(|line| line.starts_with("+="))
+    } else {
+        Some(0)
+    };
+    table
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if Some(i) != target {
+                return line.to_string();
+            }
+            let mut chars: Vec<char> = line.chars().collect();
+            let borders: Vec<usize> = (0..chars.len()).filter(|&j| chars[j] == '+').collect();
+            for (column, pair) in borders.windows(2).enumerate() {
+                let (start, end) = (pair[0] + 1, pair[1] - 1);
+                if start > end {
+                    continue;
+                }
+                match align.get(column).copied().flatten() {
+                    Some(Align::Left) => chars[start] = ':',
+                    Some(Align::Right) => chars[end] = ':',
+                    Some(Align::Center) => {
+                        chars[start] = ':';
+                        chars[end] = ':';
+                    }
+                    None => {}
+                }
+            }
+            chars.into_iter().collect()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Whether raw HTML starts with a block-level element tag
+fn is_block_html(html: &str) -> bool {
+    const BLOCK_TAGS: &[&str] = &[
+        "blockquote",
+        "details",
+        "div",
+        "dl",
+        "figure",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "hr",
+        "ol",
+        "p",
+        "pre",
+        "section",
+        "summary",
+        "table",
+        "ul",
+    ];
+    let Some(tag) = html.trim_start().strip_prefix('<') else {
+        return false;
+    };
+    let tag = tag.strip_prefix('/').unwrap_or(tag);
+    let name: String = tag
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+    BLOCK_TAGS.contains(&name.to_ascii_lowercase().as_str())
+}
+
 fn normalize_whitespace(s: &str) -> String {
     if s.is_empty() {
         return String::new();
@@ -1457,5 +2096,18 @@ fn normalize_whitespace(s: &str) -> String {
     result
 }
 
+/// Quarto callout class for a section title (Note, Warning, ...)
+fn callout_class(title: &str) -> Option<&'static str> {
+    match title.trim().trim_end_matches(':').to_lowercase().as_str() {
+        "note" | "notes" => Some("callout-note"),
+        "warning" | "warnings" => Some("callout-warning"),
+        "caution" => Some("callout-caution"),
+        "important" => Some("callout-important"),
+        "tip" | "tips" => Some("callout-tip"),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::collapsible_if)]
 mod tests;
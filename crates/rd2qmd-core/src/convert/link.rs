@@ -0,0 +1,167 @@
+//! Link resolution for `\link{}` and `\linkS4class{}`
+//!
+//! The converter asks a [`LinkResolver`] for the URL of every link target.
+//! [`DefaultLinkResolver`] links topics of the same package through an alias map
+//! and other packages through their reference documentation URLs; integrators
+//! can plug in their own resolver for custom URL schemes, a database of topics
+//! or to record the links of a package.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Target of a resolved link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedLink {
+    /// Link URL
+    pub url: String,
+    /// Link title, shown as a tooltip
+    pub title: Option<String>,
+}
+
+impl ResolvedLink {
+    /// Link to a URL without a title
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            title: None,
+        }
+    }
+}
+
+/// Resolves the targets of `\link{}` and `\linkS4class{}` to URLs
+///
+/// `package` is the package of `\link[pkg]{topic}`, or None for a topic of the
+/// package being converted. `\linkS4class{cls}` is resolved as the topic
+/// `cls-class`. Links that are not resolved become inline code.
+///
+/// # Example
+///
+/// ```
+/// use rd2qmd_core::{LinkResolver, ResolvedLink};
+///
+/// #[derive(Debug)]
+/// struct CranResolver;
+///
+/// impl LinkResolver for CranResolver {
+///     fn resolve(&self, package: Option<&str>, topic: &str) -> Option<ResolvedLink> {
+///         let package = package.unwrap_or("mypkg");
+///         Some(ResolvedLink::new(format!(
+///             "https://rdrr.io/cran/{package}/man/{topic}.html"
+///         )))
+///     }
+/// }
+/// ```
+pub trait LinkResolver: fmt::Debug + Send + Sync {
+    /// Resolve a topic, optionally of another package, to a link
+    fn resolve(&self, package: Option<&str>, topic: &str) -> Option<ResolvedLink>;
+}
+
+/// Link resolution with an alias map, a fallback URL and external package URLs
+///
+/// - `\link{topic}` links to `<file>.<extension>` when the alias map has the
+///   topic, or else to `unresolved_url` with `{topic}` replaced
+/// - `\link[pkg]{topic}` links to `<base_url>/<topic>.html` when
+///   `external_package_urls` has the package
+///
+/// Without an extension, topics of the same package are not linked. Links to
+/// files in `titles` carry that title, such as one from [`topic_link_title`].
+///
+/// [`topic_link_title`]: crate::topic_link_title
+#[derive(Debug, Clone, Default)]
+pub struct DefaultLinkResolver {
+    /// File extension for internal links (e.g., "qmd", "md", "html")
+    pub extension: Option<String>,
+    /// Alias map: maps alias names to Rd file basenames (without extension)
+    /// Shared, as the same map serves every file of a package.
+    pub alias_map: Arc<HashMap<String, String>>,
+    /// Link titles by Rd file basename (the values of the alias map)
+    pub titles: Arc<HashMap<String, String>>,
+    /// URL pattern for topics not in the alias map, with `{topic}` as placeholder
+    /// Example: `https://rdrr.io/r/base/{topic}.html`
+    pub unresolved_url: Option<String>,
+    /// External package URL map: package name -> reference documentation base URL
+    /// Example: `"dplyr" -> "https://dplyr.tidyverse.org/reference"`
+    pub external_package_urls: HashMap<String, String>,
+}
+
+impl LinkResolver for DefaultLinkResolver {
+    fn resolve(&self, package: Option<&str>, topic: &str) -> Option<ResolvedLink> {
+        if let Some(pkg) = package {
+            let base_url = self.external_package_urls.get(pkg)?;
+            let url = format!("{}/{}.html", base_url.trim_end_matches('/'), topic);
+            return Some(ResolvedLink::new(url));
+        }
+
+        let ext = self.extension.as_ref()?;
+        match self.alias_map.get(topic) {
+            Some(target_file) => Some(ResolvedLink {
+                url: format!("{}.{}", target_file, ext),
+                title: self.titles.get(target_file).cloned(),
+            }),
+            None => {
+                let url = self.unresolved_url.as_ref()?.replace("{topic}", topic);
+                Some(ResolvedLink::new(url))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver() -> DefaultLinkResolver {
+        DefaultLinkResolver {
+            extension: Some("qmd".to_string()),
+            alias_map: Arc::new(HashMap::from([(
+                "DataFrame".to_string(),
+                "pl__DataFrame".to_string(),
+            )])),
+            titles: Arc::new(HashMap::from([(
+                "pl__DataFrame".to_string(),
+                "Polars DataFrame".to_string(),
+            )])),
+            unresolved_url: Some("https://rdrr.io/r/base/{topic}.html".to_string()),
+            external_package_urls: HashMap::from([(
+                "dplyr".to_string(),
+                "https://dplyr.tidyverse.org/reference/".to_string(),
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_default_resolver_internal() {
+        let resolver = resolver();
+        assert_eq!(
+            resolver.resolve(None, "DataFrame"),
+            Some(ResolvedLink {
+                url: "pl__DataFrame.qmd".to_string(),
+                title: Some("Polars DataFrame".to_string()),
+            })
+        );
+        assert_eq!(
+            resolver.resolve(None, "vector"),
+            Some(ResolvedLink::new("https://rdrr.io/r/base/vector.html"))
+        );
+
+        // Without an extension, internal links are not resolved
+        let resolver = DefaultLinkResolver {
+            extension: None,
+            ..resolver
+        };
+        assert_eq!(resolver.resolve(None, "DataFrame"), None);
+    }
+
+    #[test]
+    fn test_default_resolver_external() {
+        let resolver = resolver();
+        assert_eq!(
+            resolver.resolve(Some("dplyr"), "filter"),
+            Some(ResolvedLink::new(
+                "https://dplyr.tidyverse.org/reference/filter.html"
+            ))
+        );
+        assert_eq!(resolver.resolve(Some("tidyr"), "pivot_longer"), None);
+    }
+}
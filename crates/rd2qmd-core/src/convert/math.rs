@@ -0,0 +1,457 @@
+//! Unicode rendering of LaTeX math
+//!
+//! Covers the subset of LaTeX that appears in most Rd equations: Greek letters,
+//! sub- and superscripts, `\frac`, `\sqrt`, accents, big operators, relations and
+//! arrows. Unknown commands are kept as they are, so nothing is silently lost.
+
+/// Render LaTeX math as Unicode text
+pub(crate) fn latex_to_unicode(latex: &str) -> String {
+    let mut parser = Parser {
+        chars: latex.chars().collect(),
+        pos: 0,
+    };
+    let text = parser.parse_until(None);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    /// Parse up to (and consume) `end`, or to the end of input
+    fn parse_until(&mut self, end: Option<char>) -> String {
+        let mut out = String::new();
+        while let Some(c) = self.next() {
+            if Some(c) == end {
+                break;
+            }
+            match c {
+                '\\' => out.push_str(&self.command()),
+                '{' => out.push_str(&self.parse_until(Some('}'))),
+                '^' => {
+                    let arg = self.argument();
+                    out.push_str(&script(&arg, superscript, '^'));
+                }
+                '_' => {
+                    let arg = self.argument();
+                    out.push_str(&script(&arg, subscript, '_'));
+                }
+                '~' | '&' => out.push(' '),
+                '\'' => out.push('′'),
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
+    /// Next argument: a braced group, a command or a single character
+    fn argument(&mut self) -> String {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+        match self.next() {
+            Some('{') => self.parse_until(Some('}')),
+            Some('\\') => self.command(),
+            Some(c) => c.to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// Optional `[...]` argument
+    fn optional_argument(&mut self) -> Option<String> {
+        if self.peek() != Some('[') {
+            return None;
+        }
+        self.pos += 1;
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c != ']') {
+            self.pos += 1;
+        }
+        let arg = self.chars[start..self.pos].iter().collect();
+        self.pos += 1;
+        Some(arg)
+    }
+
+    /// Parse a command after its backslash
+    fn command(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            // Control symbols: spacing and escaped characters
+            return match self.next() {
+                Some(',' | ';' | ':' | ' ' | '\\') => " ".to_string(),
+                Some('!') | None => String::new(),
+                Some(c) => c.to_string(),
+            };
+        }
+
+        let name: String = self.chars[start..self.pos].iter().collect();
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.argument();
+                let denominator = self.argument();
+                format!("{}/{}", group(&numerator), group(&denominator))
+            }
+            "sqrt" => {
+                let root = match self.optional_argument().as_deref().map(str::trim) {
+                    Some("3") => '∛',
+                    Some("4") => '∜',
+                    _ => '√',
+                };
+                format!("{}{}", root, group(&self.argument()))
+            }
+            "mathrm" | "mathit" | "mathbf" | "mathsf" | "mathtt" | "mathnormal" | "boldsymbol"
+            | "text" | "textrm" | "textit" | "textbf" | "mbox" | "operatorname" => self.argument(),
+            "bar" | "overline" => accent(&self.argument(), '\u{0304}'),
+            "hat" | "widehat" => accent(&self.argument(), '\u{0302}'),
+            "tilde" | "widetilde" => accent(&self.argument(), '\u{0303}'),
+            "dot" => accent(&self.argument(), '\u{0307}'),
+            "ddot" => accent(&self.argument(), '\u{0308}'),
+            "vec" => accent(&self.argument(), '\u{20D7}'),
+            "left" | "right" => {
+                // `\left.` is an invisible delimiter
+                if self.peek() == Some('.') {
+                    self.pos += 1;
+                }
+                String::new()
+            }
+            "big" | "Big" | "bigg" | "Bigg" | "bigl" | "bigr" | "Bigl" | "Bigr"
+            | "displaystyle" | "textstyle" | "limits" | "nolimits" => String::new(),
+            "quad" | "qquad" => " ".to_string(),
+            name => match symbol(name) {
+                Some(symbol) => symbol.to_string(),
+                // Keep unknown commands with their argument as LaTeX
+                None if self.peek() == Some('{') => {
+                    format!("\\{}{{{}}}", name, self.argument())
+                }
+                None => format!("\\{} ", name),
+            },
+        }
+    }
+}
+
+/// Parenthesize a compound operand of `/` or `√`
+fn group(s: &str) -> String {
+    let s = s.trim();
+    if s.chars().count() > 1 && !s.chars().all(char::is_alphanumeric) {
+        format!("({})", s)
+    } else {
+        s.to_string()
+    }
+}
+
+/// Add a combining accent after each character
+fn accent(s: &str, mark: char) -> String {
+    s.chars().flat_map(|c| [c, mark]).collect()
+}
+
+/// Render a sub- or superscript with Unicode script characters when all of them
+/// exist, and with an explicit `^`/`_` marker otherwise
+fn script(arg: &str, map: fn(char) -> Option<char>, marker: char) -> String {
+    let arg: String = arg.chars().filter(|c| !c.is_whitespace()).collect();
+    if arg.chars().all(|c| c == '′') {
+        return arg;
+    }
+    if let Some(mapped) = arg.chars().map(map).collect::<Option<String>>()
+        && !mapped.is_empty()
+    {
+        return mapped;
+    }
+    if arg.chars().count() == 1 {
+        format!("{}{}", marker, arg)
+    } else {
+        format!("{}({})", marker, arg)
+    }
+}
+
+fn superscript(c: char) -> Option<char> {
+    Some(match c {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        '+' => '⁺',
+        '-' | '−' => '⁻',
+        '=' => '⁼',
+        '(' => '⁽',
+        ')' => '⁾',
+        'a' => 'ᵃ',
+        'b' => 'ᵇ',
+        'c' => 'ᶜ',
+        'd' => 'ᵈ',
+        'e' => 'ᵉ',
+        'f' => 'ᶠ',
+        'g' => 'ᵍ',
+        'h' => 'ʰ',
+        'i' => 'ⁱ',
+        'j' => 'ʲ',
+        'k' => 'ᵏ',
+        'l' => 'ˡ',
+        'm' => 'ᵐ',
+        'n' => 'ⁿ',
+        'o' => 'ᵒ',
+        'p' => 'ᵖ',
+        'r' => 'ʳ',
+        's' => 'ˢ',
+        't' => 'ᵗ',
+        'u' => 'ᵘ',
+        'v' => 'ᵛ',
+        'w' => 'ʷ',
+        'x' => 'ˣ',
+        'y' => 'ʸ',
+        'z' => 'ᶻ',
+        'T' => 'ᵀ',
+        _ => return None,
+    })
+}
+
+fn subscript(c: char) -> Option<char> {
+    Some(match c {
+        '0' => '₀',
+        '1' => '₁',
+        '2' => '₂',
+        '3' => '₃',
+        '4' => '₄',
+        '5' => '₅',
+        '6' => '₆',
+        '7' => '₇',
+        '8' => '₈',
+        '9' => '₉',
+        '+' => '₊',
+        '-' | '−' => '₋',
+        '=' => '₌',
+        '(' => '₍',
+        ')' => '₎',
+        'a' => 'ₐ',
+        'e' => 'ₑ',
+        'h' => 'ₕ',
+        'i' => 'ᵢ',
+        'j' => 'ⱼ',
+        'k' => 'ₖ',
+        'l' => 'ₗ',
+        'm' => 'ₘ',
+        'n' => 'ₙ',
+        'o' => 'ₒ',
+        'p' => 'ₚ',
+        'r' => 'ᵣ',
+        's' => 'ₛ',
+        't' => 'ₜ',
+        'u' => 'ᵤ',
+        'v' => 'ᵥ',
+        'x' => 'ₓ',
+        _ => return None,
+    })
+}
+
+/// Unicode for symbol commands
+fn symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        // Greek letters
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" => "ϵ",
+        "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "vartheta" => "ϑ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "varpi" => "ϖ",
+        "rho" => "ρ",
+        "varrho" => "ϱ",
+        "sigma" => "σ",
+        "varsigma" => "ς",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" => "ϕ",
+        "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Upsilon" => "Υ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        // Big operators
+        "sum" => "∑",
+        "prod" => "∏",
+        "coprod" => "∐",
+        "int" => "∫",
+        "iint" => "∬",
+        "oint" => "∮",
+        "bigcup" => "⋃",
+        "bigcap" => "⋂",
+        // Binary operators
+        "times" => "×",
+        "cdot" => "·",
+        "ast" => "∗",
+        "star" => "⋆",
+        "circ" => "∘",
+        "bullet" => "•",
+        "pm" => "±",
+        "mp" => "∓",
+        "div" => "÷",
+        "cup" => "∪",
+        "cap" => "∩",
+        "setminus" => "∖",
+        "wedge" | "land" => "∧",
+        "vee" | "lor" => "∨",
+        "oplus" => "⊕",
+        "otimes" => "⊗",
+        // Relations
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "ll" => "≪",
+        "gg" => "≫",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "simeq" => "≃",
+        "cong" => "≅",
+        "propto" => "∝",
+        "in" => "∈",
+        "notin" => "∉",
+        "ni" => "∋",
+        "subset" => "⊂",
+        "subseteq" => "⊆",
+        "supset" => "⊃",
+        "supseteq" => "⊇",
+        "mid" => "|",
+        "perp" => "⊥",
+        "parallel" => "∥",
+        // Arrows
+        "to" | "rightarrow" => "→",
+        "leftarrow" | "gets" => "←",
+        "leftrightarrow" => "↔",
+        "Rightarrow" | "implies" => "⇒",
+        "Leftarrow" => "⇐",
+        "Leftrightarrow" | "iff" => "⇔",
+        "mapsto" => "↦",
+        // Other symbols
+        "infty" => "∞",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "forall" => "∀",
+        "exists" => "∃",
+        "neg" | "lnot" => "¬",
+        "emptyset" | "varnothing" => "∅",
+        "ell" => "ℓ",
+        "hbar" => "ℏ",
+        "Re" => "ℜ",
+        "Im" => "ℑ",
+        "prime" => "′",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "vdots" => "⋮",
+        "ddots" => "⋱",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "vert" => "|",
+        "Vert" | "|" => "‖",
+        "lbrace" => "{",
+        "rbrace" => "}",
+        // Function names
+        "log" => "log",
+        "ln" => "ln",
+        "exp" => "exp",
+        "sin" => "sin",
+        "cos" => "cos",
+        "tan" => "tan",
+        "arcsin" => "arcsin",
+        "arccos" => "arccos",
+        "arctan" => "arctan",
+        "sinh" => "sinh",
+        "cosh" => "cosh",
+        "tanh" => "tanh",
+        "max" => "max",
+        "min" => "min",
+        "sup" => "sup",
+        "inf" => "inf",
+        "lim" => "lim",
+        "det" => "det",
+        "arg" => "arg",
+        "Pr" => "Pr",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_greek_and_fractions() {
+        assert_eq!(latex_to_unicode(r"\frac{\alpha}{\beta}"), "α/β");
+        assert_eq!(latex_to_unicode(r"\frac{x + 1}{2n}"), "(x + 1)/2n");
+        assert_eq!(latex_to_unicode(r"\sqrt{n}"), "√n");
+        assert_eq!(latex_to_unicode(r"\sqrt[3]{x+y}"), "∛(x+y)");
+    }
+
+    #[test]
+    fn test_scripts() {
+        assert_eq!(latex_to_unicode(r"\sum_{i=1}^n x_i^2"), "∑ᵢ₌₁ⁿ xᵢ²");
+        assert_eq!(latex_to_unicode(r"e^{-\lambda}"), "e^(-λ)");
+        assert_eq!(latex_to_unicode(r"\sigma^2_{XY}"), "σ²_(XY)");
+        assert_eq!(latex_to_unicode(r"f'(x)"), "f′(x)");
+    }
+
+    #[test]
+    fn test_operators_and_spacing() {
+        assert_eq!(
+            latex_to_unicode(r"\mu \pm 2\sigma, \; p \leq 0.05"),
+            "μ ± 2σ, p ≤ 0.05"
+        );
+        assert_eq!(
+            latex_to_unicode(r"\left( \hat{\theta} - \theta \right)"),
+            "( θ̂ - θ )"
+        );
+        assert_eq!(latex_to_unicode(r"\mathrm{Var}(X)"), "Var(X)");
+        assert_eq!(latex_to_unicode(r"\{a, b\}"), "{a, b}");
+    }
+
+    #[test]
+    fn test_unknown_command_kept() {
+        assert_eq!(latex_to_unicode(r"\mathcal{L}(\theta)"), r"\mathcal{L}(θ)");
+        assert_eq!(latex_to_unicode(r"\foo x"), r"\foo x");
+    }
+}
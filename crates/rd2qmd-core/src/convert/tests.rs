@@ -1,6 +1,7 @@
 use super::*;
 use rd_parser::parse;
 use rd2qmd_mdast::mdast_to_qmd;
+use std::sync::Arc;
 
 #[test]
 fn test_simple_conversion() {
@@ -95,10 +96,10 @@ fn test_internal_link_unresolved_becomes_inline_code() {
     // When topic is not in alias_map and no fallback URL, it becomes inline code
     let doc = parse("\\title{T}\n\\description{See \\link{other_func}}").unwrap();
     let options = RdToMdastOptions {
-        link_extension: Some("qmd".to_string()),
-        alias_map: None,
-        unresolved_link_url: None,
-        external_package_urls: None,
+        link_resolver: Arc::new(DefaultLinkResolver {
+            extension: Some("qmd".to_string()),
+            ..Default::default()
+        }),
         exec_dontrun: false,
         exec_donttest: false,
         quarto_code_blocks: true,
@@ -131,10 +132,11 @@ fn test_internal_link_with_fallback_url() {
     // When topic is not in alias_map but fallback URL is set, use it
     let doc = parse("\\title{T}\n\\description{See \\link{vector}}").unwrap();
     let options = RdToMdastOptions {
-        link_extension: Some("qmd".to_string()),
-        alias_map: None,
-        unresolved_link_url: Some("https://rdrr.io/r/base/{topic}.html".to_string()),
-        external_package_urls: None,
+        link_resolver: Arc::new(DefaultLinkResolver {
+            extension: Some("qmd".to_string()),
+            unresolved_url: Some("https://rdrr.io/r/base/{topic}.html".to_string()),
+            ..Default::default()
+        }),
         exec_dontrun: false,
         exec_donttest: false,
         quarto_code_blocks: true,
@@ -165,7 +167,7 @@ fn test_internal_link_with_fallback_url() {
 #[test]
 fn test_internal_link_without_extension() {
     let doc = parse("\\title{T}\n\\description{See \\link{other_func}}").unwrap();
-    let options = RdToMdastOptions::default(); // No link_extension
+    let options = RdToMdastOptions::default(); // No link extension
     let mdast = rd_to_mdast_with_options(&doc, &options);
 
     // Should be inline code, not a link
@@ -192,10 +194,10 @@ fn test_internal_link_without_extension() {
 fn test_external_link_without_url_becomes_inline_code() {
     let doc = parse("\\title{T}\n\\description{See \\link[dplyr]{filter}}").unwrap();
     let options = RdToMdastOptions {
-        link_extension: Some("qmd".to_string()),
-        alias_map: None,
-        unresolved_link_url: None,
-        external_package_urls: None,
+        link_resolver: Arc::new(DefaultLinkResolver {
+            extension: Some("qmd".to_string()),
+            ..Default::default()
+        }),
         exec_dontrun: false,
         exec_donttest: false,
         quarto_code_blocks: true,
@@ -236,10 +238,11 @@ fn test_external_link_with_url_becomes_hyperlink() {
     );
 
     let options = RdToMdastOptions {
-        link_extension: Some("qmd".to_string()),
-        alias_map: None,
-        unresolved_link_url: None,
-        external_package_urls: Some(external_urls),
+        link_resolver: Arc::new(DefaultLinkResolver {
+            extension: Some("qmd".to_string()),
+            external_package_urls: external_urls,
+            ..Default::default()
+        }),
         exec_dontrun: false,
         exec_donttest: false,
         quarto_code_blocks: true,
@@ -281,10 +284,11 @@ fn test_external_link_with_topic_in_package() {
     );
 
     let options = RdToMdastOptions {
-        link_extension: Some("qmd".to_string()),
-        alias_map: None,
-        unresolved_link_url: None,
-        external_package_urls: Some(external_urls),
+        link_resolver: Arc::new(DefaultLinkResolver {
+            extension: Some("qmd".to_string()),
+            external_package_urls: external_urls,
+            ..Default::default()
+        }),
         exec_dontrun: false,
         exec_donttest: false,
         quarto_code_blocks: true,
@@ -323,10 +327,11 @@ fn test_alias_resolution() {
     alias_map.insert("DataFrame".to_string(), "pl__DataFrame".to_string());
 
     let options = RdToMdastOptions {
-        link_extension: Some("qmd".to_string()),
-        alias_map: Some(alias_map),
-        unresolved_link_url: None,
-        external_package_urls: None,
+        link_resolver: Arc::new(DefaultLinkResolver {
+            extension: Some("qmd".to_string()),
+            alias_map: Arc::new(alias_map),
+            ..Default::default()
+        }),
         exec_dontrun: false,
         exec_donttest: false,
         quarto_code_blocks: true,
@@ -354,6 +359,64 @@ fn test_alias_resolution() {
     );
 }
 
+#[test]
+fn test_custom_link_resolver() {
+    use std::sync::Mutex;
+
+    /// Links every topic to a site and records the requests
+    #[derive(Debug, Default)]
+    struct Recorder {
+        requests: Mutex<Vec<(Option<String>, String)>>,
+    }
+
+    impl LinkResolver for Recorder {
+        fn resolve(&self, package: Option<&str>, topic: &str) -> Option<ResolvedLink> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push((package.map(str::to_string), topic.to_string()));
+            Some(ResolvedLink {
+                url: format!(
+                    "https://example.org/{}/{}",
+                    package.unwrap_or("self"),
+                    topic
+                ),
+                title: Some(format!("Topic {}", topic)),
+            })
+        }
+    }
+
+    let doc = parse(
+        "\\title{T}\n\\description{See \\link{helper}, \\link[dplyr]{filter} and \\linkS4class{Foo}.}",
+    )
+    .unwrap();
+    let recorder = Arc::new(Recorder::default());
+    let options = RdToMdastOptions {
+        link_resolver: recorder.clone(),
+        ..Default::default()
+    };
+    let output = mdast_to_qmd(
+        &rd_to_mdast_with_options(&doc, &options),
+        &Default::default(),
+    );
+
+    assert!(output.contains("[`helper`](https://example.org/self/helper \"Topic helper\")"));
+    assert!(
+        output.contains("[`dplyr::filter`](https://example.org/dplyr/filter \"Topic filter\")")
+    );
+    assert!(
+        output.contains("[`Foo-class`](https://example.org/self/Foo-class \"Topic Foo-class\")")
+    );
+    assert_eq!(
+        *recorder.requests.lock().unwrap(),
+        vec![
+            (None, "helper".to_string()),
+            (Some("dplyr".to_string()), "filter".to_string()),
+            (None, "Foo-class".to_string()),
+        ]
+    );
+}
+
 #[test]
 fn test_examples_comes_last() {
     // Custom sections should come before Examples (pkgdown convention)
@@ -416,10 +479,11 @@ fn test_code_wrapping_link_preserves_link() {
     );
 
     let options = RdToMdastOptions {
-        link_extension: Some("qmd".to_string()),
-        alias_map: Some(alias_map),
-        unresolved_link_url: None,
-        external_package_urls: None,
+        link_resolver: Arc::new(DefaultLinkResolver {
+            extension: Some("qmd".to_string()),
+            alias_map: Arc::new(alias_map),
+            ..Default::default()
+        }),
         exec_dontrun: false,
         exec_donttest: false,
         quarto_code_blocks: true,
@@ -1246,17 +1310,39 @@ Use a flag instead.}
 
     // Verify that lifecycle badge alt text is preserved
     assert!(
-        qmd.contains("[![[Experimental]]"),
+        qmd.contains(r"[![\[Experimental\]]"),
         "Expected lifecycle badge with [Experimental] alt text"
     );
     assert!(
-        qmd.contains("[![[Deprecated]]"),
+        qmd.contains(r"[![\[Deprecated\]]"),
         "Expected lifecycle badge with [Deprecated] alt text"
     );
 
     insta::assert_snapshot!(qmd);
 }
 
+#[test]
+fn test_arguments_grid_table_escaping_snapshot() {
+    // Markdown and Pandoc syntax in grid table cells is escaped, and inline code
+    // starting with `r ` is not run as inline R code
+    let rd = r#"
+\name{test}
+\title{Test Function}
+\arguments{
+\item{x}{Costs $5 for *all*, see \code{r Sys.time()} and @hadley.}
+}
+"#;
+    let doc = parse(rd).unwrap();
+    let options = RdToMdastOptions {
+        arguments_format: ArgumentsFormat::GridTable,
+        ..Default::default()
+    };
+    let mdast = rd_to_mdast_with_options(&doc, &options);
+    let qmd = mdast_to_qmd(&mdast, &rd2qmd_mdast::WriterOptions::default());
+    assert!(qmd.contains(r"Costs \$5 for \*all\*, see `` r Sys.time() `` and \@hadley."));
+    insta::assert_snapshot!(qmd);
+}
+
 // ============================================================================
 // Integration tests for \figure tag conversion
 // ============================================================================
@@ -1297,9 +1383,9 @@ See the logo: \figure{Rlogo.svg}{options: width=100 alt="R logo image"}
     let mdast = rd_to_mdast(&doc);
     let qmd = mdast_to_qmd(&mdast, &rd2qmd_mdast::WriterOptions::default());
 
-    // Should contain ![R logo image](Rlogo.svg)
+    // Should contain ![R logo image](Rlogo.svg), with the width as an attribute
     assert!(
-        qmd.contains("![R logo image](Rlogo.svg)"),
+        qmd.contains(r#"![R logo image](Rlogo.svg){width="100"}"#),
         "Expected expert form alt text 'R logo image', got:\n{qmd}"
     );
 }
@@ -1435,6 +1521,23 @@ fn test_extract_alt_from_attrs_empty_alt() {
     );
 }
 
+#[test]
+fn test_extract_attr_from_attrs_bare_values() {
+    assert_eq!(
+        Converter::extract_attr_from_attrs("width=100 height='50'", "width"),
+        Some("100".to_string())
+    );
+    assert_eq!(
+        Converter::extract_attr_from_attrs("width=100 height='50'", "height"),
+        Some("50".to_string())
+    );
+    // Whole attribute names only
+    assert_eq!(
+        Converter::extract_attr_from_attrs("data-width=100", "width"),
+        None
+    );
+}
+
 #[test]
 fn test_extract_alt_from_attrs_empty_string() {
     // Empty string should return None
@@ -1604,3 +1707,529 @@ The \abbr{HTML} and \abbr{Rd} formats are supported.
     let qmd = mdast_to_qmd(&mdast, &rd2qmd_mdast::WriterOptions::default());
     insta::assert_snapshot!(qmd);
 }
+
+#[test]
+fn test_note_and_warning_callouts() {
+    let rd = r#"
+\name{test}
+\title{Callout Test}
+\description{Text.}
+\note{Be careful.}
+\section{Warning}{Not thread-safe.}
+\section{Implementation}{Details.}
+"#;
+    let doc = parse(rd).unwrap();
+    let options = RdToMdastOptions {
+        callouts: true,
+        ..Default::default()
+    };
+    let mdast = rd_to_mdast_with_options(&doc, &options);
+    let qmd = mdast_to_qmd(&mdast, &rd2qmd_mdast::WriterOptions::default());
+    insta::assert_snapshot!(qmd);
+
+    // Without callouts, sections stay plain headings
+    let mdast = rd_to_mdast(&doc);
+    assert!(!mdast.children.iter().any(|n| matches!(n, Node::Div(_))));
+}
+
+#[test]
+fn test_out_html() {
+    let rd = r#"
+\name{test}
+\title{Raw HTML Test}
+\description{
+\if{html}{\out{<details><summary>Show more</summary>}}
+Hidden text.
+\if{html}{\out{</details>}}
+
+Jump to \out{<a id="method-new"></a>}here.
+}
+"#;
+    let doc = parse(rd).unwrap();
+    let writer_options = rd2qmd_mdast::WriterOptions::default();
+
+    // Raw HTML blocks and inlines for Quarto
+    let qmd = mdast_to_qmd(&rd_to_mdast(&doc), &writer_options);
+    insta::assert_snapshot!("out_html_raw", qmd);
+
+    // Translated to mdast for targets without raw HTML
+    let options = RdToMdastOptions {
+        translate_html: true,
+        ..Default::default()
+    };
+    let mdast = rd_to_mdast_with_options(&doc, &options);
+    let md = mdast_to_qmd(&mdast, &writer_options);
+    insta::assert_snapshot!("out_html_translated", md);
+}
+
+#[test]
+fn test_rd_format_matches() {
+    assert!(RdFormat::Html.matches("html"));
+    assert!(!RdFormat::Html.matches("text"));
+    assert!(RdFormat::Latex.matches("html, latex"));
+    assert!(RdFormat::Text.matches("!html"));
+    assert!(!RdFormat::Html.matches("!html"));
+    assert!(RdFormat::Example.matches("TRUE"));
+    assert!(!RdFormat::Example.matches("FALSE"));
+    assert!(!RdFormat::Html.matches(""));
+}
+
+#[test]
+fn test_conditionals_for_target_format() {
+    let rd = r#"
+\name{test}
+\title{Conditional Test}
+\description{
+Rendered for \if{html,latex}{HTML or LaTeX}\ifelse{text}{text}{another format}.
+\if{!html}{
+\itemize{
+\item Not HTML.
+}
+}
+}
+"#;
+    let doc = parse(rd).unwrap();
+    let writer_options = rd2qmd_mdast::WriterOptions::default();
+
+    let html = mdast_to_qmd(&rd_to_mdast(&doc), &writer_options);
+    assert!(html.contains("Rendered for HTML or LaTeXanother format."));
+    assert!(!html.contains("Not HTML"));
+
+    let options = RdToMdastOptions {
+        rd_format: RdFormat::Text,
+        ..Default::default()
+    };
+    let text = mdast_to_qmd(&rd_to_mdast_with_options(&doc, &options), &writer_options);
+    assert!(text.contains("Rendered for text."));
+    // Block content in an active branch becomes a real list
+    assert!(text.contains("- Not HTML."));
+}
+
+#[test]
+fn test_math_formats() {
+    let rd = r#"
+\name{test}
+\title{Math Test}
+\description{
+The ratio \eqn{\frac{\alpha}{\beta}}{alpha/beta} and \eqn{x_i^2}.
+\deqn{\sum_{i=1}^n x_i}
+}
+"#;
+    let doc = parse(rd).unwrap();
+    let convert = |math| {
+        let options = RdToMdastOptions {
+            math,
+            ..Default::default()
+        };
+        mdast_to_qmd(
+            &rd_to_mdast_with_options(&doc, &options),
+            &rd2qmd_mdast::WriterOptions::default(),
+        )
+    };
+
+    let latex = convert(MathFormat::Latex);
+    assert!(latex.contains(r"$\frac{\alpha}{\beta}$"));
+    assert!(latex.contains(r"$$"));
+
+    let ascii = convert(MathFormat::Ascii);
+    assert!(ascii.contains("The ratio alpha/beta and xᵢ²."));
+    assert!(ascii.contains("∑ᵢ₌₁ⁿ xᵢ"));
+
+    let unicode = convert(MathFormat::Unicode);
+    assert!(unicode.contains("The ratio α/β and xᵢ²."));
+    assert!(!unicode.contains('$'));
+}
+
+#[test]
+fn test_tabular_header_and_format() {
+    let rd = r#"
+\name{test}
+\title{Table Test}
+\description{
+\tabular{lr}{
+  a \tab 1 \cr
+  b \tab \itemize{\item x \item y} \cr
+}
+}
+"#;
+    let doc = parse(rd).unwrap();
+    let convert = |tabular_format, table_header| {
+        let options = RdToMdastOptions {
+            tabular_format,
+            table_header,
+            ..Default::default()
+        };
+        mdast_to_qmd(
+            &rd_to_mdast_with_options(&doc, &options),
+            &rd2qmd_mdast::WriterOptions::default(),
+        )
+    };
+
+    // Lists in cells switch to a grid table, aligned on the header separator
+    let auto = convert(TabularFormat::Auto, TableHeader::FirstRow);
+    assert!(auto.contains("+:==+=====:+"));
+    assert!(auto.contains("| - x "));
+
+    // Headerless grid table carries the alignment on the top border
+    let empty = convert(TabularFormat::GridTable, TableHeader::Empty);
+    assert!(empty.starts_with("# Table Test") && empty.contains("\n+:--+-----:+\n| a |"));
+    assert!(!empty.contains('='));
+
+    // Pipe tables flatten lists and take a numbered header
+    let pipe = convert(TabularFormat::PipeTable, TableHeader::Synthetic);
+    assert!(pipe.contains("Column 1"));
+    assert!(pipe.contains("Column 2"));
+    assert!(pipe.contains("<br>"));
+}
+
+#[test]
+fn test_tabular_alignment_diagnostic() {
+    let doc = parse("\\title{T}\n\\description{\\tabular{lcr}{a \\tab b \\cr c \\tab d}}").unwrap();
+    let (root, diagnostics) = rd_to_mdast_with_diagnostics(&doc, &RdToMdastOptions::default());
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(
+        diagnostics[0]
+            .message
+            .contains("has 3 columns, but the table has 2")
+    );
+
+    // Extra format letters are dropped
+    let table = root.children.iter().find_map(|n| match n {
+        Node::Table(t) => Some(t),
+        _ => None,
+    });
+    assert_eq!(table.unwrap().align.len(), 2);
+
+    let doc = parse("\\title{T}\n\\description{\\tabular{lr}{a \\tab b \\cr}}").unwrap();
+    let (_, diagnostics) = rd_to_mdast_with_diagnostics(&doc, &RdToMdastOptions::default());
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_tabular_grid_cells_are_escaped() {
+    let rd = r#"
+\name{test}
+\title{Table Test}
+\description{
+\tabular{ll}{
+  \code{r x} \tab Costs $5 for *all* \cr
+  @key \tab \itemize{\item a_b_ \item 2 * 3} \cr
+}
+}
+"#;
+    let doc = parse(rd).unwrap();
+    let options = RdToMdastOptions {
+        tabular_format: TabularFormat::GridTable,
+        ..Default::default()
+    };
+    let md = mdast_to_qmd(
+        &rd_to_mdast_with_options(&doc, &options),
+        &rd2qmd_mdast::WriterOptions::default(),
+    );
+    assert!(
+        md.contains("| `` r x `` | Costs \\$5 for \\*all\\* |"),
+        "{md}"
+    );
+    assert!(md.contains("| \\@key "), "{md}");
+    assert!(md.contains("- a_b\\_"), "{md}");
+    assert!(md.contains("- 2 \\* 3"), "{md}");
+}
+
+#[test]
+fn test_value_and_format_items() {
+    let rd = r#"
+\name{test}
+\title{Items Test}
+\value{
+A list with components:
+\item{x}{The \code{x} value.}
+\item{y}{The y value.}
+}
+\format{
+\describe{
+  \item{\code{mpg}}{Miles per gallon}
+}
+}
+"#;
+    let doc = parse(rd).unwrap();
+    let convert = |value_items, format_items, arguments_format| {
+        let options = RdToMdastOptions {
+            value_items,
+            format_items,
+            arguments_format,
+            ..Default::default()
+        };
+        mdast_to_qmd(
+            &rd_to_mdast_with_options(&doc, &options),
+            &rd2qmd_mdast::WriterOptions::default(),
+        )
+    };
+
+    let deflist = convert(
+        ItemsFormat::DefinitionList,
+        ItemsFormat::DefinitionList,
+        ArgumentsFormat::GridTable,
+    );
+    assert!(deflist.contains("`x`\n:   The `x` value."));
+    assert!(deflist.contains("`mpg`\n:   Miles per gallon"));
+
+    let grid = convert(
+        ItemsFormat::Table,
+        ItemsFormat::List,
+        ArgumentsFormat::GridTable,
+    );
+    assert!(grid.contains("| Component | Description"));
+    assert!(grid.contains("| `y`       | The y value."));
+    assert!(grid.contains("- **`mpg`**: Miles per gallon"));
+
+    let pipe = convert(
+        ItemsFormat::List,
+        ItemsFormat::Table,
+        ArgumentsFormat::PipeTable,
+    );
+    assert!(pipe.contains("- **`x`**: The `x` value."));
+    assert!(pipe.contains("| Name | Description |"));
+    assert!(pipe.contains("| `mpg` | Miles per gallon |"));
+}
+
+#[test]
+fn test_value_and_format_grid_cells_are_escaped() {
+    let rd = r#"
+\name{test}
+\title{Items Test}
+\value{
+\item{x}{Costs $5 for *all*, see \code{r Sys.time()}.}
+}
+\format{
+\describe{
+  \item{\code{r_x}}{Weight ~ height, by @owner.}
+}
+}
+"#;
+    let doc = parse(rd).unwrap();
+    let options = RdToMdastOptions {
+        value_items: ItemsFormat::Table,
+        format_items: ItemsFormat::Table,
+        arguments_format: ArgumentsFormat::GridTable,
+        ..Default::default()
+    };
+    let md = mdast_to_qmd(
+        &rd_to_mdast_with_options(&doc, &options),
+        &rd2qmd_mdast::WriterOptions::default(),
+    );
+    assert!(
+        md.contains(r"| `x`       | Costs \$5 for \*all\*, see `` r Sys.time() ``. |"),
+        "{md}"
+    );
+    assert!(
+        md.contains(r"| `r_x` | Weight \~ height, by \@owner. |"),
+        "{md}"
+    );
+}
+
+#[test]
+fn test_pkgdown_slugs() {
+    assert_eq!(make_slug("See Also"), "see-also");
+    assert_eq!(
+        make_slug("Global options (advanced)"),
+        "global-options-advanced"
+    );
+    assert_eq!(argument_id("na.rm"), "arg-na-rm");
+    assert_eq!(argument_id("x, y"), "arg-x-y");
+    assert_eq!(argument_id("..."), "arg-dots");
+}
+
+#[test]
+fn test_heading_ids() {
+    let rd = r#"
+\name{test}
+\title{Anchors}
+\usage{test(x, na.rm = FALSE)}
+\arguments{
+\item{x}{An object.}
+\item{na.rm}{Remove missing values?}
+}
+\section{Global options}{Some options.}
+"#;
+    let doc = parse(rd).unwrap();
+    let convert = |heading_ids, arguments_format| {
+        let options = RdToMdastOptions {
+            heading_ids,
+            arguments_format,
+            ..Default::default()
+        };
+        mdast_to_qmd(
+            &rd_to_mdast_with_options(&doc, &options),
+            &rd2qmd_mdast::WriterOptions::default(),
+        )
+    };
+
+    let grid = convert(true, ArgumentsFormat::GridTable);
+    assert!(grid.contains("# Anchors\n"));
+    assert!(grid.contains("## Usage {#ref-usage}"));
+    assert!(grid.contains("## Arguments {#arguments}"));
+    assert!(grid.contains("## Global options {#global-options}"));
+    assert!(grid.contains("| []{#arg-na-rm}`na.rm` |"));
+
+    let pipe = convert(true, ArgumentsFormat::PipeTable);
+    assert!(pipe.contains("| []{#arg-x}`x` |"));
+
+    let plain = convert(false, ArgumentsFormat::PipeTable);
+    assert!(plain.contains("## Arguments\n"));
+    assert!(!plain.contains("{#"));
+}
+
+#[test]
+fn test_chunk_labels_and_options() {
+    let rd = r#"
+\name{plot.data}
+\title{Plot}
+\examples{
+\dontshow{set.seed(1)}
+x <- 1
+\dontrun{stop()}
+\donttest{plot(x)}
+}
+"#;
+    let doc = parse(rd).unwrap();
+    let mut chunk_options = ChunkOptions {
+        global: vec![
+            ("error".to_string(), ChunkValue::Bool(true)),
+            ("fig-width".to_string(), ChunkValue::Number(7.0)),
+        ],
+        ..Default::default()
+    };
+    chunk_options.topics.insert(
+        "plot.data".to_string(),
+        vec![
+            ("fig-width".to_string(), ChunkValue::Number(4.5)),
+            (
+                "fig-cap".to_string(),
+                ChunkValue::String("A \"plot\"".to_string()),
+            ),
+        ],
+    );
+    let options = RdToMdastOptions {
+        chunk_labels: true,
+        chunk_options,
+        ..Default::default()
+    };
+    let codes: Vec<String> = rd_to_mdast_with_options(&doc, &options)
+        .children
+        .into_iter()
+        .filter_map(|node| match node {
+            Node::Code(code) => Some(code.value),
+            _ => None,
+        })
+        .collect();
+
+    let directives = "#| error: true\n#| fig-width: 4.5\n#| fig-cap: \"A \\\"plot\\\"\"\n";
+    assert_eq!(
+        codes[0],
+        format!("#| label: ex-plot-data-1\n{directives}#| include: false\nset.seed(1)")
+    );
+    assert_eq!(
+        codes[1],
+        format!("#| label: ex-plot-data-2\n{directives}x <- 1")
+    );
+    // \dontrun{} code is not executed, so it gets no chunk options
+    assert_eq!(codes[2], "stop()");
+    assert_eq!(
+        codes[3],
+        format!("#| label: ex-plot-data-3\n{directives}plot(x)")
+    );
+
+    // Plain Markdown code blocks cannot carry chunk options
+    let options = RdToMdastOptions {
+        quarto_code_blocks: false,
+        ..options
+    };
+    let md = mdast_to_qmd(
+        &rd_to_mdast_with_options(&doc, &options),
+        &rd2qmd_mdast::WriterOptions::default(),
+    );
+    assert!(!md.contains("#|"));
+}
+
+#[test]
+fn test_markdown_syntax_in_rd_text_is_escaped() {
+    let rd = r#"
+\name{test}
+\title{Escaping}
+\description{
+Costs $5 for *all* [items], see \code{r Sys.time()}.
+}
+"#;
+    let doc = parse(rd).unwrap();
+    let qmd = mdast_to_qmd(&rd_to_mdast(&doc), &rd2qmd_mdast::WriterOptions::default());
+    assert!(qmd.contains(r"Costs \$5 for \*all\* \[items], see `` r Sys.time() ``."));
+}
+
+#[test]
+fn test_typography() {
+    let rd = r#"
+\name{test}
+\title{Typography}
+\description{
+Use \sQuote{a} or \dQuote{b}, see \enc{Jöreskog}{Joreskog} and more\dots
+}
+"#;
+    let doc = parse(rd).unwrap();
+    let convert = |typography| {
+        let options = RdToMdastOptions {
+            typography,
+            ..Default::default()
+        };
+        let writer_options = rd2qmd_mdast::WriterOptions {
+            typography,
+            ..Default::default()
+        };
+        mdast_to_qmd(&rd_to_mdast_with_options(&doc, &options), &writer_options)
+    };
+
+    let unicode = convert(Typography::Unicode);
+    assert!(
+        unicode
+            .contains("Use \u{2018}a\u{2019} or \u{201C}b\u{201D}, see Jöreskog and more\u{2026}")
+    );
+    let ascii = convert(Typography::Ascii);
+    assert!(ascii.contains("Use 'a' or \"b\", see Joreskog and more..."));
+    let html = convert(Typography::Html);
+    assert!(html.contains("Use &lsquo;a&rsquo; or &ldquo;b&rdquo;, see Jöreskog and more&hellip;"));
+}
+
+#[test]
+fn test_typography_in_grid_table_cells() {
+    let rd = r#"
+\name{test}
+\title{Typography}
+\arguments{
+\item{x}{A \sQuote{quoted} word, pages 1–2\dots}
+}
+"#;
+    let doc = parse(rd).unwrap();
+    let convert = |typography| {
+        let options = RdToMdastOptions {
+            arguments_format: ArgumentsFormat::GridTable,
+            typography,
+            ..Default::default()
+        };
+        let writer_options = rd2qmd_mdast::WriterOptions {
+            typography,
+            ..Default::default()
+        };
+        mdast_to_qmd(&rd_to_mdast_with_options(&doc, &options), &writer_options)
+    };
+
+    let ascii = convert(Typography::Ascii);
+    assert!(
+        ascii.contains("| A 'quoted' word, pages 1--2... |"),
+        "{ascii}"
+    );
+    let html = convert(Typography::Html);
+    assert!(
+        html.contains("| A &lsquo;quoted&rsquo; word, pages 1&ndash;2&hellip; |"),
+        "{html}"
+    );
+}
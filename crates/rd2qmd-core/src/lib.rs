@@ -3,7 +3,7 @@
 //! This crate provides:
 //! - Rd file parsing (via rd-parser crate)
 //! - Rd AST to mdast conversion
-//! - mdast to Quarto Markdown output (via rd2qmd-mdast crate)
+//! - mdast to Quarto Markdown, HTML and Jupyter notebook output (via rd2qmd-mdast crate)
 //! - Single-file conversion function
 //!
 //! # API Guide
@@ -64,6 +64,8 @@ pub mod convert;
 pub mod roxygen_code_block;
 
 use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
 
 // Re-export rd-parser types
 pub use rd_parser::{RdDocument, RdNode, RdSection, SectionTag, parse};
@@ -87,9 +89,150 @@ pub enum ConvertError {
 pub use rd_parser::parse_roxygen_comments;
 
 // Re-export rd2qmd-mdast types
-pub use rd2qmd_mdast::{Frontmatter, RdMetadata, WriterOptions, mdast_to_qmd};
+pub use rd2qmd_mdast::{
+    Frontmatter, MarkdownFlavor, Node, RdMetadata, Root, TextOptions, Typography, WriterOptions,
+    latex_document, mdast_to_html, mdast_to_ipynb, mdast_to_json, mdast_to_latex, mdast_to_man,
+    mdast_to_pandoc_json, mdast_to_qmd, mdast_to_text, topic_label, write_qmd, write_qmd_io,
+};
+
+pub use convert::{
+    ArgumentsFormat, ChunkOptions, ChunkValue, DefaultLinkResolver, Diagnostic, ItemsFormat,
+    LinkResolver, MathFormat, RdFormat, RdToMdastOptions, ResolvedLink, TableHeader, TabularFormat,
+    make_slug, rd_to_mdast, rd_to_mdast_with_diagnostics, rd_to_mdast_with_options,
+};
 
-pub use convert::{ArgumentsFormat, RdToMdastOptions, rd_to_mdast, rd_to_mdast_with_options};
+// ============================================================================
+// Output formats
+// ============================================================================
+
+/// Output document format
+///
+/// Selects the writer used to render the converted mdast tree.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum WriterFormat {
+    /// Markdown (Quarto, R Markdown or standard Markdown, see [`WriterOptions`])
+    #[default]
+    Markdown,
+    /// R Markdown (see [`MarkdownFlavor::RMarkdown`])
+    Rmd,
+    /// Plain Markdown for non-Pandoc renderers (see [`MarkdownFlavor::CommonMark`])
+    CommonMark,
+    /// HTML with Rd2HTML/pkgdown-style markup
+    Html,
+    /// Jupyter notebook for the R kernel, with examples as code cells
+    Ipynb,
+    /// LaTeX, with longtable tables, native math and hyperref links between topics
+    Latex,
+    /// roff man page (section 3)
+    Man,
+    /// unist-compatible mdast JSON, for remark and other mdast tools
+    MdastJson,
+    /// Pandoc JSON AST, for `pandoc -f json` and Quarto filters
+    PandocJson,
+    /// MDX for Docusaurus (see [`MarkdownFlavor::Mdx`])
+    Mdx,
+    /// Wrapped plain text for terminals, optionally ANSI-styled
+    Text(TextOptions),
+}
+
+impl WriterFormat {
+    /// Whether this format can render Pandoc grid tables
+    pub fn supports_grid_tables(&self) -> bool {
+        matches!(self, WriterFormat::Markdown | WriterFormat::Rmd)
+    }
+
+    /// Whether this format executes example code
+    ///
+    /// Only then is `\dontshow{}` code emitted (as hidden, executed code).
+    pub fn supports_code_execution(&self) -> bool {
+        matches!(
+            self,
+            WriterFormat::Markdown | WriterFormat::Rmd | WriterFormat::Ipynb
+        )
+    }
+
+    /// Rd format whose `\if`/`\ifelse` branches are rendered for this format
+    ///
+    /// Markdown, notebooks and the JSON ASTs end up as HTML; LaTeX uses the
+    /// `latex` branch, and man pages and plain text the `text` branch.
+    pub fn rd_format(&self) -> RdFormat {
+        match self {
+            WriterFormat::Latex => RdFormat::Latex,
+            WriterFormat::Man | WriterFormat::Text(_) => RdFormat::Text,
+            WriterFormat::Markdown
+            | WriterFormat::Rmd
+            | WriterFormat::CommonMark
+            | WriterFormat::Mdx
+            | WriterFormat::Html
+            | WriterFormat::Ipynb
+            | WriterFormat::MdastJson
+            | WriterFormat::PandocJson => RdFormat::Html,
+        }
+    }
+
+    /// Render an mdast tree in this format
+    pub fn write(&self, root: &Root, options: &WriterOptions) -> String {
+        match self {
+            WriterFormat::Markdown => mdast_to_qmd(root, options),
+            WriterFormat::Rmd => {
+                let options = WriterOptions {
+                    flavor: MarkdownFlavor::RMarkdown,
+                    ..options.clone()
+                };
+                mdast_to_qmd(root, &options)
+            }
+            WriterFormat::CommonMark => {
+                let options = WriterOptions {
+                    flavor: MarkdownFlavor::CommonMark,
+                    ..options.clone()
+                };
+                mdast_to_qmd(root, &options)
+            }
+            WriterFormat::Mdx => {
+                let options = WriterOptions {
+                    flavor: MarkdownFlavor::Mdx,
+                    ..options.clone()
+                };
+                mdast_to_qmd(root, &options)
+            }
+            WriterFormat::Html => mdast_to_html(root, options),
+            WriterFormat::Ipynb => mdast_to_ipynb(root, options),
+            WriterFormat::Latex => mdast_to_latex(root, options),
+            WriterFormat::Man => mdast_to_man(root, options),
+            WriterFormat::MdastJson => mdast_to_json(root, options),
+            WriterFormat::PandocJson => mdast_to_pandoc_json(root, options),
+            WriterFormat::Text(text_options) => mdast_to_text(root, text_options),
+        }
+    }
+
+    /// Render an mdast tree in this format to an [`io::Write`] sink
+    ///
+    /// Markdown formats are written as they are rendered, so wrap unbuffered
+    /// sinks in an [`io::BufWriter`]; other formats are rendered to a string
+    /// first.
+    pub fn write_to(
+        &self,
+        root: &Root,
+        options: &WriterOptions,
+        out: impl io::Write,
+    ) -> io::Result<()> {
+        let flavor = match self {
+            WriterFormat::Markdown => options.flavor,
+            WriterFormat::Rmd => MarkdownFlavor::RMarkdown,
+            WriterFormat::CommonMark => MarkdownFlavor::CommonMark,
+            WriterFormat::Mdx => MarkdownFlavor::Mdx,
+            _ => {
+                let mut out = out;
+                return out.write_all(self.write(root, options).as_bytes());
+            }
+        };
+        let options = WriterOptions {
+            flavor,
+            ..options.clone()
+        };
+        write_qmd_io(root, &options, out)
+    }
+}
 
 // ============================================================================
 // Option structs for single-file conversion
@@ -102,6 +245,9 @@ pub struct FrontmatterOptions {
     pub enabled: bool,
     /// Output pkgdown-style pagetitle (`<title> — <name>`)
     pub pagetitle: bool,
+    /// Document id, such as the Rd file basename, used as the MDX `id` and `slug`
+    /// (default: the `\name`)
+    pub id: Option<String>,
 }
 
 /// Code block execution options
@@ -113,6 +259,10 @@ pub struct CodeExecutionOptions {
     pub exec_dontrun: bool,
     /// Make \donttest{} code executable (default: true)
     pub exec_donttest: bool,
+    /// Label executable example chunks `ex-<topic>-1`, ... (default: true)
+    pub chunk_labels: bool,
+    /// Chunk options for executable example chunks
+    pub chunk_options: ChunkOptions,
 }
 
 impl Default for CodeExecutionOptions {
@@ -121,6 +271,8 @@ impl Default for CodeExecutionOptions {
             quarto_code_blocks: true,
             exec_dontrun: false,
             exec_donttest: true,
+            chunk_labels: true,
+            chunk_options: ChunkOptions::default(),
         }
     }
 }
@@ -136,6 +288,24 @@ pub struct LinkOptions {
     pub alias_map: Option<HashMap<String, String>>,
     /// External package URL map: package name -> reference documentation base URL
     pub external_package_urls: Option<HashMap<String, String>>,
+    /// Custom link resolver, used instead of the options above
+    pub resolver: Option<Arc<dyn LinkResolver>>,
+}
+
+impl LinkOptions {
+    /// Link resolver for these options
+    fn link_resolver(&self) -> Arc<dyn LinkResolver> {
+        match &self.resolver {
+            Some(resolver) => Arc::clone(resolver),
+            None => Arc::new(DefaultLinkResolver {
+                extension: Some(self.output_extension.clone()),
+                alias_map: Arc::new(self.alias_map.clone().unwrap_or_default()),
+                titles: Arc::default(),
+                unresolved_url: self.unresolved_url.clone(),
+                external_package_urls: self.external_package_urls.clone().unwrap_or_default(),
+            }),
+        }
+    }
 }
 
 /// Options for single-file Rd to QMD conversion
@@ -149,6 +319,26 @@ pub struct RdConvertOptions {
     pub links: LinkOptions,
     /// Arguments section table format
     pub arguments_format: ArgumentsFormat,
+    /// Rendering of `\value` components
+    pub value_items: ItemsFormat,
+    /// Rendering of `\describe` items in the Format section
+    pub format_items: ItemsFormat,
+    /// `\tabular` table format
+    pub tabular_format: TabularFormat,
+    /// Header row for `\tabular` tables
+    pub table_header: TableHeader,
+    /// Output document format
+    pub format: WriterFormat,
+    /// Render the Note section and warning-like sections as Quarto callouts
+    pub callouts: bool,
+    /// Translate `\out{}` HTML to mdast instead of keeping it as raw HTML
+    pub translate_html: bool,
+    /// Give section headings and arguments pkgdown-compatible anchor ids
+    pub heading_ids: bool,
+    /// Rendering of `\eqn` and `\deqn` equations
+    pub math: MathFormat,
+    /// Quotes, dashes and ellipses in text
+    pub typography: Typography,
 }
 
 // ============================================================================
@@ -157,8 +347,9 @@ pub struct RdConvertOptions {
 
 /// Extract plain text from Rd nodes
 ///
-/// This function recursively extracts text content from Rd nodes,
-/// handling common inline markup like `\code{}`, `\emph{}`, and `\strong{}`.
+/// This function recursively extracts text content from Rd nodes, handling
+/// inline markup like `\code{}`, `\emph{}`, `\strong{}`, links, quotes and
+/// special characters. Lists, tables and code blocks are skipped.
 ///
 /// # Example
 ///
@@ -173,16 +364,137 @@ pub struct RdConvertOptions {
 /// ```
 pub fn extract_text(nodes: &[RdNode]) -> String {
     let mut result = String::new();
+    push_plain_text(&mut result, nodes);
+    result.trim().to_string()
+}
+
+/// Link title for a topic: its `\title{}` and the first sentence of its description
+///
+/// Used as the title (tooltip) of links to the topic. The description sentence is
+/// left out when it only repeats the title, as roxygen2 does for topics without
+/// a description.
+///
+/// # Example
+///
+/// ```
+/// use rd2qmd_core::{parse, topic_link_title};
+///
+/// let doc = parse(r#"\name{filter}\title{Keep rows}
+/// \description{Keep rows that match a condition. See \link{slice} for positions.}"#).unwrap();
+/// assert_eq!(
+///     topic_link_title(&doc).as_deref(),
+///     Some("Keep rows: Keep rows that match a condition.")
+/// );
+/// ```
+pub fn topic_link_title(doc: &RdDocument) -> Option<String> {
+    let section_text = |tag| {
+        doc.get_section(&tag)
+            .map(|section| {
+                extract_text(&section.content)
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default()
+    };
+
+    let title = section_text(SectionTag::Title);
+    if title.is_empty() {
+        return None;
+    }
+    let description = section_text(SectionTag::Description);
+    let sentence = first_sentence(&description);
+    if sentence.is_empty() || sentence.trim_end_matches('.') == title.trim_end_matches('.') {
+        Some(title)
+    } else {
+        Some(format!("{}: {}", title, sentence))
+    }
+}
+
+/// Append the plain text of inline Rd markup, including link text and special
+/// characters; lists, tables and code blocks are skipped
+fn push_plain_text(out: &mut String, nodes: &[RdNode]) {
     for node in nodes {
         match node {
-            RdNode::Text(s) => result.push_str(s),
-            RdNode::Code(children) | RdNode::Emph(children) | RdNode::Strong(children) => {
-                result.push_str(&extract_text(children));
+            RdNode::Text(s) => out.push_str(s),
+            RdNode::Paragraph(children) => {
+                push_plain_text(out, children);
+                out.push(' ');
+            }
+            RdNode::Code(children)
+            | RdNode::Emph(children)
+            | RdNode::Strong(children)
+            | RdNode::Samp(children)
+            | RdNode::Dfn(children)
+            | RdNode::Kbd(children)
+            | RdNode::File(children)
+            | RdNode::Href { text: children, .. } => push_plain_text(out, children),
+            RdNode::SQuote(children) => {
+                out.push('\'');
+                push_plain_text(out, children);
+                out.push('\'');
             }
+            RdNode::DQuote(children) => {
+                out.push('"');
+                push_plain_text(out, children);
+                out.push('"');
+            }
+            RdNode::Link {
+                package,
+                topic,
+                text,
+            } => match (text, package) {
+                (Some(text), _) => push_plain_text(out, text),
+                (None, Some(pkg)) => out.push_str(&format!("{}::{}", pkg, topic)),
+                (None, None) => out.push_str(topic),
+            },
+            RdNode::LinkS4Class { classname, .. } => out.push_str(classname),
+            RdNode::Verb(s)
+            | RdNode::Url(s)
+            | RdNode::Email(s)
+            | RdNode::Pkg(s)
+            | RdNode::Var(s)
+            | RdNode::Acronym(s)
+            | RdNode::Abbr(s)
+            | RdNode::Cite(s)
+            | RdNode::Option(s)
+            | RdNode::Env(s)
+            | RdNode::Command(s) => out.push_str(s),
+            RdNode::Enc { encoded, .. } => out.push_str(encoded),
+            RdNode::Eqn { latex, ascii } => out.push_str(ascii.as_deref().unwrap_or(latex)),
+            RdNode::Special(ch) => out.push_str(convert::special_char_to_string(*ch)),
+            RdNode::LineBreak | RdNode::Tab => out.push(' '),
             _ => {}
         }
     }
-    result.trim().to_string()
+}
+
+/// Abbreviations whose period doesn't end a sentence
+const ABBREVIATIONS: [&str; 5] = ["e.g.", "i.e.", "etc.", "vs.", "cf."];
+
+/// First sentence of text: up to a `.`, `!` or `?` that is followed by a
+/// capitalized word, so that abbreviations like "e.g." don't end it
+fn first_sentence(text: &str) -> &str {
+    for (i, c) in text.char_indices() {
+        let sentence = &text[..=i];
+        let last_word = sentence
+            .rsplit(' ')
+            .next()
+            .unwrap_or(sentence)
+            .trim_start_matches(['(', '[']);
+        if matches!(c, '.' | '!' | '?')
+            && !ABBREVIATIONS
+                .iter()
+                .any(|a| last_word.eq_ignore_ascii_case(a))
+            && text[i + 1..]
+                .strip_prefix(' ')
+                .and_then(|next| next.chars().next())
+                .is_some_and(|next| !next.is_lowercase())
+        {
+            return sentence;
+        }
+    }
+    text
 }
 
 /// Extract Rd metadata (lifecycle, aliases, keywords, concepts, source_files) from a document
@@ -356,6 +668,12 @@ impl RdConverter {
         self
     }
 
+    /// Set the document id, used as the MDX `id` and `slug` (default: the `\name`)
+    pub fn document_id(mut self, id: impl Into<String>) -> Self {
+        self.options.frontmatter.id = Some(id.into());
+        self
+    }
+
     /// Enable or disable Quarto {r} code blocks (default: true)
     pub fn quarto_code_blocks(mut self, enabled: bool) -> Self {
         self.options.code.quarto_code_blocks = enabled;
@@ -374,6 +692,18 @@ impl RdConverter {
         self
     }
 
+    /// Label executable example chunks `ex-<topic>-1`, `ex-<topic>-2`, ... (default: true)
+    pub fn chunk_labels(mut self, enabled: bool) -> Self {
+        self.options.code.chunk_labels = enabled;
+        self
+    }
+
+    /// Set chunk options for executable example chunks, such as `error: true`
+    pub fn chunk_options(mut self, options: ChunkOptions) -> Self {
+        self.options.code.chunk_options = options;
+        self
+    }
+
     /// Set the fallback URL for unresolved links
     pub fn unresolved_link_url(mut self, url: impl Into<String>) -> Self {
         self.options.links.unresolved_url = Some(url.into());
@@ -392,12 +722,87 @@ impl RdConverter {
         self
     }
 
+    /// Resolve links with a custom [`LinkResolver`] instead of the alias map,
+    /// fallback URL and external package URLs
+    pub fn link_resolver(mut self, resolver: impl LinkResolver + 'static) -> Self {
+        self.options.links.resolver = Some(Arc::new(resolver));
+        self
+    }
+
     /// Set the arguments section format
     pub fn arguments_format(mut self, format: ArgumentsFormat) -> Self {
         self.options.arguments_format = format;
         self
     }
 
+    /// Set how `\value` components are rendered (default: definition list)
+    pub fn value_items(mut self, format: ItemsFormat) -> Self {
+        self.options.value_items = format;
+        self
+    }
+
+    /// Set how `\describe` items in the Format section are rendered (default: definition list)
+    pub fn format_items(mut self, format: ItemsFormat) -> Self {
+        self.options.format_items = format;
+        self
+    }
+
+    /// Set the `\tabular` table format (default: pipe table unless cells contain lists or code)
+    pub fn tabular_format(mut self, format: TabularFormat) -> Self {
+        self.options.tabular_format = format;
+        self
+    }
+
+    /// Set the header row of `\tabular` tables (default: first row)
+    pub fn table_header(mut self, header: TableHeader) -> Self {
+        self.options.table_header = header;
+        self
+    }
+
+    /// Set the output document format (default: Markdown)
+    pub fn format(mut self, format: WriterFormat) -> Self {
+        self.options.format = format;
+        self
+    }
+
+    /// Enable or disable Quarto callouts for Note and warning-like sections (default: false)
+    pub fn callouts(mut self, enabled: bool) -> Self {
+        self.options.callouts = enabled;
+        self
+    }
+
+    /// Translate `\out{}` HTML to mdast instead of writing it as raw HTML (default: false)
+    ///
+    /// Enable this for targets that cannot pass raw HTML through, such as plain
+    /// Markdown, MDX, LaTeX and man pages.
+    pub fn translate_html(mut self, enabled: bool) -> Self {
+        self.options.translate_html = enabled;
+        self
+    }
+
+    /// Add pkgdown-compatible anchor ids to section headings and arguments (default: false)
+    ///
+    /// Sections get `{#arguments}`, `{#ref-examples}`, ... and arguments `{#arg-na-rm}`,
+    /// so links into pkgdown sites keep working.
+    pub fn heading_ids(mut self, enabled: bool) -> Self {
+        self.options.heading_ids = enabled;
+        self
+    }
+
+    /// Set how equations are rendered (default: LaTeX math)
+    pub fn math(mut self, math: MathFormat) -> Self {
+        self.options.math = math;
+        self
+    }
+
+    /// Set how quotes, dashes and ellipses are written (default: Unicode)
+    ///
+    /// ASCII also uses the fallback text of `\enc{}`; HTML writes entities.
+    pub fn typography(mut self, typography: Typography) -> Self {
+        self.options.typography = typography;
+        self
+    }
+
     /// Set all options at once
     pub fn with_options(mut self, options: RdConvertOptions) -> Self {
         self.options = options;
@@ -408,12 +813,18 @@ impl RdConverter {
     pub fn convert(self) -> Result<String, ConvertError> {
         convert_rd_content(&self.content, &self.options)
     }
+
+    /// Execute the conversion, also returning diagnostics about the Rd source
+    pub fn convert_with_diagnostics(self) -> Result<(String, Vec<Diagnostic>), ConvertError> {
+        convert_rd_content_with_diagnostics(&self.content, &self.options)
+    }
 }
 
 /// Convert Rd content to Quarto Markdown
 ///
 /// This is the main entry point for single-file conversion. It parses the Rd content,
-/// converts it to mdast, and outputs Quarto Markdown with optional frontmatter.
+/// converts it to mdast, and outputs Quarto Markdown with optional frontmatter
+/// (or another document format, see [`RdConvertOptions::format`]).
 ///
 /// For a more flexible API, consider using [`RdConverter`] builder.
 ///
@@ -429,7 +840,7 @@ impl RdConverter {
 /// "#;
 ///
 /// let options = RdConvertOptions {
-///     frontmatter: FrontmatterOptions { enabled: true, pagetitle: true },
+///     frontmatter: FrontmatterOptions { enabled: true, pagetitle: true, ..Default::default() },
 ///     code: CodeExecutionOptions::default(),
 ///     links: LinkOptions { output_extension: "qmd".to_string(), ..Default::default() },
 ///     ..Default::default()
@@ -443,22 +854,51 @@ pub fn convert_rd_content(
     content: &str,
     options: &RdConvertOptions,
 ) -> Result<String, ConvertError> {
+    convert_rd_content_with_diagnostics(content, options).map(|(output, _)| output)
+}
+
+/// Convert Rd content to Quarto Markdown, also returning diagnostics
+///
+/// Like [`convert_rd_content`], but reports problems in the Rd source that did
+/// not stop the conversion (see [`Diagnostic`]).
+pub fn convert_rd_content_with_diagnostics(
+    content: &str,
+    options: &RdConvertOptions,
+) -> Result<(String, Vec<Diagnostic>), ConvertError> {
     let doc = parse(content).map_err(|e| ConvertError::Parse(e.to_string()))?;
 
     // Build converter options
     let converter_options = RdToMdastOptions {
-        link_extension: Some(options.links.output_extension.clone()),
-        alias_map: options.links.alias_map.clone(),
-        unresolved_link_url: options.links.unresolved_url.clone(),
-        external_package_urls: options.links.external_package_urls.clone(),
+        link_resolver: options.links.link_resolver(),
         exec_dontrun: options.code.exec_dontrun,
         exec_donttest: options.code.exec_donttest,
-        quarto_code_blocks: options.code.quarto_code_blocks,
-        arguments_format: options.arguments_format.clone(),
+        quarto_code_blocks: options.format.supports_code_execution()
+            && options.code.quarto_code_blocks,
+        chunk_labels: options.code.chunk_labels,
+        chunk_options: options.code.chunk_options.clone(),
+        arguments_format: if options.format.supports_grid_tables() {
+            options.arguments_format.clone()
+        } else {
+            ArgumentsFormat::PipeTable
+        },
+        tabular_format: if options.format.supports_grid_tables() {
+            options.tabular_format
+        } else {
+            TabularFormat::PipeTable
+        },
+        table_header: options.table_header,
+        value_items: options.value_items,
+        format_items: options.format_items,
+        callouts: options.callouts,
+        translate_html: options.translate_html,
+        heading_ids: options.heading_ids,
+        rd_format: options.format.rd_format(),
+        math: options.math,
+        typography: options.typography,
     };
 
     // Convert to mdast
-    let mdast = rd_to_mdast_with_options(&doc, &converter_options);
+    let (mdast, diagnostics) = rd_to_mdast_with_diagnostics(&doc, &converter_options);
 
     // Extract title and name for frontmatter
     let title = doc
@@ -493,15 +933,19 @@ pub fn convert_rd_content(
                 title,
                 pagetitle,
                 format: None,
+                id: options.frontmatter.id.clone().or_else(|| name.clone()),
+                name,
                 metadata: Some(metadata),
             })
         } else {
             None
         },
         quarto_code_blocks: options.code.quarto_code_blocks,
+        typography: options.typography,
+        ..Default::default()
     };
 
-    Ok(mdast_to_qmd(&mdast, &writer_options))
+    Ok((options.format.write(&mdast, &writer_options), diagnostics))
 }
 
 #[cfg(test)]
@@ -532,6 +976,43 @@ mod tests {
         assert_eq!(extract_text(&nodes), "nested");
     }
 
+    #[test]
+    fn test_topic_link_title() {
+        let title = |rd: &str| topic_link_title(&parse(rd).unwrap());
+
+        assert_eq!(
+            title(
+                "\\name{f}\\title{Apply \\code{f}}\n\\description{\n\\code{f()} applies \\link{g}, e.g. to\nvectors. More text.\n}"
+            )
+            .as_deref(),
+            Some("Apply f: f() applies g, e.g. to vectors.")
+        );
+        // A description that repeats the title is left out
+        assert_eq!(
+            title("\\name{f}\\title{Do things}\\description{Do things.}").as_deref(),
+            Some("Do things")
+        );
+        assert_eq!(
+            title("\\name{f}\\title{Do things}").as_deref(),
+            Some("Do things")
+        );
+        assert_eq!(title("\\name{f}\\description{No title.}"), None);
+    }
+
+    #[test]
+    fn test_first_sentence() {
+        assert_eq!(
+            first_sentence("Compute e.g. the mean of x. More text."),
+            "Compute e.g. the mean of x."
+        );
+        assert_eq!(
+            first_sentence("Summaries (e.g. Mean, SD, etc. Or vs. Median). More."),
+            "Summaries (e.g. Mean, SD, etc. Or vs. Median)."
+        );
+        assert_eq!(first_sentence("Really? Yes."), "Really?");
+        assert_eq!(first_sentence("No period"), "No period");
+    }
+
     #[test]
     fn test_convert_rd_content_basic() {
         let content = r#"\name{test}
@@ -542,6 +1023,7 @@ mod tests {
             frontmatter: FrontmatterOptions {
                 enabled: true,
                 pagetitle: false,
+                ..Default::default()
             },
             links: LinkOptions {
                 output_extension: "qmd".to_string(),
@@ -566,6 +1048,7 @@ mod tests {
             frontmatter: FrontmatterOptions {
                 enabled: true,
                 pagetitle: true,
+                ..Default::default()
             },
             links: LinkOptions {
                 output_extension: "qmd".to_string(),
@@ -588,6 +1071,7 @@ mod tests {
             frontmatter: FrontmatterOptions {
                 enabled: false,
                 pagetitle: false,
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -645,6 +1129,28 @@ mod tests {
         assert!(result.contains("pagetitle: \"My Function — myFunc\""));
     }
 
+    #[test]
+    fn test_rd_converter_mdx_id() {
+        let content = r#"\name{myFunc}
+\title{My Function}
+\description{Does something.}
+"#;
+        let result = RdConverter::new(content)
+            .frontmatter(true)
+            .format(WriterFormat::Mdx)
+            .convert()
+            .unwrap();
+        assert!(result.contains("id: \"myFunc\"\nsidebar_label: \"myFunc\"\nslug: \"myFunc\"\n"));
+
+        let result = RdConverter::new(content)
+            .frontmatter(true)
+            .document_id("my-func")
+            .format(WriterFormat::Mdx)
+            .convert()
+            .unwrap();
+        assert!(result.contains("id: \"my-func\"\nsidebar_label: \"myFunc\"\nslug: \"my-func\"\n"));
+    }
+
     #[test]
     fn test_rd_converter_output_extension() {
         let content = r#"\name{foo}
@@ -847,6 +1353,28 @@ Sys.sleep(10)
         assert!(result_pipe.contains("| Argument |"));
     }
 
+    #[test]
+    fn test_rd_converter_html_format() {
+        let content = r#"\name{html_test}
+\title{HTML Test}
+\description{Compare \code{x < y}.}
+\arguments{
+\item{x}{The x value.}
+}
+\examples{
+\dontshow{set.seed(1)}
+html_test(1)
+}
+"#;
+        let result = RdConverter::new(content)
+            .output_extension("html")
+            .format(WriterFormat::Html)
+            .convert()
+            .unwrap();
+
+        insta::assert_snapshot!(result);
+    }
+
     #[test]
     fn test_rd_converter_with_options() {
         let content = r#"\name{opts}
@@ -857,19 +1385,32 @@ Sys.sleep(10)
             frontmatter: FrontmatterOptions {
                 enabled: true,
                 pagetitle: true,
+                ..Default::default()
             },
             code: CodeExecutionOptions {
                 quarto_code_blocks: false,
                 exec_dontrun: true,
                 exec_donttest: false,
+                ..Default::default()
             },
             links: LinkOptions {
                 output_extension: "md".to_string(),
                 unresolved_url: Some("https://fallback.com/{topic}".to_string()),
                 alias_map: None,
                 external_package_urls: None,
+                resolver: None,
             },
             arguments_format: ArgumentsFormat::PipeTable,
+            format: WriterFormat::Markdown,
+            callouts: false,
+            translate_html: false,
+            heading_ids: false,
+            typography: Typography::Unicode,
+            math: MathFormat::Latex,
+            tabular_format: TabularFormat::Auto,
+            table_header: TableHeader::FirstRow,
+            value_items: ItemsFormat::DefinitionList,
+            format_items: ItemsFormat::DefinitionList,
         };
 
         let result = RdConverter::new(content)
@@ -0,0 +1,281 @@
+//! Markdown escaping for text nodes
+//!
+//! Text in the mdast tree is literal, but many characters mean something to
+//! Markdown: `*` and `_` start emphasis, `[` a link, `<` raw HTML, an autolink or
+//! a Quarto shortcode (`{{< ... >}}`), `#` at the start of a line a heading, and
+//! Pandoc adds `$` math, `^`/`~` super- and subscripts and `@` citations. What
+//! needs escaping depends on where the text is written ([`TextContext`]) and on
+//! the [`MarkdownFlavor`].
+//!
+//! Typographic quotes, dashes and ellipses in text are written according to a
+//! [`Typography`] profile.
+
+use crate::writer::MarkdownFlavor;
+
+/// Where text is written, which decides the characters that need escaping
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextContext {
+    /// Paragraphs, list items and definition lists, where a line can start a block
+    #[default]
+    Block,
+    /// Heading text, where `#` could close the heading and `{...}` add attributes
+    Heading,
+    /// Pipe table cell, where `|` ends the cell
+    TableCell,
+    /// Link text, image descriptions and bracketed spans, where `]` ends the text
+    LinkText,
+}
+
+/// Rendering of typographic quotes, dashes and ellipses in text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Typography {
+    /// ASCII only: `'...'`, `"..."`, `--`, `---` and `...`; `\enc{}` uses its
+    /// ASCII fallback
+    Ascii,
+    /// Unicode characters (default): ‘...’, “...”, –, — and …, as R renders
+    /// `\sQuote` and `\dQuote` with `useFancyQuotes`
+    #[default]
+    Unicode,
+    /// HTML entities: `&lsquo;`, `&ldquo;`, `&ndash;`, `&mdash;` and `&hellip;`
+    Html,
+}
+
+/// Write typographic quotes, dashes and ellipses in text with a [`Typography`] profile
+///
+/// Unicode text is returned unchanged; the ASCII and HTML profiles replace these
+/// characters with ASCII sequences or HTML entities.
+///
+/// # Example
+///
+/// ```
+/// use rd2qmd_mdast::{Typography, typeset};
+///
+/// assert_eq!(typeset("\u{2018}a\u{2019} \u{2013} b", Typography::Ascii), "'a' -- b");
+/// assert_eq!(typeset("a\u{2026}", Typography::Html), "a&hellip;");
+/// ```
+pub fn typeset(text: &str, typography: Typography) -> String {
+    if typography == Typography::Unicode {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        let replacement = match (typography, c) {
+            (Typography::Ascii, '\u{2018}' | '\u{2019}') => "'",
+            (Typography::Ascii, '\u{201C}' | '\u{201D}') => "\"",
+            (Typography::Ascii, '\u{2013}') => "--",
+            (Typography::Ascii, '\u{2014}') => "---",
+            (Typography::Ascii, '\u{2026}') => "...",
+            (Typography::Html, '\u{2018}') => "&lsquo;",
+            (Typography::Html, '\u{2019}') => "&rsquo;",
+            (Typography::Html, '\u{201C}') => "&ldquo;",
+            (Typography::Html, '\u{201D}') => "&rdquo;",
+            (Typography::Html, '\u{2013}') => "&ndash;",
+            (Typography::Html, '\u{2014}') => "&mdash;",
+            (Typography::Html, '\u{2026}') => "&hellip;",
+            _ => {
+                out.push(c);
+                continue;
+            }
+        };
+        out.push_str(replacement);
+    }
+    out
+}
+
+/// Backslash-escape Markdown syntax in literal text
+///
+/// `line_start` tells whether the text starts a line of a block, where list
+/// markers, `#`, `>` and `:` would be read as block syntax. Lines after a
+/// newline in the text are treated the same way in [`TextContext::Block`].
+///
+/// # Example
+///
+/// ```
+/// use rd2qmd_mdast::{MarkdownFlavor, TextContext, escape_text};
+///
+/// let escaped = escape_text("# of *items*", TextContext::Block, MarkdownFlavor::Pandoc, true);
+/// assert_eq!(escaped, r"\# of \*items\*");
+/// ```
+pub fn escape_text(
+    text: &str,
+    context: TextContext,
+    flavor: MarkdownFlavor,
+    line_start: bool,
+) -> String {
+    let pandoc = flavor != MarkdownFlavor::Mdx;
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut at_line_start = line_start && context == TextContext::Block;
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let prev = i.checked_sub(1).map(|j| chars[j]);
+        let next = chars.get(i + 1).copied();
+
+        if at_line_start && !matches!(c, ' ' | '\t') {
+            at_line_start = false;
+            if matches!(c, '#' | '>' | '-' | '+' | '=' | ':') {
+                out.push('\\');
+                out.push(c);
+                i += 1;
+                continue;
+            }
+            // `1.` and `1)` start an ordered list
+            if c.is_ascii_digit() {
+                let end = (i..chars.len())
+                    .find(|&j| !chars[j].is_ascii_digit())
+                    .unwrap_or(chars.len());
+                if let Some(&marker @ ('.' | ')')) = chars.get(end) {
+                    out.extend(&chars[i..end]);
+                    out.push('\\');
+                    out.push(marker);
+                    i = end + 1;
+                    continue;
+                }
+            }
+        }
+
+        let escape = match c {
+            '\\' | '`' | '*' | '[' | '<' => true,
+            // Intraword underscores don't start emphasis
+            '_' => !(is_word(prev) && is_word(next)),
+            ']' => context == TextContext::LinkText,
+            '|' => context == TextContext::TableCell,
+            '#' => context == TextContext::Heading,
+            '{' => !pandoc || context == TextContext::Heading,
+            '}' | '>' => !pandoc,
+            // Character references such as `&amp;`
+            '&' => next.is_some_and(|n| n.is_ascii_alphabetic() || n == '#'),
+            '$' | '^' | '~' => pandoc,
+            // Citations (`@key`), but not e-mail addresses
+            '@' => pandoc && !is_word(prev) && next.is_some_and(|n| is_word(Some(n)) || n == '{'),
+            _ => false,
+        };
+        if escape {
+            out.push('\\');
+        }
+        out.push(c);
+        if c == '\n' && context == TextContext::Block {
+            at_line_start = true;
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Whether inline code would be run by knitr or Quarto as inline R code
+///
+/// `` `r x` `` and `` `{r} x` `` are evaluated when the document is rendered.
+pub fn is_inline_r_code(code: &str) -> bool {
+    code.starts_with("r ") || code.starts_with("r#") || code.starts_with("{r}")
+}
+
+fn is_word(c: Option<char>) -> bool {
+    c.is_some_and(char::is_alphanumeric)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(text: &str) -> String {
+        escape_text(text, TextContext::Block, MarkdownFlavor::Pandoc, true)
+    }
+
+    fn inline(text: &str, context: TextContext) -> String {
+        escape_text(text, context, MarkdownFlavor::Pandoc, false)
+    }
+
+    #[test]
+    fn test_emphasis_and_code_characters() {
+        assert_eq!(block("a *b* `c` \\d"), r"a \*b\* \`c\` \\d");
+        assert_eq!(block("use na_rm or _x_"), r"use na_rm or \_x\_");
+    }
+
+    #[test]
+    fn test_line_start_markers() {
+        assert_eq!(block("# of rows"), r"\# of rows");
+        assert_eq!(block("- 1 is negative"), r"\- 1 is negative");
+        assert_eq!(block("+ more"), r"\+ more");
+        assert_eq!(block("> quoted"), r"\> quoted");
+        assert_eq!(block(": term"), r"\: term");
+        assert_eq!(block("2020. A year"), r"2020\. A year");
+        assert_eq!(block("1) first"), r"1\) first");
+        assert_eq!(block("first\n# second"), "first\n\\# second");
+        // Only at the start of a line
+        assert_eq!(
+            inline("a # b - c 1. d", TextContext::Block),
+            "a # b - c 1. d"
+        );
+        assert_eq!(block("10 items"), "10 items");
+    }
+
+    #[test]
+    fn test_links_html_and_shortcodes() {
+        assert_eq!(block("see [x](y)"), r"see \[x](y)");
+        assert_eq!(
+            block("<b> and <https://x.org>"),
+            r"\<b> and \<https://x.org>"
+        );
+        assert_eq!(block("{{< var x >}}"), r"{{\< var x >}}");
+        assert_eq!(block("&amp; & co"), r"\&amp; & co");
+    }
+
+    #[test]
+    fn test_pandoc_extensions() {
+        assert_eq!(block("$5 and $10"), r"\$5 and \$10");
+        assert_eq!(block("2^10^ and H~2~O"), r"2\^10\^ and H\~2\~O");
+        assert_eq!(block("ask @hadley"), r"ask \@hadley");
+        assert_eq!(block("mail a@b.org"), "mail a@b.org");
+    }
+
+    #[test]
+    fn test_contexts() {
+        assert_eq!(inline("a | b", TextContext::TableCell), r"a \| b");
+        assert_eq!(inline("a | b", TextContext::Block), "a | b");
+        assert_eq!(inline("x[1]", TextContext::LinkText), r"x\[1\]");
+        assert_eq!(inline("x[1]", TextContext::Block), r"x\[1]");
+        assert_eq!(
+            inline("Issue #1 {x}", TextContext::Heading),
+            r"Issue \#1 \{x}"
+        );
+        // Block syntax can't start inside a table cell or heading
+        assert_eq!(
+            escape_text("- a", TextContext::TableCell, MarkdownFlavor::Pandoc, true),
+            "- a"
+        );
+    }
+
+    #[test]
+    fn test_mdx() {
+        let mdx = |text| escape_text(text, TextContext::Block, MarkdownFlavor::Mdx, false);
+        assert_eq!(mdx("{x} <y> *z*"), r"\{x\} \<y\> \*z\*");
+        // No Pandoc extensions in MDX
+        assert_eq!(mdx("$5 ^ ~ @x"), "$5 ^ ~ @x");
+    }
+
+    #[test]
+    fn test_typeset() {
+        let text =
+            "\u{2018}a\u{2019} \u{201C}b\u{201D} 1\u{2013}2 x\u{2014}y z\u{2026} M\u{fc}ller";
+        assert_eq!(typeset(text, Typography::Unicode), text);
+        assert_eq!(
+            typeset(text, Typography::Ascii),
+            "'a' \"b\" 1--2 x---y z... M\u{fc}ller"
+        );
+        assert_eq!(
+            typeset(text, Typography::Html),
+            "&lsquo;a&rsquo; &ldquo;b&rdquo; 1&ndash;2 x&mdash;y z&hellip; M\u{fc}ller"
+        );
+    }
+
+    #[test]
+    fn test_inline_r_code() {
+        assert!(is_inline_r_code("r x"));
+        assert!(is_inline_r_code("r#comment"));
+        assert!(is_inline_r_code("{r} 1 + 1"));
+        assert!(!is_inline_r_code("r"));
+        assert!(!is_inline_r_code("rnorm(1)"));
+    }
+}
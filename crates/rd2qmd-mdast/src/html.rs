@@ -0,0 +1,717 @@
+//! mdast to HTML writer
+//!
+//! Converts an mdast tree into HTML. The markup follows R's `Rd2HTML()` and
+//! pkgdown where they agree (`<pre><code class="language-r">`, `<dl>` for
+//! definition lists), so existing reference stylesheets apply. Math is written
+//! as Pandoc-style `math inline`/`math display` spans with `\(...\)` and
+//! `\[...\]` delimiters, which KaTeX and MathJax auto-render pick up.
+//!
+//! Stable classes are hooks for stylesheets: the table of the Arguments section
+//! is `<table class="ref-arguments">`, as in pkgdown, and definition lists (such
+//! as Value components) are `<dl class="ref-definitions">`.
+
+use crate::escape::{Typography, typeset};
+use crate::mdast::{Align, Node, Root};
+use crate::writer::{Frontmatter, WriterOptions};
+
+/// Convert mdast to HTML
+///
+/// Without frontmatter, an HTML fragment is produced for embedding into a site
+/// template. With frontmatter, a standalone page is written whose `<title>` is
+/// the page title (falling back to the title).
+pub fn mdast_to_html(root: &Root, options: &WriterOptions) -> String {
+    let mut writer = HtmlWriter::new(options.typography);
+    writer.write_root(root, options.frontmatter.as_ref());
+    writer.output
+}
+
+/// HTML writer state
+struct HtmlWriter {
+    output: String,
+    /// Rendering of typographic quotes, dashes and ellipses in text
+    typography: Typography,
+    /// Whether the current section is Arguments, whose table gets a class
+    in_arguments: bool,
+}
+
+impl HtmlWriter {
+    fn new(typography: Typography) -> Self {
+        Self {
+            output: String::new(),
+            typography,
+            in_arguments: false,
+        }
+    }
+
+    fn write_root(&mut self, root: &Root, frontmatter: Option<&Frontmatter>) {
+        if let Some(fm) = frontmatter {
+            self.write_head(fm);
+        }
+
+        for node in &root.children {
+            self.write_node(node);
+            self.ensure_newline();
+        }
+
+        if frontmatter.is_some() {
+            self.output.push_str("</main>\n</body>\n</html>\n");
+        }
+    }
+
+    fn write_head(&mut self, fm: &Frontmatter) {
+        self.output.push_str("<!DOCTYPE html>\n<html>\n<head>\n");
+        self.output.push_str("<meta charset=\"utf-8\">\n");
+        self.output
+            .push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+        if let Some(title) = fm.pagetitle.as_ref().or(fm.title.as_ref()) {
+            self.output.push_str("<title>");
+            self.output.push_str(&escape_html(title));
+            self.output.push_str("</title>\n");
+        }
+        if let Some(metadata) = &fm.metadata
+            && !metadata.keywords.is_empty()
+        {
+            self.output.push_str("<meta name=\"keywords\" content=\"");
+            self.output
+                .push_str(&escape_attribute(&metadata.keywords.join(", ")));
+            self.output.push_str("\">\n");
+        }
+        self.output.push_str("</head>\n<body>\n<main>\n");
+    }
+
+    fn write_node(&mut self, node: &Node) {
+        match node {
+            Node::Heading(h) => {
+                self.ensure_newline();
+                if h.depth <= 2 {
+                    self.in_arguments = h.depth == 2
+                        && matches!(h.children.as_slice(), [Node::Text(t)] if t.value == "Arguments");
+                }
+                self.output.push_str(&format!("<h{}", h.depth));
+                self.output.push_str(&html_attributes(&h.attributes));
+                self.output.push('>');
+                self.write_children(&h.children);
+                self.output.push_str(&format!("</h{}>\n", h.depth));
+            }
+            Node::Paragraph(p) => {
+                self.ensure_newline();
+                self.output.push_str("<p>");
+                self.write_children(&p.children);
+                self.output.push_str("</p>\n");
+            }
+            Node::ThematicBreak => {
+                self.ensure_newline();
+                self.output.push_str("<hr>\n");
+            }
+            Node::Blockquote(b) => {
+                self.ensure_newline();
+                self.output.push_str("<blockquote>\n");
+                self.write_children(&b.children);
+                self.ensure_newline();
+                self.output.push_str("</blockquote>\n");
+            }
+            Node::List(l) => self.write_list(l),
+            Node::ListItem(li) => {
+                self.output.push_str("<li>");
+                self.write_flow(&li.children, !li.spread);
+                self.output.push_str("</li>\n");
+            }
+            Node::Code(c) => self.write_code(c),
+            Node::Table(t) => self.write_table(t),
+            Node::TableRow(_) => {}  // Handled by write_table
+            Node::TableCell(_) => {} // Handled by write_table
+            Node::DefinitionList(dl) => {
+                self.ensure_newline();
+                self.output.push_str("<dl class=\"ref-definitions\">\n");
+                self.write_children(&dl.children);
+                self.output.push_str("</dl>\n");
+            }
+            Node::DefinitionTerm(dt) => {
+                self.output.push_str("<dt>");
+                self.write_children(&dt.children);
+                self.output.push_str("</dt>\n");
+            }
+            Node::DefinitionDescription(dd) => {
+                self.output.push_str("<dd>");
+                self.write_flow(&dd.children, true);
+                self.output.push_str("</dd>\n");
+            }
+            Node::Text(t) => self
+                .output
+                .push_str(&typeset(&escape_html(&t.value), self.typography)),
+            Node::Emphasis(e) => {
+                self.output.push_str("<em>");
+                self.write_children(&e.children);
+                self.output.push_str("</em>");
+            }
+            Node::Strong(s) => {
+                self.output.push_str("<strong>");
+                self.write_children(&s.children);
+                self.output.push_str("</strong>");
+            }
+            Node::InlineCode(c) => {
+                self.output.push_str("<code>");
+                self.output.push_str(&escape_html(&c.value));
+                self.output.push_str("</code>");
+            }
+            Node::Break => self.output.push_str("<br>\n"),
+            Node::Link(l) => {
+                self.output.push_str("<a href=\"");
+                self.output.push_str(&escape_attribute(&l.url));
+                self.output.push('"');
+                if let Some(title) = &l.title {
+                    self.output.push_str(" title=\"");
+                    self.output.push_str(&escape_attribute(title));
+                    self.output.push('"');
+                }
+                self.output.push('>');
+                self.write_children(&l.children);
+                self.output.push_str("</a>");
+            }
+            Node::Image(img) => {
+                self.output.push_str("<img src=\"");
+                self.output.push_str(&escape_attribute(&img.url));
+                self.output.push_str("\" alt=\"");
+                self.output.push_str(&escape_attribute(&img.alt));
+                self.output.push('"');
+                if let Some(title) = &img.title {
+                    self.output.push_str(" title=\"");
+                    self.output.push_str(&escape_attribute(title));
+                    self.output.push('"');
+                }
+                // Image attributes (width, height) are img attributes, not data-*
+                let attributes = &img.attributes;
+                if let Some(id) = &attributes.id {
+                    self.output
+                        .push_str(&format!(" id=\"{}\"", escape_attribute(id)));
+                }
+                if !attributes.classes.is_empty() {
+                    self.output.push_str(&format!(
+                        " class=\"{}\"",
+                        escape_attribute(&attributes.classes.join(" "))
+                    ));
+                }
+                for (key, value) in &attributes.attributes {
+                    if is_attribute_name(key) {
+                        self.output
+                            .push_str(&format!(" {}=\"{}\"", key, escape_attribute(value)));
+                    }
+                }
+                self.output.push('>');
+            }
+            Node::Math(m) => {
+                self.ensure_newline();
+                self.output.push_str("<p><span class=\"math display\">\\[");
+                self.output.push_str(&escape_html(m.value.trim()));
+                self.output.push_str("\\]</span></p>\n");
+            }
+            Node::InlineMath(m) => {
+                self.output.push_str("<span class=\"math inline\">\\(");
+                self.output.push_str(&escape_html(&m.value));
+                self.output.push_str("\\)</span>");
+            }
+            Node::Html(h) => self.output.push_str(&h.value),
+            Node::Div(d) => {
+                self.ensure_newline();
+                self.output.push_str("<div");
+                self.output.push_str(&html_attributes(&d.attributes));
+                self.output.push_str(">\n");
+                self.write_children(&d.children);
+                self.ensure_newline();
+                self.output.push_str("</div>\n");
+            }
+            Node::Span(s) => {
+                self.output.push_str("<span");
+                self.output.push_str(&html_attributes(&s.attributes));
+                self.output.push('>');
+                self.write_children(&s.children);
+                self.output.push_str("</span>");
+            }
+            Node::RawBlock(r) if r.format == "html" => {
+                self.ensure_newline();
+                self.output.push_str(r.value.trim_end_matches('\n'));
+                self.output.push('\n');
+            }
+            Node::RawInline(r) if r.format == "html" => self.output.push_str(&r.value),
+            Node::RawBlock(_) | Node::RawInline(_) => {} // Other output formats
+        }
+    }
+
+    fn write_children(&mut self, children: &[Node]) {
+        for child in children {
+            self.write_node(child);
+        }
+    }
+
+    /// Write the content of a list item or definition description.
+    ///
+    /// In tight mode, paragraphs are written without `<p>` wrappers, matching
+    /// how Markdown renders tight lists.
+    fn write_flow(&mut self, children: &[Node], tight: bool) {
+        let single_paragraph = children.len() == 1 && matches!(children[0], Node::Paragraph(_));
+        for child in children {
+            match child {
+                Node::Paragraph(p) if tight || single_paragraph => {
+                    self.write_children(&p.children);
+                }
+                _ if is_block(child) => {
+                    self.ensure_newline();
+                    self.write_node(child);
+                }
+                _ => self.write_node(child),
+            }
+        }
+    }
+
+    fn write_list(&mut self, l: &crate::mdast::List) {
+        self.ensure_newline();
+        let tag = if l.ordered { "ol" } else { "ul" };
+        self.output.push('<');
+        self.output.push_str(tag);
+        if let Some(start) = l.start.filter(|&s| l.ordered && s != 1) {
+            self.output.push_str(&format!(" start=\"{}\"", start));
+        }
+        self.output.push_str(">\n");
+        for child in &l.children {
+            if let Node::ListItem(li) = child {
+                self.output.push_str("<li>");
+                self.write_flow(&li.children, !(l.spread || li.spread));
+                self.output.push_str("</li>\n");
+            }
+        }
+        self.output.push_str(&format!("</{}>\n", tag));
+    }
+
+    fn write_code(&mut self, c: &crate::mdast::Code) {
+        self.ensure_newline();
+        match &c.lang {
+            Some(lang) => {
+                self.output.push_str("<pre><code class=\"language-");
+                self.output.push_str(&escape_attribute(lang));
+                self.output.push_str("\">");
+            }
+            None => self.output.push_str("<pre><code>"),
+        }
+        self.output
+            .push_str(&escape_html(c.value.trim_end_matches('\n')));
+        self.output.push_str("</code></pre>\n");
+    }
+
+    fn write_table(&mut self, t: &crate::mdast::Table) {
+        self.ensure_newline();
+
+        let rows: Vec<&crate::mdast::TableRow> = t
+            .children
+            .iter()
+            .filter_map(|n| {
+                if let Node::TableRow(r) = n {
+                    Some(r)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        if rows.is_empty() {
+            return;
+        }
+
+        if self.in_arguments {
+            self.output.push_str("<table class=\"ref-arguments\">\n");
+        } else {
+            self.output.push_str("<table>\n");
+        }
+        // An all-empty first row (`TableHeader::Empty`) means the table has no header
+        let header = rows[0];
+        let has_header = header
+            .children
+            .iter()
+            .any(|cell| matches!(cell, Node::TableCell(c) if !c.children.is_empty()));
+        if has_header {
+            self.output.push_str("<thead>\n");
+            self.write_table_row(header, &t.align, "th");
+            self.output.push_str("</thead>\n");
+        }
+        let body = &rows[1..];
+        if !body.is_empty() {
+            self.output.push_str("<tbody>\n");
+            for row in body {
+                self.write_table_row(row, &t.align, "td");
+            }
+            self.output.push_str("</tbody>\n");
+        }
+        self.output.push_str("</table>\n");
+    }
+
+    fn write_table_row(
+        &mut self,
+        row: &crate::mdast::TableRow,
+        align: &[Option<Align>],
+        tag: &str,
+    ) {
+        self.output.push_str("<tr>");
+        for (i, cell) in row.children.iter().enumerate() {
+            if let Node::TableCell(c) = cell {
+                self.output.push('<');
+                self.output.push_str(tag);
+                match align.get(i).copied().flatten() {
+                    Some(Align::Left) => self.output.push_str(" style=\"text-align: left;\""),
+                    Some(Align::Center) => self.output.push_str(" style=\"text-align: center;\""),
+                    Some(Align::Right) => self.output.push_str(" style=\"text-align: right;\""),
+                    None => {}
+                }
+                self.output.push('>');
+                self.write_flow(&c.children, true);
+                self.output.push_str(&format!("</{}>", tag));
+            }
+        }
+        self.output.push_str("</tr>\n");
+    }
+
+    fn ensure_newline(&mut self) {
+        if !self.output.is_empty() && !self.output.ends_with('\n') {
+            self.output.push('\n');
+        }
+    }
+}
+
+/// Whether a node is written as a block-level element
+fn is_block(node: &Node) -> bool {
+    matches!(
+        node,
+        Node::Heading(_)
+            | Node::Paragraph(_)
+            | Node::ThematicBreak
+            | Node::Blockquote(_)
+            | Node::List(_)
+            | Node::Code(_)
+            | Node::Table(_)
+            | Node::DefinitionList(_)
+            | Node::Math(_)
+            | Node::Div(_)
+            | Node::RawBlock(_)
+    )
+}
+
+/// Render Pandoc attributes as HTML attributes; custom keys get a `data-` prefix
+fn html_attributes(attributes: &crate::mdast::Attributes) -> String {
+    let mut out = String::new();
+    if let Some(id) = &attributes.id {
+        out.push_str(&format!(" id=\"{}\"", escape_attribute(id)));
+    }
+    if !attributes.classes.is_empty() {
+        out.push_str(&format!(
+            " class=\"{}\"",
+            escape_attribute(&attributes.classes.join(" "))
+        ));
+    }
+    for (key, value) in &attributes.attributes {
+        if !is_attribute_name(key) {
+            continue;
+        }
+        let prefix = if key.starts_with("data-") {
+            ""
+        } else {
+            "data-"
+        };
+        out.push_str(&format!(
+            " {}{}=\"{}\"",
+            prefix,
+            key,
+            escape_attribute(value)
+        ));
+    }
+    out
+}
+
+/// Whether a key can be written as an HTML attribute name as is
+///
+/// Keys with other characters (spaces, quotes, `=`, `>`) could break out of the
+/// tag, so they are dropped.
+fn is_attribute_name(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Escape text content for HTML
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape an attribute value for HTML (double-quoted)
+pub(crate) fn escape_attribute(s: &str) -> String {
+    escape_html(s).replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mdast::*;
+    use crate::writer::RdMetadata;
+
+    fn to_html(children: Vec<Node>) -> String {
+        mdast_to_html(&Root::new(children), &WriterOptions::default())
+    }
+
+    #[test]
+    fn test_heading_and_paragraph() {
+        let html = to_html(vec![
+            Node::heading(2, vec![Node::text("Arguments")]),
+            Node::paragraph(vec![Node::text("Some text.")]),
+        ]);
+        assert_eq!(html, "<h2>Arguments</h2>\n<p>Some text.</p>\n");
+    }
+
+    #[test]
+    fn test_text_escaping() {
+        let html = to_html(vec![Node::paragraph(vec![Node::text("a < b && c > d")])]);
+        assert_eq!(html, "<p>a &lt; b &amp;&amp; c &gt; d</p>\n");
+    }
+
+    #[test]
+    fn test_inline_markup() {
+        let html = to_html(vec![Node::paragraph(vec![
+            Node::emphasis(vec![Node::text("em")]),
+            Node::text(" "),
+            Node::strong(vec![Node::text("strong")]),
+            Node::text(" "),
+            Node::inline_code("x <- 1"),
+        ])]);
+        assert_eq!(
+            html,
+            "<p><em>em</em> <strong>strong</strong> <code>x &lt;- 1</code></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_link_attribute_escaping() {
+        let html = to_html(vec![Node::paragraph(vec![Node::link_with_title(
+            "foo.html?a=1&b=2",
+            "Say \"hi\"",
+            vec![Node::text("foo")],
+        )])]);
+        assert_eq!(
+            html,
+            "<p><a href=\"foo.html?a=1&amp;b=2\" title=\"Say &quot;hi&quot;\">foo</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_code_block() {
+        let html = to_html(vec![Node::code(Some("r".to_string()), "if (x < 1) y")]);
+        assert_eq!(
+            html,
+            "<pre><code class=\"language-r\">if (x &lt; 1) y</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn test_code_block_no_language() {
+        let html = to_html(vec![Node::code(None, "plain")]);
+        assert_eq!(html, "<pre><code>plain</code></pre>\n");
+    }
+
+    #[test]
+    fn test_tight_list() {
+        let html = to_html(vec![Node::list(
+            false,
+            vec![
+                Node::list_item(vec![Node::paragraph(vec![Node::text("one")])]),
+                Node::list_item(vec![Node::paragraph(vec![Node::text("two")])]),
+            ],
+        )]);
+        assert_eq!(html, "<ul>\n<li>one</li>\n<li>two</li>\n</ul>\n");
+    }
+
+    #[test]
+    fn test_ordered_list_start() {
+        let html = to_html(vec![Node::ordered_list_from(
+            3,
+            vec![Node::list_item(vec![Node::paragraph(vec![Node::text(
+                "three",
+            )])])],
+        )]);
+        assert_eq!(html, "<ol start=\"3\">\n<li>three</li>\n</ol>\n");
+    }
+
+    #[test]
+    fn test_table() {
+        let html = to_html(vec![Node::table(
+            vec![Some(Align::Left), Some(Align::Right)],
+            vec![
+                Node::table_row(vec![
+                    Node::table_cell(vec![Node::text("Name")]),
+                    Node::table_cell(vec![Node::text("Value")]),
+                ]),
+                Node::table_row(vec![
+                    Node::table_cell(vec![Node::text("a")]),
+                    Node::table_cell(vec![Node::text("1")]),
+                ]),
+            ],
+        )]);
+        assert_eq!(
+            html,
+            "<table>\n<thead>\n\
+             <tr><th style=\"text-align: left;\">Name</th><th style=\"text-align: right;\">Value</th></tr>\n\
+             </thead>\n<tbody>\n\
+             <tr><td style=\"text-align: left;\">a</td><td style=\"text-align: right;\">1</td></tr>\n\
+             </tbody>\n</table>\n"
+        );
+    }
+
+    #[test]
+    fn test_table_without_header() {
+        let html = to_html(vec![Node::table(
+            vec![None],
+            vec![
+                Node::table_row(vec![Node::table_cell(vec![])]),
+                Node::table_row(vec![Node::table_cell(vec![Node::text("a")])]),
+            ],
+        )]);
+        assert_eq!(
+            html,
+            "<table>\n<tbody>\n<tr><td>a</td></tr>\n</tbody>\n</table>\n"
+        );
+    }
+
+    #[test]
+    fn test_arguments_table_class() {
+        let table = || {
+            Node::table(
+                vec![None, None],
+                vec![
+                    Node::table_row(vec![
+                        Node::table_cell(vec![Node::text("Argument")]),
+                        Node::table_cell(vec![Node::text("Description")]),
+                    ]),
+                    Node::table_row(vec![
+                        Node::table_cell(vec![Node::inline_code("x")]),
+                        Node::table_cell(vec![Node::text("A value.")]),
+                    ]),
+                ],
+            )
+        };
+        let html = to_html(vec![
+            Node::heading(2, vec![Node::text("Arguments")]),
+            table(),
+            Node::heading(2, vec![Node::text("Details")]),
+            table(),
+        ]);
+        assert_eq!(html.matches("<table class=\"ref-arguments\">").count(), 1);
+        assert!(html.contains("<h2>Details</h2>\n<table>\n"));
+    }
+
+    #[test]
+    fn test_definition_list() {
+        let html = to_html(vec![Node::definition_list(vec![
+            Node::definition_term(vec![Node::inline_code("x")]),
+            Node::definition_description(vec![Node::paragraph(vec![Node::text("A value.")])]),
+        ])]);
+        assert_eq!(
+            html,
+            "<dl class=\"ref-definitions\">\n<dt><code>x</code></dt>\n<dd>A value.</dd>\n</dl>\n"
+        );
+    }
+
+    #[test]
+    fn test_math() {
+        let html = to_html(vec![
+            Node::paragraph(vec![Node::text("Where "), Node::inline_math("a<b")]),
+            Node::math("\\sum_i x_i"),
+        ]);
+        assert_eq!(
+            html,
+            "<p>Where <span class=\"math inline\">\\(a&lt;b\\)</span></p>\n\
+             <p><span class=\"math display\">\\[\\sum_i x_i\\]</span></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_raw_html_passthrough() {
+        let html = to_html(vec![Node::html("<div class=\"x\"></div>")]);
+        assert_eq!(html, "<div class=\"x\"></div>\n");
+    }
+
+    #[test]
+    fn test_div_span_and_raw() {
+        let html = to_html(vec![
+            Node::div(
+                Attributes {
+                    id: Some("n".to_string()),
+                    classes: vec!["callout-note".to_string()],
+                    attributes: vec![("collapse".to_string(), "true".to_string())],
+                },
+                vec![Node::paragraph(vec![Node::span(
+                    Attributes::class("x"),
+                    vec![Node::text("a")],
+                )])],
+            ),
+            Node::raw_block("html", "<hr>"),
+            Node::raw_block("latex", "\\newpage"),
+        ]);
+        assert_eq!(
+            html,
+            "<div id=\"n\" class=\"callout-note\" data-collapse=\"true\">\n<p><span class=\"x\">a</span></p>\n</div>\n<hr>\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_attribute_names_dropped() {
+        let attributes = Attributes {
+            attributes: vec![
+                ("width".to_string(), "100".to_string()),
+                ("onerror=alert(1) x".to_string(), "y".to_string()),
+                ("a\"b".to_string(), "z".to_string()),
+            ],
+            ..Default::default()
+        };
+        let mut image = Node::image("logo.png", "Logo");
+        if let Node::Image(img) = &mut image {
+            img.attributes = attributes.clone();
+        }
+        let html = to_html(vec![
+            Node::paragraph(vec![image]),
+            Node::div(attributes, vec![]),
+        ]);
+        assert_eq!(
+            html,
+            "<p><img src=\"logo.png\" alt=\"Logo\" width=\"100\"></p>\n<div data-width=\"100\">\n</div>\n"
+        );
+    }
+
+    #[test]
+    fn test_standalone_page() {
+        let options = WriterOptions {
+            frontmatter: Some(Frontmatter {
+                title: Some("Foo".to_string()),
+                pagetitle: Some("Foo & Bar \u{2014} foo".to_string()),
+                format: None,
+                name: None,
+                id: None,
+                metadata: Some(RdMetadata {
+                    keywords: vec!["datasets".to_string()],
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        };
+        let root = Root::new(vec![Node::heading(1, vec![Node::text("Foo")])]);
+        let html = mdast_to_html(&root, &options);
+        assert!(html.starts_with("<!DOCTYPE html>\n"));
+        assert!(html.contains("<title>Foo &amp; Bar \u{2014} foo</title>"));
+        assert!(html.contains("<meta name=\"keywords\" content=\"datasets\">"));
+        assert!(html.contains("<main>\n<h1>Foo</h1>\n</main>"));
+        assert!(html.ends_with("</html>\n"));
+    }
+}
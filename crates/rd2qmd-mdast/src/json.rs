@@ -0,0 +1,208 @@
+//! mdast to JSON writer
+//!
+//! Writes the tree in the unist/mdast JSON format (see [`crate::mdast`]) for
+//! JavaScript pipelines such as remark, Astro or Docusaurus. Frontmatter is
+//! written as a leading `yaml` node, as `mdast-util-frontmatter` does.
+//! [`Root::from_json`] reads the output back.
+
+use crate::mdast::{Node, Root};
+use crate::writer::{WriterOptions, mdast_to_qmd};
+use serde::Serialize;
+
+/// Root node with a leading frontmatter node, serialized like [`Root`]
+#[derive(Serialize)]
+#[serde(tag = "type", rename = "root")]
+struct RootWithFrontmatter<'a> {
+    children: Vec<Child<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Child<'a> {
+    Yaml(Yaml<'a>),
+    Node(&'a Node),
+}
+
+/// Frontmatter node (`mdast-util-frontmatter`)
+#[derive(Serialize)]
+#[serde(tag = "type", rename = "yaml")]
+struct Yaml<'a> {
+    value: &'a str,
+}
+
+/// Convert mdast to mdast JSON (pretty-printed)
+pub fn mdast_to_json(root: &Root, options: &WriterOptions) -> String {
+    let mut output = if options.frontmatter.is_some() {
+        let frontmatter_only = WriterOptions {
+            frontmatter: options.frontmatter.clone(),
+            ..Default::default()
+        };
+        let yaml = mdast_to_qmd(&Root::new(vec![]), &frontmatter_only);
+        // The yaml node holds the content between the `---` fences
+        let yaml = yaml
+            .trim_end()
+            .trim_start_matches("---")
+            .trim_end_matches("---")
+            .trim_matches('\n');
+        let children = std::iter::once(Child::Yaml(Yaml { value: yaml }))
+            .chain(root.children.iter().map(Child::Node))
+            .collect();
+        serde_json::to_string_pretty(&RootWithFrontmatter { children })
+    } else {
+        serde_json::to_string_pretty(root)
+    }
+    .expect("mdast JSON serialization");
+    output.push('\n');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mdast::{Align, Node};
+    use crate::writer::Frontmatter;
+    use serde_json::{Value, json};
+
+    fn sample_tree() -> Root {
+        Root::new(vec![
+            Node::heading(1, vec![Node::text("Title")]),
+            Node::paragraph(vec![
+                Node::emphasis(vec![Node::text("em")]),
+                Node::inline_code("x"),
+                Node::link("foo.qmd", vec![Node::text("foo")]),
+                Node::line_break(),
+                Node::inline_math("\\alpha"),
+            ]),
+            Node::thematic_break(),
+            Node::code_with_meta(
+                Some("r".to_string()),
+                Some("executable".to_string()),
+                "1 + 1",
+            ),
+            Node::list(
+                false,
+                vec![Node::list_item(vec![Node::paragraph(vec![Node::text(
+                    "a",
+                )])])],
+            ),
+            Node::table(
+                vec![Some(Align::Left), None],
+                vec![Node::table_row(vec![
+                    Node::table_cell(vec![Node::text("A")]),
+                    Node::table_cell(vec![Node::text("B")]),
+                ])],
+            ),
+            Node::definition_list(vec![
+                Node::definition_term(vec![Node::text("term")]),
+                Node::definition_description(vec![Node::paragraph(vec![Node::text("desc")])]),
+            ]),
+            Node::math("x^2"),
+            Node::html("<br>"),
+            Node::image("a.png", "alt"),
+        ])
+    }
+
+    #[test]
+    fn test_unist_layout() {
+        let json = mdast_to_json(&sample_tree(), &WriterOptions::default());
+        let value: Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["type"], "root");
+        let children = value["children"].as_array().unwrap();
+        assert_eq!(children[0]["type"], "heading");
+        assert_eq!(children[0]["depth"], 1);
+        assert_eq!(
+            children[0]["children"][0],
+            json!({"type": "text", "value": "Title"})
+        );
+        let inline = children[1]["children"].as_array().unwrap();
+        assert_eq!(inline[1], json!({"type": "inlineCode", "value": "x"}));
+        assert_eq!(inline[2]["url"], "foo.qmd");
+        assert!(inline[2]["title"].is_null());
+        assert_eq!(inline[3], json!({"type": "break"}));
+        assert_eq!(inline[4]["type"], "inlineMath");
+        assert_eq!(children[2], json!({"type": "thematicBreak"}));
+        assert_eq!(children[3]["lang"], "r");
+        assert_eq!(children[3]["meta"], "executable");
+        assert_eq!(children[4]["type"], "list");
+        assert_eq!(children[4]["children"][0]["type"], "listItem");
+        assert_eq!(children[5]["align"], json!(["left", null]));
+        assert_eq!(children[5]["children"][0]["type"], "tableRow");
+        assert_eq!(children[6]["type"], "defList");
+        assert_eq!(children[6]["children"][0]["type"], "defListTerm");
+        assert_eq!(children[6]["children"][1]["type"], "defListDescription");
+        assert_eq!(children[7]["type"], "math");
+        assert_eq!(children[8]["type"], "html");
+        assert_eq!(children[9]["alt"], "alt");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let root = sample_tree();
+        let json = mdast_to_json(&root, &WriterOptions::default());
+        assert_eq!(Root::from_json(&json).unwrap(), root);
+    }
+
+    #[test]
+    fn test_frontmatter_yaml_node() {
+        let options = WriterOptions {
+            frontmatter: Some(Frontmatter {
+                title: Some("Foo".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let root = Root::new(vec![Node::paragraph(vec![Node::text("x")])]);
+        let json = mdast_to_json(&root, &options);
+        let value: Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            value["children"][0],
+            json!({"type": "yaml", "value": "title: \"Foo\""})
+        );
+        // Frontmatter is not part of the tree when reading back
+        assert_eq!(Root::from_json(&json).unwrap(), root);
+    }
+
+    #[test]
+    fn test_import_remark_output() {
+        // As produced by remark: positions, omitted optional fields
+        let json = r#"{
+          "type": "root",
+          "children": [
+            {
+              "type": "list",
+              "ordered": true,
+              "start": 2,
+              "children": [
+                {
+                  "type": "listItem",
+                  "children": [
+                    {
+                      "type": "paragraph",
+                      "children": [{"type": "text", "value": "item"}],
+                      "position": {"start": {"line": 1, "column": 4, "offset": 3}}
+                    }
+                  ]
+                }
+              ]
+            },
+            {"type": "code", "lang": null, "meta": null, "value": "x"}
+          ],
+          "position": {"start": {"line": 1, "column": 1, "offset": 0}}
+        }"#;
+        let root = Root::from_json(json).unwrap();
+        assert_eq!(
+            root,
+            Root::new(vec![
+                Node::ordered_list_from(
+                    2,
+                    vec![Node::list_item(vec![Node::paragraph(vec![Node::text(
+                        "item"
+                    )])])]
+                ),
+                Node::code(None, "x"),
+            ])
+        );
+    }
+}
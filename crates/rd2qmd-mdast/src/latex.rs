@@ -0,0 +1,464 @@
+//! mdast to LaTeX writer
+//!
+//! Converts an mdast tree into LaTeX for a PDF reference manual without R's
+//! `Rd2latex` toolchain. Tables become `longtable`s so that long Arguments
+//! tables break across pages, math is passed through as native LaTeX (Rd math
+//! already is LaTeX), and links between topics become `hyperref` references to
+//! `topic:<name>` labels.
+
+use crate::mdast::{Node, Root};
+use crate::text::html_to_text;
+use crate::writer::WriterOptions;
+
+/// Prefix of the labels that anchor topics in a combined document
+pub const TOPIC_LABEL_PREFIX: &str = "topic:";
+
+/// File extension of internal links that refer to other topics
+const TOPIC_LINK_EXTENSION: &str = ".tex";
+
+/// Convert mdast to LaTeX
+///
+/// Without frontmatter, a fragment is produced for `\input` into a larger
+/// document; links to other topics become `\hyperref` references to
+/// `topic:<name>` labels. With frontmatter, a standalone document is written
+/// (see [`latex_document`]); as it contains no other topics, such links are
+/// written as plain text.
+pub fn mdast_to_latex(root: &Root, options: &WriterOptions) -> String {
+    let standalone = options.frontmatter.is_some();
+    let writer = LatexWriter {
+        topic_links: !standalone,
+    };
+    let body = writer.render_blocks(&root.children);
+
+    match &options.frontmatter {
+        Some(fm) => latex_document(fm.title.as_deref(), &body),
+        None => body,
+    }
+}
+
+/// Wrap a LaTeX body in a complete document with the packages the writer uses
+///
+/// `title` sets the document title and PDF metadata; call `\maketitle` in the
+/// body to typeset it.
+pub fn latex_document(title: Option<&str>, body: &str) -> String {
+    let mut out = String::new();
+    out.push_str("\\documentclass{article}\n");
+    out.push_str("\\usepackage[T1]{fontenc}\n");
+    out.push_str("\\usepackage[utf8]{inputenc}\n");
+    out.push_str("\\usepackage{amsmath}\n");
+    out.push_str("\\usepackage{amssymb}\n");
+    out.push_str("\\usepackage{array}\n");
+    out.push_str("\\usepackage{booktabs}\n");
+    out.push_str("\\usepackage{longtable}\n");
+    out.push_str("\\usepackage[hidelinks]{hyperref}\n");
+    if let Some(title) = title {
+        let title = escape(title);
+        out.push_str(&format!("\\title{{{}}}\n", title));
+        out.push_str(&format!("\\hypersetup{{pdftitle={{{}}}}}\n", title));
+    }
+    out.push_str("\\date{}\n");
+    out.push('\n');
+    out.push_str("\\begin{document}\n\n");
+    out.push_str(body);
+    if !body.is_empty() && !body.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str("\n\\end{document}\n");
+    out
+}
+
+/// Whether raw content targets this writer
+fn is_latex(format: &str) -> bool {
+    matches!(format, "latex" | "tex")
+}
+
+/// Label that anchors a topic (Rd file basename) in a combined document
+pub fn topic_label(topic: &str) -> String {
+    format!("{}{}", TOPIC_LABEL_PREFIX, topic)
+}
+
+/// LaTeX writer settings
+struct LatexWriter {
+    /// Render links to other topics as `\hyperref` references
+    topic_links: bool,
+}
+
+impl LatexWriter {
+    /// Render blocks separated by blank lines
+    fn render_blocks(&self, nodes: &[Node]) -> String {
+        nodes
+            .iter()
+            .map(|node| self.render_block(node))
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+            + if nodes.is_empty() { "" } else { "\n" }
+    }
+
+    fn render_block(&self, node: &Node) -> String {
+        match node {
+            Node::Heading(h) => {
+                let text = self.render_inline(&h.children);
+                match h.depth {
+                    // Unnumbered, like R's reference manual, but listed in the contents
+                    1 => format!(
+                        "\\section*{{{}}}\n\\addcontentsline{{toc}}{{section}}{{{}}}",
+                        text, text
+                    ),
+                    2 => format!("\\subsection*{{{}}}", text),
+                    3 => format!("\\subsubsection*{{{}}}", text),
+                    _ => format!("\\paragraph*{{{}}}", text),
+                }
+            }
+            Node::Paragraph(p) => self.render_inline(&p.children),
+            Node::ThematicBreak => "\\noindent\\rule{\\linewidth}{0.4pt}".to_string(),
+            Node::Blockquote(b) => format!(
+                "\\begin{{quote}}\n{}\\end{{quote}}",
+                self.render_blocks(&b.children)
+            ),
+            Node::List(l) => self.render_list(l),
+            Node::ListItem(li) => self.render_blocks(&li.children).trim_end().to_string(),
+            Node::Code(c) => format!(
+                "\\begin{{verbatim}}\n{}\n\\end{{verbatim}}",
+                c.value.trim_end_matches('\n')
+            ),
+            Node::Math(m) => format!("\\[\n{}\n\\]", m.value.trim()),
+            Node::Table(t) => self.render_table(t),
+            Node::DefinitionList(dl) => {
+                let mut out = String::from("\\begin{description}\n");
+                for child in &dl.children {
+                    match child {
+                        Node::DefinitionTerm(dt) => {
+                            out.push_str(&format!(
+                                "\\item[{{{}}}]",
+                                self.render_inline(&dt.children)
+                            ));
+                        }
+                        Node::DefinitionDescription(dd) => {
+                            out.push(' ');
+                            out.push_str(&self.render_blocks(&dd.children));
+                        }
+                        _ => {}
+                    }
+                }
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("\\end{description}");
+                out
+            }
+            Node::TableRow(_)
+            | Node::TableCell(_)
+            | Node::DefinitionTerm(_)
+            | Node::DefinitionDescription(_) => String::new(), // Handled by their containers
+            Node::Html(h) => escape(html_to_text(&h.value).trim()).replace('\n', "\\newline\n"),
+            Node::Div(d) => self.render_blocks(&d.children).trim_end().to_string(),
+            Node::RawBlock(r) if is_latex(&r.format) => r.value.trim_end().to_string(),
+            Node::RawBlock(_) => String::new(),
+            // Inline content at block level is written as a paragraph
+            _ => self.render_inline(std::slice::from_ref(node)),
+        }
+    }
+
+    fn render_list(&self, l: &crate::mdast::List) -> String {
+        let env = if l.ordered { "enumerate" } else { "itemize" };
+        let mut out = format!("\\begin{{{}}}\n", env);
+        if let Some(start) = l.start.filter(|&s| l.ordered && s != 1) {
+            out.push_str(&format!("\\setcounter{{enumi}}{{{}}}\n", start - 1));
+        }
+        for child in &l.children {
+            if let Node::ListItem(li) = child {
+                out.push_str("\\item ");
+                out.push_str(&self.render_blocks(&li.children));
+            }
+        }
+        out.push_str(&format!("\\end{{{}}}", env));
+        out
+    }
+
+    fn render_table(&self, t: &crate::mdast::Table) -> String {
+        let rows: Vec<Vec<String>> = t
+            .children
+            .iter()
+            .filter_map(|n| match n {
+                Node::TableRow(r) => Some(
+                    r.children
+                        .iter()
+                        .map(|c| match c {
+                            Node::TableCell(cell) => self.render_inline(&cell.children),
+                            _ => String::new(),
+                        })
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .collect();
+        let num_cols = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        if num_cols == 0 {
+            return String::new();
+        }
+
+        // Two-column tables are name/description pairs (e.g. Arguments), as in
+        // Rd2latex; other tables share the line width equally
+        let widths: Vec<f64> = if num_cols == 2 {
+            vec![0.25, 0.70]
+        } else {
+            vec![0.95 / num_cols as f64; num_cols]
+        };
+        let spec: String = widths
+            .iter()
+            .map(|width| {
+                format!(
+                    ">{{\\raggedright\\arraybackslash}}p{{{:.2}\\linewidth}}",
+                    width
+                )
+            })
+            .collect();
+
+        let row_line = |row: &Vec<String>| {
+            let mut cells: Vec<&str> = row.iter().map(String::as_str).collect();
+            cells.resize(num_cols, "");
+            format!("{} \\\\\n", cells.join(" & "))
+        };
+
+        let mut out = format!("\\begin{{longtable}}{{@{{}}{}@{{}}}}\n\\toprule\n", spec);
+        if let Some(header) = rows.first() {
+            out.push_str(&row_line(header));
+            out.push_str("\\midrule\n\\endhead\n");
+        }
+        for row in rows.iter().skip(1) {
+            out.push_str(&row_line(row));
+        }
+        out.push_str("\\bottomrule\n\\end{longtable}");
+        out
+    }
+
+    fn render_inline(&self, nodes: &[Node]) -> String {
+        let mut out = String::new();
+        for node in nodes {
+            match node {
+                Node::Text(t) => out.push_str(&escape(&t.value)),
+                Node::Emphasis(e) => {
+                    out.push_str(&format!("\\emph{{{}}}", self.render_inline(&e.children)))
+                }
+                Node::Strong(s) => {
+                    out.push_str(&format!("\\textbf{{{}}}", self.render_inline(&s.children)))
+                }
+                Node::InlineCode(c) => out.push_str(&format!("\\texttt{{{}}}", escape(&c.value))),
+                Node::Link(l) => {
+                    let text = self.render_inline(&l.children);
+                    out.push_str(&self.render_link(&text, &l.url));
+                }
+                Node::Image(img) => out.push_str(&format!("[{}]", escape(&img.alt))),
+                Node::InlineMath(m) => out.push_str(&format!("${}$", m.value)),
+                Node::Break => out.push_str("\\newline\n"),
+                Node::Html(h) => {
+                    out.push_str(&escape(&html_to_text(&h.value)).replace('\n', "\\newline "))
+                }
+                Node::Span(s) => out.push_str(&self.render_inline(&s.children)),
+                Node::RawInline(r) if is_latex(&r.format) => out.push_str(&r.value),
+                Node::Paragraph(p) => out.push_str(&self.render_inline(&p.children)),
+                _ => {}
+            }
+        }
+        out
+    }
+
+    fn render_link(&self, text: &str, url: &str) -> String {
+        let is_absolute = url.contains("://") || url.starts_with("mailto:");
+        if is_absolute {
+            return format!("\\href{{{}}}{{{}}}", escape_url(url), text);
+        }
+        if !self.topic_links {
+            return text.to_string();
+        }
+        let target = url.split('#').next().unwrap_or(url);
+        let topic = target.strip_suffix(TOPIC_LINK_EXTENSION).unwrap_or(target);
+        format!("\\hyperref[{}]{{{}}}", topic_label(topic), text)
+    }
+}
+
+/// Escape LaTeX special characters in text
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '{' | '}' | '$' | '&' | '#' | '_' | '%' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape a URL for `\href` (only `%`, `#` and `\` need protecting)
+fn escape_url(url: &str) -> String {
+    url.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('#', "\\#")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mdast::*;
+    use crate::writer::Frontmatter;
+
+    fn to_latex(children: Vec<Node>) -> String {
+        mdast_to_latex(&Root::new(children), &WriterOptions::default())
+    }
+
+    #[test]
+    fn test_headings() {
+        let latex = to_latex(vec![
+            Node::heading(1, vec![Node::text("Arithmetic Mean")]),
+            Node::heading(2, vec![Node::text("Description")]),
+            Node::paragraph(vec![Node::text("Body.")]),
+        ]);
+        assert_eq!(
+            latex,
+            "\\section*{Arithmetic Mean}\n\
+             \\addcontentsline{toc}{section}{Arithmetic Mean}\n\n\
+             \\subsection*{Description}\n\n\
+             Body.\n"
+        );
+    }
+
+    #[test]
+    fn test_escaping() {
+        let latex = to_latex(vec![Node::paragraph(vec![
+            Node::text("50% of $x_i & {y} #1 ~ ^ \\"),
+            Node::inline_code("na.rm_x"),
+        ])]);
+        assert_eq!(
+            latex,
+            "50\\% of \\$x\\_i \\& \\{y\\} \\#1 \\textasciitilde{} \\textasciicircum{} \\textbackslash{}\\texttt{na.rm\\_x}\n"
+        );
+    }
+
+    #[test]
+    fn test_inline_markup() {
+        let latex = to_latex(vec![Node::paragraph(vec![
+            Node::emphasis(vec![Node::text("em")]),
+            Node::text(" "),
+            Node::strong(vec![Node::text("strong")]),
+        ])]);
+        assert_eq!(latex, "\\emph{em} \\textbf{strong}\n");
+    }
+
+    #[test]
+    fn test_native_math() {
+        let latex = to_latex(vec![
+            Node::paragraph(vec![Node::inline_math("\\alpha_i")]),
+            Node::math("\\sum_{i=1}^n x_i"),
+        ]);
+        assert_eq!(latex, "$\\alpha_i$\n\n\\[\n\\sum_{i=1}^n x_i\n\\]\n");
+    }
+
+    #[test]
+    fn test_code_block_verbatim() {
+        let latex = to_latex(vec![Node::code(Some("r".to_string()), "x <- 1 # 100%")]);
+        assert_eq!(latex, "\\begin{verbatim}\nx <- 1 # 100%\n\\end{verbatim}\n");
+    }
+
+    #[test]
+    fn test_topic_links() {
+        let latex = to_latex(vec![Node::paragraph(vec![
+            Node::link("other_topic.tex", vec![Node::inline_code("other")]),
+            Node::text(" "),
+            Node::link("https://example.com/a%20b#x", vec![Node::text("site")]),
+        ])]);
+        assert_eq!(
+            latex,
+            "\\hyperref[topic:other_topic]{\\texttt{other}} \\href{https://example.com/a\\%20b\\#x}{site}\n"
+        );
+    }
+
+    #[test]
+    fn test_standalone_document() {
+        let options = WriterOptions {
+            frontmatter: Some(Frontmatter {
+                title: Some("My Title".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let root = Root::new(vec![Node::paragraph(vec![Node::link(
+            "other.tex",
+            vec![Node::text("other")],
+        )])]);
+        let latex = mdast_to_latex(&root, &options);
+        assert!(latex.starts_with("\\documentclass{article}\n"));
+        assert!(latex.contains("\\usepackage{longtable}\n"));
+        assert!(latex.contains("\\title{My Title}\n"));
+        // No other topics in a standalone document: links are text only
+        assert!(latex.contains("\\begin{document}\n\nother\n"));
+        assert!(latex.ends_with("\\end{document}\n"));
+    }
+
+    #[test]
+    fn test_longtable() {
+        let latex = to_latex(vec![Node::table(
+            vec![None, None],
+            vec![
+                Node::table_row(vec![
+                    Node::table_cell(vec![Node::text("Argument")]),
+                    Node::table_cell(vec![Node::text("Description")]),
+                ]),
+                Node::table_row(vec![
+                    Node::table_cell(vec![Node::inline_code("x")]),
+                    Node::table_cell(vec![Node::text(
+                        "A description that is quite a bit longer than the argument.",
+                    )]),
+                ]),
+            ],
+        )]);
+        assert!(latex.starts_with(
+            "\\begin{longtable}{@{}>{\\raggedright\\arraybackslash}p{0.25\\linewidth}\
+             >{\\raggedright\\arraybackslash}p{0.70\\linewidth}@{}}\n\\toprule\n"
+        ));
+        assert!(latex.contains("Argument & Description \\\\\n\\midrule\n\\endhead\n"));
+        assert!(latex.contains("\\texttt{x} & A description"));
+        assert!(latex.ends_with("\\bottomrule\n\\end{longtable}\n"));
+    }
+
+    #[test]
+    fn test_lists() {
+        let latex = to_latex(vec![
+            Node::list(
+                false,
+                vec![Node::list_item(vec![Node::paragraph(vec![Node::text(
+                    "a",
+                )])])],
+            ),
+            Node::ordered_list_from(
+                3,
+                vec![Node::list_item(vec![Node::paragraph(vec![Node::text(
+                    "c",
+                )])])],
+            ),
+        ]);
+        assert_eq!(
+            latex,
+            "\\begin{itemize}\n\\item a\n\\end{itemize}\n\n\
+             \\begin{enumerate}\n\\setcounter{enumi}{2}\n\\item c\n\\end{enumerate}\n"
+        );
+    }
+
+    #[test]
+    fn test_definition_list() {
+        let latex = to_latex(vec![Node::definition_list(vec![
+            Node::definition_term(vec![Node::inline_code("x")]),
+            Node::definition_description(vec![Node::paragraph(vec![Node::text("An object.")])]),
+        ])]);
+        assert_eq!(
+            latex,
+            "\\begin{description}\n\\item[{\\texttt{x}}] An object.\n\\end{description}\n"
+        );
+    }
+}
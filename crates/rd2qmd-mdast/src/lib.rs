@@ -2,7 +2,15 @@
 //!
 //! This crate provides:
 //! - mdast (Markdown Abstract Syntax Tree) types (subset)
-//! - Serialization to Quarto Markdown format
+//! - Serialization to Quarto Markdown format, with context-aware escaping of text
+//! - Serialization to HTML
+//! - Serialization to Jupyter notebooks
+//! - Serialization to LaTeX
+//! - Serialization to Pandoc's JSON AST
+//! - Export and import as unist-compatible mdast JSON
+//! - Translation of raw HTML into mdast, for targets without raw HTML
+//! - Serialization to roff man pages
+//! - Serialization to plain text (optionally ANSI-styled) for terminals
 //!
 //! ## Example
 //!
@@ -18,12 +26,32 @@
 //! assert!(qmd.contains("# Hello"));
 //! ```
 
+pub mod escape;
+pub mod html;
+pub mod json;
+pub mod latex;
+pub mod man;
 pub mod mdast;
+pub mod notebook;
+pub mod pandoc;
+pub mod raw_html;
+pub mod text;
 pub mod writer;
 
+pub use escape::{TextContext, Typography, escape_text, is_inline_r_code, typeset};
+pub use html::mdast_to_html;
+pub use json::mdast_to_json;
+pub use latex::{latex_document, mdast_to_latex, topic_label};
+pub use man::mdast_to_man;
 pub use mdast::{
-    Align, Blockquote, Code, DefinitionDescription, DefinitionList, DefinitionTerm, Emphasis,
-    Heading, Html, Image, InlineCode, InlineMath, Link, List, ListItem, Math, Node, Paragraph,
-    Root, Strong, Table, TableCell, TableRow, Text,
+    Align, Attributes, Blockquote, Code, DefinitionDescription, DefinitionList, DefinitionTerm,
+    Div, Emphasis, Heading, Html, Image, InlineCode, InlineMath, Link, List, ListItem, Math, Node,
+    Paragraph, Raw, Root, Span, Strong, Table, TableCell, TableRow, Text,
+};
+pub use notebook::mdast_to_ipynb;
+pub use pandoc::mdast_to_pandoc_json;
+pub use raw_html::{translate_raw_html, translate_raw_html_inline};
+pub use text::{TextOptions, mdast_to_text};
+pub use writer::{
+    Frontmatter, MarkdownFlavor, RdMetadata, WriterOptions, mdast_to_qmd, write_qmd, write_qmd_io,
 };
-pub use writer::{Frontmatter, RdMetadata, WriterOptions, mdast_to_qmd};
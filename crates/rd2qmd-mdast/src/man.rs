@@ -0,0 +1,538 @@
+//! mdast to roff (man page) writer
+//!
+//! Converts an mdast tree into a man(7) page for section 3, readable with
+//! `man ./foo.3` without R. Level 2 headings become `.SH`, deeper headings
+//! `.SS`, code blocks `.EX`/`.EE`, definition lists `.TP`, and tables tbl(1)
+//! tables. Links are written as their text followed by the URL.
+
+use crate::mdast::{Node, Root};
+use crate::text::html_to_text;
+use crate::writer::WriterOptions;
+
+/// Manual section for R package documentation (library functions)
+const MAN_SECTION: &str = "3";
+
+/// Convert mdast to a roff man page
+///
+/// The `.TH` header and NAME section are built from the frontmatter title and
+/// aliases; without frontmatter, the level 1 heading is used instead.
+pub fn mdast_to_man(root: &Root, options: &WriterOptions) -> String {
+    let mut writer = ManWriter::new();
+    writer.write_root(root, options);
+    writer.output
+}
+
+/// Roff writer state
+struct ManWriter {
+    output: String,
+}
+
+impl ManWriter {
+    fn new() -> Self {
+        Self {
+            output: String::new(),
+        }
+    }
+
+    fn write_root(&mut self, root: &Root, options: &WriterOptions) {
+        let frontmatter = options.frontmatter.as_ref();
+        let title = frontmatter
+            .and_then(|f| f.title.clone())
+            .or_else(|| {
+                root.children.iter().find_map(|n| match n {
+                    Node::Heading(h) if h.depth == 1 => Some(plain_text(&h.children)),
+                    _ => None,
+                })
+            })
+            .unwrap_or_default();
+        let names: Vec<String> = frontmatter
+            .and_then(|f| f.metadata.as_ref())
+            .map(|m| m.aliases.clone())
+            .filter(|aliases| !aliases.is_empty())
+            .unwrap_or_else(|| vec![title.clone()]);
+
+        // Ask man(1) to run the page through tbl(1)
+        if contains_table(&root.children) {
+            self.output.push_str("'\\\" t\n");
+        }
+        self.output.push_str(&format!(
+            ".TH {} {} \"\" \"\" \"R Documentation\"\n",
+            quote(&names[0]),
+            MAN_SECTION
+        ));
+        self.output.push_str(".SH NAME\n");
+        let names = names
+            .iter()
+            .map(|n| escape(n))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.write_lines(&format!("{} \\- {}", names, escape(&title)));
+
+        for node in &root.children {
+            match node {
+                // The title is already in the NAME section
+                Node::Heading(h) if h.depth == 1 => {}
+                _ => self.write_block(node),
+            }
+        }
+    }
+
+    fn write_block(&mut self, node: &Node) {
+        match node {
+            Node::Heading(h) => {
+                let text = plain_text(&h.children);
+                if h.depth <= 2 {
+                    self.output
+                        .push_str(&format!(".SH {}\n", quote(&text.to_uppercase())));
+                } else {
+                    self.output.push_str(&format!(".SS {}\n", quote(&text)));
+                }
+            }
+            Node::Paragraph(p) => {
+                self.output.push_str(".PP\n");
+                let text = self.render_inline(&p.children);
+                self.write_lines(&text);
+            }
+            Node::ThematicBreak => self.output.push_str(".PP\n"),
+            Node::Blockquote(b) => {
+                self.output.push_str(".RS\n");
+                for child in &b.children {
+                    self.write_block(child);
+                }
+                self.output.push_str(".RE\n");
+            }
+            Node::List(l) => self.write_list(l),
+            Node::ListItem(li) => {
+                for child in &li.children {
+                    self.write_block(child);
+                }
+            }
+            Node::Code(c) => {
+                self.output.push_str(".PP\n.in +4n\n.EX\n");
+                for line in c.value.trim_end_matches('\n').lines() {
+                    self.write_code_line(line);
+                }
+                self.output.push_str(".EE\n.in\n");
+            }
+            Node::Math(m) => {
+                self.output.push_str(".PP\n.RS\n");
+                self.write_lines(&escape(m.value.trim()));
+                self.output.push_str(".RE\n");
+            }
+            Node::Table(t) => self.write_table(t),
+            Node::DefinitionList(dl) => {
+                for child in &dl.children {
+                    match child {
+                        Node::DefinitionTerm(dt) => {
+                            self.output.push_str(".TP\n");
+                            let term = self.render_inline(&dt.children);
+                            self.write_lines(&term.replace("\n.br\n", " "));
+                        }
+                        Node::DefinitionDescription(dd) => self.write_indented(&dd.children),
+                        _ => {}
+                    }
+                }
+            }
+            Node::TableRow(_)
+            | Node::TableCell(_)
+            | Node::DefinitionTerm(_)
+            | Node::DefinitionDescription(_) => {} // Handled by their containers
+            Node::Html(h) => {
+                let text = html_to_text(&h.value);
+                if !text.trim().is_empty() {
+                    self.output.push_str(".PP\n");
+                    self.write_lines(&escape(&text).replace('\n', "\n.br\n"));
+                }
+            }
+            Node::Div(d) => {
+                for child in &d.children {
+                    self.write_block(child);
+                }
+            }
+            Node::RawBlock(r) if is_roff(&r.format) => {
+                self.output.push_str(r.value.trim_end_matches('\n'));
+                self.output.push('\n');
+            }
+            Node::RawBlock(_) => {}
+            // Inline content at block level is written as a paragraph
+            _ => {
+                self.output.push_str(".PP\n");
+                let text = self.render_inline(std::slice::from_ref(node));
+                self.write_lines(&text);
+            }
+        }
+    }
+
+    fn write_list(&mut self, l: &crate::mdast::List) {
+        let mut num = l.start.unwrap_or(1);
+        for child in &l.children {
+            if let Node::ListItem(li) = child {
+                let tag = if l.ordered {
+                    let tag = format!(".IP {}. 4\n", num);
+                    num += 1;
+                    tag
+                } else {
+                    ".IP \\(bu 2\n".to_string()
+                };
+                self.output.push_str(&tag);
+                self.write_indented(&li.children);
+            }
+        }
+    }
+
+    /// Write the body of a `.IP`/`.TP` paragraph
+    ///
+    /// The first paragraph continues the tagged paragraph; further paragraphs
+    /// use `.IP` to keep the indentation, and nested blocks are wrapped in
+    /// `.RS`/`.RE`.
+    fn write_indented(&mut self, children: &[Node]) {
+        for (i, child) in children.iter().enumerate() {
+            match child {
+                Node::Paragraph(p) => {
+                    if i > 0 {
+                        self.output.push_str(".IP\n");
+                    }
+                    let text = self.render_inline(&p.children);
+                    self.write_lines(&text);
+                }
+                _ => {
+                    self.output.push_str(".RS\n");
+                    self.write_block(child);
+                    self.output.push_str(".RE\n");
+                }
+            }
+        }
+    }
+
+    fn write_table(&mut self, t: &crate::mdast::Table) {
+        let rows: Vec<Vec<String>> = t
+            .children
+            .iter()
+            .filter_map(|n| match n {
+                Node::TableRow(r) => Some(
+                    r.children
+                        .iter()
+                        .map(|c| match c {
+                            Node::TableCell(cell) => self.render_inline(&cell.children),
+                            _ => String::new(),
+                        })
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .collect();
+        let num_cols = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        if num_cols == 0 {
+            return;
+        }
+
+        self.output.push_str(".PP\n.TS\nallbox tab(;);\n");
+        self.output.push_str(&vec!["lb"; num_cols].join(" "));
+        self.output.push('\n');
+        self.output.push_str(&vec!["lx"; num_cols].join(" "));
+        self.output.push_str(".\n");
+        for row in &rows {
+            for i in 0..num_cols {
+                if i > 0 {
+                    self.output.push(';');
+                }
+                // Text blocks let long cells wrap
+                self.output.push_str("T{\n");
+                self.write_lines(row.get(i).map(String::as_str).unwrap_or(""));
+                self.output.push_str("T}");
+            }
+            self.output.push('\n');
+        }
+        self.output.push_str(".TE\n");
+    }
+
+    /// Render inline nodes to roff text (`\n.br\n` marks hard line breaks)
+    fn render_inline(&self, nodes: &[Node]) -> String {
+        let mut out = String::new();
+        for node in nodes {
+            match node {
+                Node::Text(t) => out.push_str(&escape(&t.value.replace('\n', " "))),
+                Node::Emphasis(e) => {
+                    out.push_str(&format!("\\fI{}\\fP", self.render_inline(&e.children)))
+                }
+                Node::Strong(s) => {
+                    out.push_str(&format!("\\fB{}\\fP", self.render_inline(&s.children)))
+                }
+                Node::InlineCode(c) => out.push_str(&format!("\\fB{}\\fP", escape_code(&c.value))),
+                Node::Link(l) => {
+                    let text = self.render_inline(&l.children);
+                    out.push_str(&render_link(&text, &l.url, &plain_text(&l.children)));
+                }
+                Node::Image(img) => out.push_str(&format!("[image: {}]", escape(&img.alt))),
+                Node::InlineMath(m) => out.push_str(&escape(&m.value)),
+                Node::Break => out.push_str("\n.br\n"),
+                Node::Html(h) => {
+                    out.push_str(&escape(&html_to_text(&h.value)).replace('\n', "\n.br\n"))
+                }
+                Node::Span(s) => out.push_str(&self.render_inline(&s.children)),
+                Node::RawInline(r) if is_roff(&r.format) => out.push_str(&r.value),
+                Node::Paragraph(p) => out.push_str(&self.render_inline(&p.children)),
+                _ => {}
+            }
+        }
+        out
+    }
+
+    /// Write text lines, protecting lines that would be read as requests
+    fn write_lines(&mut self, text: &str) {
+        for line in text.lines() {
+            let line = line.trim();
+            // Blank lines produce vertical space in roff
+            if line.is_empty() {
+                continue;
+            }
+            if line != ".br" && (line.starts_with('.') || line.starts_with('\'')) {
+                self.output.push_str("\\&");
+            }
+            self.output.push_str(line);
+            self.output.push('\n');
+        }
+    }
+
+    fn write_code_line(&mut self, line: &str) {
+        if line.starts_with('.') || line.starts_with('\'') {
+            self.output.push_str("\\&");
+        }
+        self.output.push_str(&escape_code(line));
+        self.output.push('\n');
+    }
+}
+
+/// Show links as their text followed by the URL
+///
+/// Relative links point to other topics and are written as text only.
+fn render_link(text: &str, url: &str, plain: &str) -> String {
+    let is_absolute = url.contains("://") || url.starts_with("mailto:");
+    if !is_absolute {
+        return text.to_string();
+    }
+    let bare = url.strip_prefix("mailto:").unwrap_or(url);
+    if plain == url || plain == bare {
+        text.to_string()
+    } else {
+        format!("{} <{}>", text, escape(url))
+    }
+}
+
+/// Whether any node in the tree is a table (requires the tbl preprocessor)
+fn contains_table(nodes: &[Node]) -> bool {
+    nodes.iter().any(|node| match node {
+        Node::Table(_) => true,
+        Node::Blockquote(b) => contains_table(&b.children),
+        Node::List(l) => contains_table(&l.children),
+        Node::ListItem(li) => contains_table(&li.children),
+        Node::DefinitionList(dl) => contains_table(&dl.children),
+        Node::DefinitionDescription(dd) => contains_table(&dd.children),
+        _ => false,
+    })
+}
+
+/// Concatenate the text content of inline nodes without markup
+fn plain_text(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(t) => out.push_str(&t.value.replace('\n', " ")),
+            Node::InlineCode(c) => out.push_str(&c.value),
+            Node::InlineMath(m) => out.push_str(&m.value),
+            Node::Emphasis(e) => out.push_str(&plain_text(&e.children)),
+            Node::Strong(s) => out.push_str(&plain_text(&s.children)),
+            Node::Link(l) => out.push_str(&plain_text(&l.children)),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Whether raw content targets this writer
+fn is_roff(format: &str) -> bool {
+    matches!(format, "man" | "roff" | "ms")
+}
+
+/// Escape roff special characters in running text
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\e")
+}
+
+/// Escape code: additionally use `\-` so hyphens stay ASCII minus signs
+fn escape_code(s: &str) -> String {
+    escape(s).replace('-', "\\-")
+}
+
+/// Quote a macro argument
+fn quote(s: &str) -> String {
+    format!("\"{}\"", escape(s).replace('"', "\\(dq"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::{Frontmatter, RdMetadata};
+
+    fn to_man(children: Vec<Node>) -> String {
+        mdast_to_man(&Root::new(children), &WriterOptions::default())
+    }
+
+    /// Output after the `.TH` header and NAME section
+    fn body(man: &str) -> String {
+        man.lines().skip(3).map(|l| format!("{}\n", l)).collect()
+    }
+
+    #[test]
+    fn test_header_from_frontmatter() {
+        let options = WriterOptions {
+            frontmatter: Some(Frontmatter {
+                title: Some("Arithmetic Mean".to_string()),
+                metadata: Some(RdMetadata {
+                    aliases: vec!["mean".to_string(), "mean.default".to_string()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let man = mdast_to_man(&Root::new(vec![]), &options);
+        assert_eq!(
+            man,
+            ".TH \"mean\" 3 \"\" \"\" \"R Documentation\"\n\
+             .SH NAME\n\
+             mean, mean.default \\- Arithmetic Mean\n"
+        );
+    }
+
+    #[test]
+    fn test_header_from_heading() {
+        let man = to_man(vec![
+            Node::heading(1, vec![Node::text("My Title")]),
+            Node::heading(2, vec![Node::text("Description")]),
+            Node::paragraph(vec![Node::text("Body.")]),
+        ]);
+        assert!(man.starts_with(".TH \"My Title\" 3"));
+        assert_eq!(body(&man), ".SH \"DESCRIPTION\"\n.PP\nBody.\n");
+    }
+
+    #[test]
+    fn test_subsection() {
+        let man = to_man(vec![Node::heading(3, vec![Node::text("Methods")])]);
+        assert_eq!(body(&man), ".SS \"Methods\"\n");
+    }
+
+    #[test]
+    fn test_inline_fonts() {
+        let man = to_man(vec![Node::paragraph(vec![
+            Node::emphasis(vec![Node::text("em")]),
+            Node::text(" "),
+            Node::strong(vec![Node::text("strong")]),
+            Node::text(" "),
+            Node::inline_code("na.rm"),
+        ])]);
+        assert_eq!(body(&man), ".PP\n\\fIem\\fP \\fBstrong\\fP \\fBna.rm\\fP\n");
+    }
+
+    #[test]
+    fn test_escaping() {
+        let man = to_man(vec![Node::paragraph(vec![Node::text(
+            ".leading dot and \\backslash",
+        )])]);
+        assert_eq!(body(&man), ".PP\n\\&.leading dot and \\ebackslash\n");
+    }
+
+    #[test]
+    fn test_code_block() {
+        let man = to_man(vec![Node::code(
+            Some("r".to_string()),
+            "x <- c(-1, 2)\n.hidden()",
+        )]);
+        assert_eq!(
+            body(&man),
+            ".PP\n.in +4n\n.EX\nx <\\- c(\\-1, 2)\n\\&.hidden()\n.EE\n.in\n"
+        );
+    }
+
+    #[test]
+    fn test_definition_list() {
+        let man = to_man(vec![Node::definition_list(vec![
+            Node::definition_term(vec![Node::inline_code("x")]),
+            Node::definition_description(vec![
+                Node::paragraph(vec![Node::text("An object.")]),
+                Node::paragraph(vec![Node::text("More.")]),
+            ]),
+        ])]);
+        assert_eq!(body(&man), ".TP\n\\fBx\\fP\nAn object.\n.IP\nMore.\n");
+    }
+
+    #[test]
+    fn test_lists() {
+        let man = to_man(vec![
+            Node::list(
+                false,
+                vec![Node::list_item(vec![Node::paragraph(vec![Node::text(
+                    "bullet",
+                )])])],
+            ),
+            Node::ordered_list_from(
+                3,
+                vec![Node::list_item(vec![Node::paragraph(vec![Node::text(
+                    "third",
+                )])])],
+            ),
+        ]);
+        assert_eq!(body(&man), ".IP \\(bu 2\nbullet\n.IP 3. 4\nthird\n");
+    }
+
+    #[test]
+    fn test_links_with_urls() {
+        let man = to_man(vec![Node::paragraph(vec![
+            Node::link("https://r-project.org", vec![Node::text("R")]),
+            Node::text(", "),
+            Node::link(
+                "https://example.com",
+                vec![Node::text("https://example.com")],
+            ),
+            Node::text(" and "),
+            Node::link("other.3", vec![Node::inline_code("other")]),
+        ])]);
+        assert_eq!(
+            body(&man),
+            ".PP\nR <https://r-project.org>, https://example.com and \\fBother\\fP\n"
+        );
+    }
+
+    #[test]
+    fn test_table_uses_tbl() {
+        let man = to_man(vec![Node::table(
+            vec![None, None],
+            vec![
+                Node::table_row(vec![
+                    Node::table_cell(vec![Node::text("Argument")]),
+                    Node::table_cell(vec![Node::text("Description")]),
+                ]),
+                Node::table_row(vec![
+                    Node::table_cell(vec![Node::inline_code("x")]),
+                    Node::table_cell(vec![Node::text("A value.")]),
+                ]),
+            ],
+        )]);
+        assert!(man.starts_with("'\\\" t\n.TH"));
+        assert!(man.contains(
+            ".TS\nallbox tab(;);\nlb lb\nlx lx.\n\
+             T{\nArgument\nT};T{\nDescription\nT}\n\
+             T{\n\\fBx\\fP\nT};T{\nA value.\nT}\n.TE\n"
+        ));
+    }
+
+    #[test]
+    fn test_line_break() {
+        let man = to_man(vec![Node::paragraph(vec![
+            Node::text("a"),
+            Node::line_break(),
+            Node::text("b"),
+        ])]);
+        assert_eq!(body(&man), ".PP\na\n.br\nb\n");
+    }
+}
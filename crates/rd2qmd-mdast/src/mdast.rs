@@ -2,11 +2,31 @@
 //!
 //! A subset of mdast nodes needed for Markdown generation.
 //! Reference: https://github.com/syntax-tree/mdast
+//!
+//! ## JSON format
+//!
+//! The serde representation follows unist/mdast, so trees can be exchanged
+//! with remark, markdown-rs and other mdast tools: every node is an object
+//! with a `type` field (`root`, `heading`, `inlineCode`, ...) and the mdast
+//! field names (`children`, `value`, `depth`, `lang`, `meta`, `url`, ...).
+//! Absent optional fields are written as `null`, as remark does, and may be
+//! omitted on input; unknown fields such as `position` are ignored.
+//!
+//! Definition lists are not part of mdast; they use the node types of
+//! `mdast-util-definition-list` (`defList`, `defListTerm`,
+//! `defListDescription`). Display and inline math use `math` and
+//! `inlineMath` from `mdast-util-math`.
+//!
+//! Pandoc's attributed containers and raw content are also not part of mdast;
+//! they use `div`, `span`, `rawBlock` and `rawInline`, with an `attributes`
+//! object (`id`, `classes`, `attributes` as `[key, value]` pairs) or a raw
+//! `format` (e.g. `html`, `latex`).
 
 use serde::{Deserialize, Serialize};
 
 /// Root node of an mdast document
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "root")]
 pub struct Root {
     pub children: Vec<Node>,
 }
@@ -28,8 +48,11 @@ pub enum Node {
     TableCell(TableCell),
 
     // Container for definition lists (not standard mdast, but useful)
+    #[serde(rename = "defList")]
     DefinitionList(DefinitionList),
+    #[serde(rename = "defListTerm")]
     DefinitionTerm(DefinitionTerm),
+    #[serde(rename = "defListDescription")]
     DefinitionDescription(DefinitionDescription),
 
     // Inline nodes
@@ -47,6 +70,12 @@ pub enum Node {
 
     // HTML (for raw output)
     Html(Html),
+
+    // Pandoc extensions: fenced divs, bracketed spans and raw content
+    Div(Div),
+    Span(Span),
+    RawBlock(Raw),
+    RawInline(Raw),
 }
 
 /// Heading node (# to ######)
@@ -54,6 +83,9 @@ pub enum Node {
 pub struct Heading {
     pub depth: u8,
     pub children: Vec<Node>,
+    /// Pandoc attributes; the id is the heading's anchor
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    pub attributes: Attributes,
 }
 
 /// Paragraph node
@@ -71,8 +103,10 @@ pub struct Blockquote {
 /// List node (ordered or unordered)
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct List {
+    #[serde(default)]
     pub ordered: bool,
     pub start: Option<u32>,
+    #[serde(default)]
     pub spread: bool,
     pub children: Vec<Node>,
 }
@@ -80,6 +114,7 @@ pub struct List {
 /// List item node
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ListItem {
+    #[serde(default)]
     pub spread: bool,
     pub children: Vec<Node>,
 }
@@ -95,6 +130,7 @@ pub struct Code {
 /// Table node
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Table {
+    #[serde(default)]
     pub align: Vec<Option<Align>>,
     pub children: Vec<Node>,
 }
@@ -175,7 +211,11 @@ pub struct Link {
 pub struct Image {
     pub url: String,
     pub title: Option<String>,
+    #[serde(default)]
     pub alt: String,
+    /// Pandoc attributes, such as `width` and `height`
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    pub attributes: Attributes,
 }
 
 /// Display math node ($$...$$)
@@ -196,6 +236,85 @@ pub struct Html {
     pub value: String,
 }
 
+/// Pandoc-style attributes (`{#id .class key="value"}`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Attributes {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub classes: Vec<String>,
+    #[serde(default)]
+    pub attributes: Vec<(String, String)>,
+}
+
+impl Attributes {
+    /// Attributes with a single class
+    pub fn class(class: impl Into<String>) -> Self {
+        Self {
+            classes: vec![class.into()],
+            ..Default::default()
+        }
+    }
+
+    /// Whether no id, class or attribute is set
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.classes.is_empty() && self.attributes.is_empty()
+    }
+
+    /// Whether the given class is set
+    pub fn has_class(&self, class: &str) -> bool {
+        self.classes.iter().any(|c| c == class)
+    }
+}
+
+/// Fenced div node (`::: {.class}`), a block container with attributes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Div {
+    #[serde(default)]
+    pub attributes: Attributes,
+    pub children: Vec<Node>,
+}
+
+/// Bracketed span node (`[text]{.class}`), an inline container with attributes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Span {
+    #[serde(default)]
+    pub attributes: Attributes,
+    pub children: Vec<Node>,
+}
+
+/// Raw content for one output format (`{=html}`), dropped by other writers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Raw {
+    pub format: String,
+    pub value: String,
+}
+
+impl Node {
+    /// Child nodes of a container node, or `None` for leaf nodes
+    pub fn children_mut(&mut self) -> Option<&mut Vec<Node>> {
+        match self {
+            Node::Heading(Heading { children, .. })
+            | Node::Paragraph(Paragraph { children })
+            | Node::Blockquote(Blockquote { children })
+            | Node::List(List { children, .. })
+            | Node::ListItem(ListItem { children, .. })
+            | Node::Table(Table { children, .. })
+            | Node::TableRow(TableRow { children })
+            | Node::TableCell(TableCell { children })
+            | Node::DefinitionList(DefinitionList { children })
+            | Node::DefinitionTerm(DefinitionTerm { children })
+            | Node::DefinitionDescription(DefinitionDescription { children })
+            | Node::Emphasis(Emphasis { children })
+            | Node::Strong(Strong { children })
+            | Node::Link(Link { children, .. })
+            | Node::Div(Div { children, .. })
+            | Node::Span(Span { children, .. }) => Some(children),
+            _ => None,
+        }
+    }
+}
+
 // Convenience constructors
 impl Node {
     pub fn text(s: impl Into<String>) -> Self {
@@ -207,7 +326,23 @@ impl Node {
     }
 
     pub fn heading(depth: u8, children: Vec<Node>) -> Self {
-        Node::Heading(Heading { depth, children })
+        Node::Heading(Heading {
+            depth,
+            children,
+            attributes: Attributes::default(),
+        })
+    }
+
+    /// Heading with an explicit identifier (`## Title {#id}`)
+    pub fn heading_with_id(depth: u8, children: Vec<Node>, id: impl Into<String>) -> Self {
+        Node::Heading(Heading {
+            depth,
+            children,
+            attributes: Attributes {
+                id: Some(id.into()),
+                ..Default::default()
+            },
+        })
     }
 
     pub fn code(lang: Option<String>, value: impl Into<String>) -> Self {
@@ -269,6 +404,7 @@ impl Node {
             url: url.into(),
             title: None,
             alt: alt.into(),
+            attributes: Attributes::default(),
         })
     }
 
@@ -281,6 +417,7 @@ impl Node {
             url: url.into(),
             title: Some(title.into()),
             alt: alt.into(),
+            attributes: Attributes::default(),
         })
     }
 
@@ -351,6 +488,34 @@ impl Node {
         })
     }
 
+    pub fn div(attributes: Attributes, children: Vec<Node>) -> Self {
+        Node::Div(Div {
+            attributes,
+            children,
+        })
+    }
+
+    pub fn span(attributes: Attributes, children: Vec<Node>) -> Self {
+        Node::Span(Span {
+            attributes,
+            children,
+        })
+    }
+
+    pub fn raw_block(format: impl Into<String>, value: impl Into<String>) -> Self {
+        Node::RawBlock(Raw {
+            format: format.into(),
+            value: value.into(),
+        })
+    }
+
+    pub fn raw_inline(format: impl Into<String>, value: impl Into<String>) -> Self {
+        Node::RawInline(Raw {
+            format: format.into(),
+            value: value.into(),
+        })
+    }
+
     pub fn blockquote(children: Vec<Node>) -> Self {
         Node::Blockquote(Blockquote { children })
     }
@@ -368,6 +533,29 @@ impl Root {
     pub fn new(children: Vec<Node>) -> Self {
         Self { children }
     }
+
+    /// Serialize the tree to an mdast JSON string
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Serialize the tree to a pretty-printed mdast JSON string
+    pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Deserialize a tree from an mdast JSON string
+    ///
+    /// A top-level `yaml` frontmatter node (as written by
+    /// [`mdast_to_json`](crate::json::mdast_to_json)) is skipped, since
+    /// frontmatter is not part of the tree.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let mut value: serde_json::Value = serde_json::from_str(json)?;
+        if let Some(children) = value.get_mut("children").and_then(|c| c.as_array_mut()) {
+            children.retain(|child| child.get("type").and_then(|t| t.as_str()) != Some("yaml"));
+        }
+        serde_json::from_value(value)
+    }
 }
 
 #[cfg(test)]
@@ -509,4 +697,30 @@ mod tests {
         let parsed: Root = serde_json::from_str(&json).unwrap();
         assert_eq!(root, parsed);
     }
+
+    #[test]
+    fn test_div_span_raw_json() {
+        let root = Root::new(vec![
+            Node::div(
+                Attributes::class("callout-note"),
+                vec![Node::paragraph(vec![Node::span(
+                    Attributes::default(),
+                    vec![Node::raw_inline("html", "<br>")],
+                )])],
+            ),
+            Node::raw_block("latex", "\\newpage"),
+        ]);
+
+        let json: serde_json::Value = serde_json::from_str(&root.to_json().unwrap()).unwrap();
+        assert_eq!(json["children"][0]["type"], "div");
+        assert_eq!(
+            json["children"][0]["attributes"],
+            serde_json::json!({"id": null, "classes": ["callout-note"], "attributes": []})
+        );
+        assert_eq!(json["children"][1]["type"], "rawBlock");
+        assert_eq!(json["children"][1]["format"], "latex");
+
+        let parsed = Root::from_json(&root.to_json().unwrap()).unwrap();
+        assert_eq!(root, parsed);
+    }
 }
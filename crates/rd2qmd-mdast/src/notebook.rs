@@ -0,0 +1,302 @@
+//! mdast to Jupyter notebook writer
+//!
+//! Converts an mdast tree into a Jupyter notebook (nbformat 4.5) for the R kernel
+//! (IRkernel). Executable R code blocks (`meta == "executable"`) become code cells;
+//! everything else, including non-executable code such as Usage and `\dontrun{}`
+//! examples, is grouped into Markdown cells.
+
+use crate::mdast::{Code, Node, Root};
+use crate::writer::{WriterOptions, mdast_to_qmd};
+use serde_json::{Value, json};
+
+/// Quarto chunk option marking code that runs but is not shown (from `\dontshow{}`)
+const INCLUDE_FALSE: &str = "#| include: false";
+
+/// Convert mdast to a Jupyter notebook (JSON)
+///
+/// Frontmatter, if provided, is written as a leading raw cell, which Quarto
+/// reads as the notebook's YAML metadata.
+pub fn mdast_to_ipynb(root: &Root, options: &WriterOptions) -> String {
+    let mut cells = Vec::new();
+
+    if options.frontmatter.is_some() {
+        let frontmatter_only = WriterOptions {
+            frontmatter: options.frontmatter.clone(),
+            ..Default::default()
+        };
+        let yaml = mdast_to_qmd(&Root::new(vec![]), &frontmatter_only);
+        cells.push(raw_cell(yaml.trim_end()));
+    }
+
+    // Markdown cells are rendered without frontmatter and with plain code fences
+    let markdown_options = WriterOptions {
+        frontmatter: None,
+        quarto_code_blocks: false,
+        ..options.clone()
+    };
+    let mut pending: Vec<Node> = Vec::new();
+    let flush = |pending: &mut Vec<Node>, cells: &mut Vec<Value>| {
+        if !pending.is_empty() {
+            let markdown = mdast_to_qmd(&Root::new(std::mem::take(pending)), &markdown_options);
+            cells.push(markdown_cell(markdown.trim_end()));
+        }
+    };
+
+    for node in &root.children {
+        match node {
+            Node::Code(code) if is_executable(code) => {
+                flush(&mut pending, &mut cells);
+                cells.push(code_cell(code));
+            }
+            _ => pending.push(node.clone()),
+        }
+    }
+    flush(&mut pending, &mut cells);
+
+    // Cell ids are required since nbformat 4.5; number them for stable output
+    for (i, cell) in cells.iter_mut().enumerate() {
+        cell["id"] = json!(format!("cell-{}", i + 1));
+    }
+
+    let notebook = json!({
+        "cells": cells,
+        "metadata": {
+            "kernelspec": {
+                "display_name": "R",
+                "language": "R",
+                "name": "ir"
+            },
+            "language_info": {
+                "name": "R"
+            }
+        },
+        "nbformat": 4,
+        "nbformat_minor": 5
+    });
+
+    let mut output = serde_json::to_string_pretty(&notebook).expect("notebook JSON serialization");
+    output.push('\n');
+    output
+}
+
+/// Whether a code block should become a notebook code cell
+fn is_executable(code: &Code) -> bool {
+    code.lang.as_deref() == Some("r") && code.meta.as_deref() == Some("executable")
+}
+
+fn markdown_cell(source: &str) -> Value {
+    json!({
+        "cell_type": "markdown",
+        "metadata": {},
+        "source": source_lines(source)
+    })
+}
+
+fn raw_cell(source: &str) -> Value {
+    json!({
+        "cell_type": "raw",
+        "metadata": {},
+        "source": source_lines(source)
+    })
+}
+
+/// Build a code cell; `\dontshow{}` code (`#| include: false`) becomes a hidden
+/// cell tagged `remove-input`. Other `#|` chunk options stay in the source, where
+/// Quarto reads them.
+fn code_cell(code: &Code) -> Value {
+    let options_end = code
+        .value
+        .split_inclusive('\n')
+        .take_while(|line| line.starts_with("#|"))
+        .map(str::len)
+        .sum::<usize>();
+    let (options, body) = code.value.split_at(options_end);
+    let hidden = options.lines().any(|line| line.trim_end() == INCLUDE_FALSE);
+    let (source, metadata) = if hidden {
+        let options: String = options
+            .split_inclusive('\n')
+            .filter(|line| line.trim_end() != INCLUDE_FALSE)
+            .collect();
+        (
+            options + body,
+            json!({
+                "jupyter": { "source_hidden": true },
+                "tags": ["remove-input"]
+            }),
+        )
+    } else {
+        (code.value.clone(), json!({}))
+    };
+
+    json!({
+        "cell_type": "code",
+        "execution_count": null,
+        "metadata": metadata,
+        "outputs": [],
+        "source": source_lines(source.trim_end_matches('\n'))
+    })
+}
+
+/// Split cell source into lines, keeping line endings (nbformat multiline string)
+fn source_lines(source: &str) -> Vec<String> {
+    source.split_inclusive('\n').map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::escape::Typography;
+    use crate::writer::Frontmatter;
+
+    fn to_notebook(children: Vec<Node>, options: &WriterOptions) -> Value {
+        let ipynb = mdast_to_ipynb(&Root::new(children), options);
+        serde_json::from_str(&ipynb).unwrap()
+    }
+
+    fn executable(value: &str) -> Node {
+        Node::code_with_meta(Some("r".to_string()), Some("executable".to_string()), value)
+    }
+
+    #[test]
+    fn test_notebook_metadata() {
+        let nb = to_notebook(vec![], &WriterOptions::default());
+        assert_eq!(nb["nbformat"], 4);
+        assert_eq!(nb["nbformat_minor"], 5);
+        assert_eq!(nb["metadata"]["kernelspec"]["name"], "ir");
+        assert_eq!(nb["cells"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_prose_grouped_into_markdown_cell() {
+        let nb = to_notebook(
+            vec![
+                Node::heading(2, vec![Node::text("Usage")]),
+                Node::code(Some("r".to_string()), "foo(x)"),
+                Node::paragraph(vec![Node::text("Text.")]),
+            ],
+            &WriterOptions::default(),
+        );
+        let cells = nb["cells"].as_array().unwrap();
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0]["cell_type"], "markdown");
+        assert_eq!(
+            cells[0]["source"],
+            json!([
+                "## Usage\n",
+                "\n",
+                "```r\n",
+                "foo(x)\n",
+                "```\n",
+                "\n",
+                "Text."
+            ])
+        );
+    }
+
+    #[test]
+    fn test_executable_code_cell() {
+        let nb = to_notebook(
+            vec![
+                Node::heading(2, vec![Node::text("Examples")]),
+                executable("x <- 1\nprint(x)"),
+            ],
+            &WriterOptions::default(),
+        );
+        let cells = nb["cells"].as_array().unwrap();
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[1]["cell_type"], "code");
+        assert_eq!(cells[1]["id"], "cell-2");
+        assert_eq!(cells[1]["source"], json!(["x <- 1\n", "print(x)"]));
+        assert_eq!(cells[1]["outputs"], json!([]));
+        assert!(cells[1]["execution_count"].is_null());
+    }
+
+    #[test]
+    fn test_non_executable_code_stays_markdown() {
+        // \dontrun{} code has no executable meta
+        let nb = to_notebook(
+            vec![
+                executable("a()"),
+                Node::code(Some("r".to_string()), "b()"),
+                executable("c()"),
+            ],
+            &WriterOptions::default(),
+        );
+        let types: Vec<_> = nb["cells"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["cell_type"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(types, vec!["code", "markdown", "code"]);
+    }
+
+    #[test]
+    fn test_dontshow_hidden_cell() {
+        let nb = to_notebook(
+            vec![executable("#| include: false\nset.seed(1)")],
+            &WriterOptions::default(),
+        );
+        let cell = &nb["cells"][0];
+        assert_eq!(cell["cell_type"], "code");
+        assert_eq!(cell["source"], json!(["set.seed(1)"]));
+        assert_eq!(cell["metadata"]["tags"], json!(["remove-input"]));
+        assert_eq!(cell["metadata"]["jupyter"]["source_hidden"], true);
+    }
+
+    #[test]
+    fn test_labelled_dontshow_hidden_cell() {
+        let nb = to_notebook(
+            vec![executable(
+                "#| label: ex-foo-1\n#| include: false\nset.seed(1)",
+            )],
+            &WriterOptions::default(),
+        );
+        let cell = &nb["cells"][0];
+        assert_eq!(
+            cell["source"],
+            json!(["#| label: ex-foo-1\n", "set.seed(1)"])
+        );
+        assert_eq!(cell["metadata"]["tags"], json!(["remove-input"]));
+    }
+
+    #[test]
+    fn test_markdown_cells_use_writer_options() {
+        let options = WriterOptions {
+            quarto_code_blocks: true,
+            typography: Typography::Ascii,
+            ..Default::default()
+        };
+        let nb = to_notebook(
+            vec![
+                Node::paragraph(vec![Node::text("\u{201C}a\u{201D} \u{2013} b")]),
+                Node::code(Some("r".to_string()), "foo(x)"),
+            ],
+            &options,
+        );
+        let cells = nb["cells"].as_array().unwrap();
+        assert_eq!(
+            cells[0]["source"],
+            json!(["\"a\" -- b\n", "\n", "```r\n", "foo(x)\n", "```"])
+        );
+    }
+
+    #[test]
+    fn test_frontmatter_raw_cell() {
+        let options = WriterOptions {
+            frontmatter: Some(Frontmatter {
+                title: Some("Foo".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let nb = to_notebook(vec![Node::paragraph(vec![Node::text("x")])], &options);
+        let cells = nb["cells"].as_array().unwrap();
+        assert_eq!(cells[0]["cell_type"], "raw");
+        assert_eq!(
+            cells[0]["source"],
+            json!(["---\n", "title: \"Foo\"\n", "---"])
+        );
+        assert_eq!(cells[1]["cell_type"], "markdown");
+    }
+}
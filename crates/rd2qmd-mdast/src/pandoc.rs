@@ -0,0 +1,658 @@
+//! mdast to Pandoc JSON AST writer
+//!
+//! Converts an mdast tree into Pandoc's native JSON AST (`pandoc -f json`), so
+//! Pandoc and Quarto receive tables, definition lists and math as structured
+//! elements instead of re-parsing Markdown. Headers get Pandoc-style
+//! auto-generated identifiers, since the JSON reader does not add them.
+
+use std::collections::HashMap;
+
+use crate::mdast::{Align, Node, Root};
+use crate::writer::{Frontmatter, WriterOptions};
+use serde_json::{Value, json};
+
+/// Pandoc API version of the generated AST (pandoc-types 1.23, Pandoc 3.x)
+const PANDOC_API_VERSION: [u32; 3] = [1, 23, 1];
+
+/// Convert mdast to Pandoc JSON
+///
+/// Frontmatter fields are written to the document metadata.
+pub fn mdast_to_pandoc_json(root: &Root, options: &WriterOptions) -> String {
+    let mut writer = PandocWriter::default();
+    let blocks = writer.blocks(&root.children, false);
+    let meta = options.frontmatter.as_ref().map(meta).unwrap_or(json!({}));
+
+    let document = json!({
+        "pandoc-api-version": PANDOC_API_VERSION,
+        "meta": meta,
+        "blocks": blocks,
+    });
+    let mut output = serde_json::to_string_pretty(&document).expect("Pandoc JSON serialization");
+    output.push('\n');
+    output
+}
+
+/// Pandoc JSON writer state
+#[derive(Default)]
+struct PandocWriter {
+    /// Header identifiers used so far, for de-duplication
+    identifiers: HashMap<String, usize>,
+}
+
+impl PandocWriter {
+    /// Convert block nodes; in tight mode paragraphs become `Plain`
+    ///
+    /// Runs of inline nodes (e.g. table cell content) are wrapped in a single
+    /// paragraph.
+    fn blocks(&mut self, nodes: &[Node], tight: bool) -> Vec<Value> {
+        let mut out = Vec::new();
+        let mut pending: Vec<Node> = Vec::new();
+        let flush = |pending: &mut Vec<Node>, out: &mut Vec<Value>| {
+            if !pending.is_empty() {
+                let content = inlines(&std::mem::take(pending));
+                out.push(json!({"t": if tight { "Plain" } else { "Para" }, "c": content}));
+            }
+        };
+        for node in nodes {
+            if is_inline(node) {
+                pending.push(node.clone());
+            } else {
+                flush(&mut pending, &mut out);
+                out.extend(self.block(node, tight));
+            }
+        }
+        flush(&mut pending, &mut out);
+        out
+    }
+
+    fn block(&mut self, node: &Node, tight: bool) -> Option<Value> {
+        let block = match node {
+            Node::Heading(h) if h.attributes.id.is_some() => json!({
+                "t": "Header",
+                "c": [h.depth, attributes(&h.attributes), inlines(&h.children)]
+            }),
+            Node::Heading(h) => {
+                let id = self.identifier(&plain_text(&h.children));
+                json!({
+                    "t": "Header",
+                    "c": [h.depth, attr(&id, &[]), inlines(&h.children)]
+                })
+            }
+            Node::Paragraph(p) if tight => json!({"t": "Plain", "c": inlines(&p.children)}),
+            Node::Paragraph(p) => json!({"t": "Para", "c": inlines(&p.children)}),
+            Node::ThematicBreak => json!({"t": "HorizontalRule"}),
+            Node::Blockquote(b) => json!({"t": "BlockQuote", "c": self.blocks(&b.children, false)}),
+            Node::List(l) => {
+                let items: Vec<Value> = l
+                    .children
+                    .iter()
+                    .filter_map(|child| match child {
+                        Node::ListItem(li) => Some(Value::Array(
+                            self.blocks(&li.children, !(l.spread || li.spread)),
+                        )),
+                        _ => None,
+                    })
+                    .collect();
+                if l.ordered {
+                    let attributes =
+                        json!([l.start.unwrap_or(1), {"t": "Decimal"}, {"t": "Period"}]);
+                    json!({"t": "OrderedList", "c": [attributes, items]})
+                } else {
+                    json!({"t": "BulletList", "c": items})
+                }
+            }
+            Node::ListItem(li) => return self.blocks(&li.children, tight).into_iter().next(),
+            Node::Code(c) => {
+                let classes: Vec<&str> = c.lang.as_deref().into_iter().collect();
+                json!({
+                    "t": "CodeBlock",
+                    "c": [attr("", &classes), c.value.trim_end_matches('\n')]
+                })
+            }
+            Node::Math(m) => json!({
+                "t": "Para",
+                "c": [{"t": "Math", "c": [{"t": "DisplayMath"}, m.value.trim()]}]
+            }),
+            Node::Table(t) => self.table(t),
+            Node::DefinitionList(dl) => {
+                // [(term, [definition blocks])]
+                let mut items: Vec<(Value, Vec<Value>)> = Vec::new();
+                for child in &dl.children {
+                    match child {
+                        Node::DefinitionTerm(dt) => items.push((inlines(&dt.children), Vec::new())),
+                        Node::DefinitionDescription(dd) => {
+                            let definition = Value::Array(self.blocks(&dd.children, true));
+                            match items.last_mut() {
+                                Some((_, definitions)) => definitions.push(definition),
+                                None => items.push((json!([]), vec![definition])),
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                let items: Vec<Value> = items
+                    .into_iter()
+                    .map(|(term, definitions)| json!([term, definitions]))
+                    .collect();
+                json!({"t": "DefinitionList", "c": items})
+            }
+            Node::TableRow(_)
+            | Node::TableCell(_)
+            | Node::DefinitionTerm(_)
+            | Node::DefinitionDescription(_) => return None, // Handled by their containers
+            Node::Html(h) => json!({"t": "RawBlock", "c": ["html", h.value]}),
+            Node::RawBlock(r) => json!({"t": "RawBlock", "c": [r.format, r.value]}),
+            Node::Div(d) => json!({
+                "t": "Div",
+                "c": [attributes(&d.attributes), self.blocks(&d.children, false)]
+            }),
+            // Inline nodes are grouped into paragraphs by `blocks`
+            _ => return None,
+        };
+        Some(block)
+    }
+
+    fn table(&mut self, t: &crate::mdast::Table) -> Value {
+        let rows: Vec<&crate::mdast::TableRow> = t
+            .children
+            .iter()
+            .filter_map(|n| match n {
+                Node::TableRow(r) => Some(r),
+                _ => None,
+            })
+            .collect();
+        let num_cols = rows.iter().map(|r| r.children.len()).max().unwrap_or(0);
+
+        let alignment = |i: usize| match t.align.get(i).copied().flatten() {
+            Some(Align::Left) => json!({"t": "AlignLeft"}),
+            Some(Align::Center) => json!({"t": "AlignCenter"}),
+            Some(Align::Right) => json!({"t": "AlignRight"}),
+            None => json!({"t": "AlignDefault"}),
+        };
+        let colspecs: Vec<Value> = (0..num_cols)
+            .map(|i| json!([alignment(i), {"t": "ColWidthDefault"}]))
+            .collect();
+
+        let mut row = |r: &crate::mdast::TableRow| {
+            let cells: Vec<Value> = r
+                .children
+                .iter()
+                .filter_map(|c| match c {
+                    Node::TableCell(cell) => Some(json!([
+                        attr("", &[]),
+                        {"t": "AlignDefault"},
+                        1,
+                        1,
+                        self.blocks(&cell.children, true)
+                    ])),
+                    _ => None,
+                })
+                .collect();
+            json!([attr("", &[]), cells])
+        };
+
+        let head: Vec<Value> = rows.first().map(|r| row(r)).into_iter().collect();
+        let body: Vec<Value> = rows.iter().skip(1).map(|r| row(r)).collect();
+
+        json!({
+            "t": "Table",
+            "c": [
+                attr("", &[]),
+                [null, []],
+                colspecs,
+                [attr("", &[]), head],
+                [[attr("", &[]), 0, [], body]],
+                [attr("", &[]), []]
+            ]
+        })
+    }
+
+    /// Pandoc-style auto identifier, made unique with a numeric suffix
+    fn identifier(&mut self, text: &str) -> String {
+        let mut id: String = text
+            .chars()
+            .skip_while(|c| !c.is_alphabetic())
+            .filter_map(|c| {
+                if c.is_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                    Some(c.to_lowercase().next().unwrap_or(c))
+                } else if c.is_whitespace() {
+                    Some('-')
+                } else {
+                    None
+                }
+            })
+            .collect();
+        if id.is_empty() {
+            id = "section".to_string();
+        }
+
+        let count = self.identifiers.entry(id.clone()).or_insert(0);
+        *count += 1;
+        if *count > 1 {
+            format!("{}-{}", id, *count - 1)
+        } else {
+            id
+        }
+    }
+}
+
+/// Whether a node is phrasing content
+fn is_inline(node: &Node) -> bool {
+    matches!(
+        node,
+        Node::Text(_)
+            | Node::Emphasis(_)
+            | Node::Strong(_)
+            | Node::InlineCode(_)
+            | Node::Break
+            | Node::Link(_)
+            | Node::Image(_)
+            | Node::InlineMath(_)
+            | Node::Span(_)
+            | Node::RawInline(_)
+    )
+}
+
+/// Convert inline nodes, without leading or trailing space
+fn inlines(nodes: &[Node]) -> Value {
+    let mut out = Vec::new();
+    push_inlines(&mut out, nodes);
+    while out.first().is_some_and(is_space) {
+        out.remove(0);
+    }
+    while out.last().is_some_and(is_space) {
+        out.pop();
+    }
+    Value::Array(out)
+}
+
+/// Convert inline nodes nested in another inline, keeping surrounding space
+fn nested(nodes: &[Node]) -> Value {
+    let mut out = Vec::new();
+    push_inlines(&mut out, nodes);
+    Value::Array(out)
+}
+
+fn push_inlines(out: &mut Vec<Value>, nodes: &[Node]) {
+    for node in nodes {
+        match node {
+            Node::Text(t) => push_text(out, &t.value),
+            Node::Emphasis(e) => out.push(json!({"t": "Emph", "c": nested(&e.children)})),
+            Node::Strong(s) => out.push(json!({"t": "Strong", "c": nested(&s.children)})),
+            Node::InlineCode(c) => out.push(json!({"t": "Code", "c": [attr("", &[]), c.value]})),
+            Node::Break => out.push(json!({"t": "LineBreak"})),
+            Node::Link(l) => out.push(json!({
+                "t": "Link",
+                "c": [
+                    attr("", &[]),
+                    nested(&l.children),
+                    [l.url, l.title.as_deref().unwrap_or("")]
+                ]
+            })),
+            Node::Image(img) => {
+                let mut alt = Vec::new();
+                push_text(&mut alt, &img.alt);
+                out.push(json!({
+                    "t": "Image",
+                    "c": [
+                        attributes(&img.attributes),
+                        alt,
+                        [img.url, img.title.as_deref().unwrap_or("")]
+                    ]
+                }));
+            }
+            Node::InlineMath(m) => {
+                out.push(json!({"t": "Math", "c": [{"t": "InlineMath"}, m.value]}))
+            }
+            Node::Html(h) => out.push(json!({"t": "RawInline", "c": ["html", h.value]})),
+            Node::RawInline(r) => out.push(json!({"t": "RawInline", "c": [r.format, r.value]})),
+            Node::Span(s) => out.push(json!({
+                "t": "Span",
+                "c": [attributes(&s.attributes), nested(&s.children)]
+            })),
+            Node::Paragraph(p) => push_inlines(out, &p.children),
+            _ => {}
+        }
+    }
+}
+
+/// Split text into `Str` and `Space`/`SoftBreak` elements
+fn push_text(out: &mut Vec<Value>, text: &str) {
+    let mut word = String::new();
+    let mut space: Option<&str> = None;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !word.is_empty() {
+                out.push(json!({"t": "Str", "c": std::mem::take(&mut word)}));
+            }
+            space = match (space, c) {
+                (_, '\n') | (Some("SoftBreak"), _) => Some("SoftBreak"),
+                _ => Some("Space"),
+            };
+        } else {
+            if let Some(kind) = space.take() {
+                push_space(out, kind);
+            }
+            word.push(c);
+        }
+    }
+    if !word.is_empty() {
+        out.push(json!({"t": "Str", "c": word}));
+    }
+    if let Some(kind) = space {
+        push_space(out, kind);
+    }
+}
+
+/// Push a space element, collapsing it into a preceding one
+fn push_space(out: &mut Vec<Value>, kind: &str) {
+    if !out.last().is_some_and(is_space) {
+        out.push(json!({"t": kind}));
+    }
+}
+
+fn is_space(inline: &Value) -> bool {
+    matches!(inline["t"].as_str(), Some("Space" | "SoftBreak"))
+}
+
+/// Pandoc attributes: `[identifier, [classes], [[key, value]]]`
+fn attr(id: &str, classes: &[&str]) -> Value {
+    json!([id, classes, []])
+}
+
+/// Pandoc attributes of a div or span
+fn attributes(attributes: &crate::mdast::Attributes) -> Value {
+    json!([
+        attributes.id.as_deref().unwrap_or(""),
+        attributes.classes,
+        attributes.attributes
+    ])
+}
+
+/// Concatenate the text content of inline nodes
+fn plain_text(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(t) => out.push_str(&t.value),
+            Node::InlineCode(c) => out.push_str(&c.value),
+            Node::InlineMath(m) => out.push_str(&m.value),
+            Node::Emphasis(e) => out.push_str(&plain_text(&e.children)),
+            Node::Strong(s) => out.push_str(&plain_text(&s.children)),
+            Node::Link(l) => out.push_str(&plain_text(&l.children)),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Document metadata from frontmatter
+fn meta(fm: &Frontmatter) -> Value {
+    let mut meta = serde_json::Map::new();
+    let meta_inlines = |s: &str| {
+        let mut content = Vec::new();
+        push_text(&mut content, s);
+        json!({"t": "MetaInlines", "c": content})
+    };
+    let meta_list = |items: &[String]| {
+        let items: Vec<Value> = items
+            .iter()
+            .map(|s| json!({"t": "MetaString", "c": s}))
+            .collect();
+        json!({"t": "MetaList", "c": items})
+    };
+
+    if let Some(title) = &fm.title {
+        meta.insert("title".to_string(), meta_inlines(title));
+    }
+    if let Some(pagetitle) = &fm.pagetitle {
+        meta.insert("pagetitle".to_string(), meta_inlines(pagetitle));
+    }
+    if let Some(format) = &fm.format {
+        meta.insert(
+            "format".to_string(),
+            json!({"t": "MetaString", "c": format}),
+        );
+    }
+    if let Some(metadata) = &fm.metadata {
+        if let Some(lifecycle) = &metadata.lifecycle {
+            meta.insert(
+                "lifecycle".to_string(),
+                json!({"t": "MetaString", "c": lifecycle}),
+            );
+        }
+        for (key, values) in [
+            ("aliases", &metadata.aliases),
+            ("keywords", &metadata.keywords),
+            ("concepts", &metadata.concepts),
+            ("source-files", &metadata.source_files),
+        ] {
+            if !values.is_empty() {
+                meta.insert(key.to_string(), meta_list(values));
+            }
+        }
+    }
+    Value::Object(meta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mdast::Attributes;
+    use crate::writer::RdMetadata;
+
+    fn to_pandoc(children: Vec<Node>) -> Value {
+        let json = mdast_to_pandoc_json(&Root::new(children), &WriterOptions::default());
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_document_structure() {
+        let doc = to_pandoc(vec![]);
+        assert_eq!(doc["pandoc-api-version"], json!([1, 23, 1]));
+        assert_eq!(doc["meta"], json!({}));
+        assert_eq!(doc["blocks"], json!([]));
+    }
+
+    #[test]
+    fn test_header_with_identifier() {
+        let doc = to_pandoc(vec![
+            Node::heading(2, vec![Node::text("See Also")]),
+            Node::heading(2, vec![Node::text("See Also")]),
+        ]);
+        assert_eq!(
+            doc["blocks"][0],
+            json!({"t": "Header", "c": [2, ["see-also", [], []], [
+                {"t": "Str", "c": "See"}, {"t": "Space"}, {"t": "Str", "c": "Also"}
+            ]]})
+        );
+        assert_eq!(doc["blocks"][1]["c"][1][0], "see-also-1");
+    }
+
+    #[test]
+    fn test_paragraph_inlines() {
+        let doc = to_pandoc(vec![Node::paragraph(vec![
+            Node::text(" Hello\nworld "),
+            Node::emphasis(vec![Node::text("em")]),
+            Node::inline_code("x"),
+            Node::link("https://example.com", vec![Node::text("link")]),
+            Node::inline_math("\\alpha"),
+            Node::line_break(),
+        ])]);
+        assert_eq!(
+            doc["blocks"][0],
+            json!({"t": "Para", "c": [
+                {"t": "Str", "c": "Hello"},
+                {"t": "SoftBreak"},
+                {"t": "Str", "c": "world"},
+                {"t": "Space"},
+                {"t": "Emph", "c": [{"t": "Str", "c": "em"}]},
+                {"t": "Code", "c": [["", [], []], "x"]},
+                {"t": "Link", "c": [["", [], []], [{"t": "Str", "c": "link"}], ["https://example.com", ""]]},
+                {"t": "Math", "c": [{"t": "InlineMath"}, "\\alpha"]},
+                {"t": "LineBreak"}
+            ]})
+        );
+    }
+
+    #[test]
+    fn test_code_block() {
+        let doc = to_pandoc(vec![Node::code(Some("r".to_string()), "x <- 1\n")]);
+        assert_eq!(
+            doc["blocks"][0],
+            json!({"t": "CodeBlock", "c": [["", ["r"], []], "x <- 1"]})
+        );
+    }
+
+    #[test]
+    fn test_display_math() {
+        let doc = to_pandoc(vec![Node::math("x^2")]);
+        assert_eq!(
+            doc["blocks"][0],
+            json!({"t": "Para", "c": [{"t": "Math", "c": [{"t": "DisplayMath"}, "x^2"]}]})
+        );
+    }
+
+    #[test]
+    fn test_lists() {
+        let item = |s: &str| Node::list_item(vec![Node::paragraph(vec![Node::text(s)])]);
+        let doc = to_pandoc(vec![
+            Node::list(false, vec![item("a")]),
+            Node::ordered_list_from(3, vec![item("c")]),
+        ]);
+        assert_eq!(
+            doc["blocks"][0],
+            json!({"t": "BulletList", "c": [[{"t": "Plain", "c": [{"t": "Str", "c": "a"}]}]]})
+        );
+        assert_eq!(
+            doc["blocks"][1],
+            json!({"t": "OrderedList", "c": [
+                [3, {"t": "Decimal"}, {"t": "Period"}],
+                [[{"t": "Plain", "c": [{"t": "Str", "c": "c"}]}]]
+            ]})
+        );
+    }
+
+    #[test]
+    fn test_definition_list() {
+        let doc = to_pandoc(vec![Node::definition_list(vec![
+            Node::definition_term(vec![Node::inline_code("x")]),
+            Node::definition_description(vec![Node::paragraph(vec![Node::text("obj")])]),
+        ])]);
+        assert_eq!(
+            doc["blocks"][0],
+            json!({"t": "DefinitionList", "c": [[
+                [{"t": "Code", "c": [["", [], []], "x"]}],
+                [[{"t": "Plain", "c": [{"t": "Str", "c": "obj"}]}]]
+            ]]})
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let doc = to_pandoc(vec![Node::table(
+            vec![Some(Align::Left), None],
+            vec![
+                Node::table_row(vec![
+                    Node::table_cell(vec![Node::text("A")]),
+                    Node::table_cell(vec![Node::text("B")]),
+                ]),
+                Node::table_row(vec![
+                    Node::table_cell(vec![Node::text("1")]),
+                    Node::table_cell(vec![Node::text("2")]),
+                ]),
+            ],
+        )]);
+        let cell = |s: &str| json!([["", [], []], {"t": "AlignDefault"}, 1, 1, [{"t": "Plain", "c": [{"t": "Str", "c": s}]}]]);
+        assert_eq!(
+            doc["blocks"][0],
+            json!({"t": "Table", "c": [
+                ["", [], []],
+                [null, []],
+                [[{"t": "AlignLeft"}, {"t": "ColWidthDefault"}], [{"t": "AlignDefault"}, {"t": "ColWidthDefault"}]],
+                [["", [], []], [[["", [], []], [cell("A"), cell("B")]]]],
+                [[["", [], []], 0, [], [[["", [], []], [cell("1"), cell("2")]]]]],
+                [["", [], []], []]
+            ]})
+        );
+    }
+
+    #[test]
+    fn test_cell_inlines_in_one_plain() {
+        let doc = to_pandoc(vec![Node::table(
+            vec![None],
+            vec![Node::table_row(vec![Node::table_cell(vec![
+                Node::text("a "),
+                Node::inline_code("b"),
+            ])])],
+        )]);
+        assert_eq!(
+            doc["blocks"][0]["c"][3][1][0][1][0][4],
+            json!([{"t": "Plain", "c": [
+                {"t": "Str", "c": "a"},
+                {"t": "Space"},
+                {"t": "Code", "c": [["", [], []], "b"]}
+            ]}])
+        );
+    }
+
+    #[test]
+    fn test_div_and_span() {
+        let doc = to_pandoc(vec![Node::div(
+            Attributes::class("callout-note"),
+            vec![Node::paragraph(vec![
+                Node::span(Attributes::class("x"), vec![Node::text("a")]),
+                Node::raw_inline("tex", "\\LaTeX"),
+            ])],
+        )]);
+        assert_eq!(
+            doc["blocks"][0],
+            json!({"t": "Div", "c": [
+                ["", ["callout-note"], []],
+                [{"t": "Para", "c": [
+                    {"t": "Span", "c": [["", ["x"], []], [{"t": "Str", "c": "a"}]]},
+                    {"t": "RawInline", "c": ["tex", "\\LaTeX"]}
+                ]}]
+            ]})
+        );
+    }
+
+    #[test]
+    fn test_raw_html() {
+        let doc = to_pandoc(vec![Node::html("<div></div>")]);
+        assert_eq!(
+            doc["blocks"][0],
+            json!({"t": "RawBlock", "c": ["html", "<div></div>"]})
+        );
+    }
+
+    #[test]
+    fn test_metadata() {
+        let options = WriterOptions {
+            frontmatter: Some(Frontmatter {
+                title: Some("My Title".to_string()),
+                metadata: Some(RdMetadata {
+                    aliases: vec!["foo".to_string()],
+                    lifecycle: Some("deprecated".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let json = mdast_to_pandoc_json(&Root::new(vec![]), &options);
+        let doc: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            doc["meta"]["title"],
+            json!({"t": "MetaInlines", "c": [{"t": "Str", "c": "My"}, {"t": "Space"}, {"t": "Str", "c": "Title"}]})
+        );
+        assert_eq!(
+            doc["meta"]["aliases"],
+            json!({"t": "MetaList", "c": [{"t": "MetaString", "c": "foo"}]})
+        );
+        assert_eq!(
+            doc["meta"]["lifecycle"],
+            json!({"t": "MetaString", "c": "deprecated"})
+        );
+    }
+}
@@ -0,0 +1,947 @@
+//! Translation of raw HTML into mdast
+//!
+//! Rd's `\out{}` passes HTML through verbatim, which only works for HTML-based
+//! output. For other targets, [`translate_raw_html`] replaces `html` raw blocks and
+//! raw inlines with equivalent mdast, covering the markup roxygen2 and package
+//! authors commonly write:
+//!
+//! - `<div>` and `<span>` become [`Div`](crate::Div) and [`Span`](crate::Span)
+//!   nodes with the same id, classes and attributes
+//! - `<a id="...">` anchors become spans carrying that id; `<a href>` becomes a link
+//! - `<details>` becomes a `.details` div, with the `<summary>` as a bold paragraph
+//! - `<table>` becomes a table, `<ul>`/`<ol>` lists, `<pre>` code blocks and
+//!   `<p>`/`<h1>`..`<h6>` paragraphs and headings
+//! - `<code>`, `<em>`, `<strong>`, `<br>` and `<img>` map to their inline nodes
+//!
+//! Other tags are unwrapped to their content, while `<script>`, `<style>` and
+//! comments are dropped. An element may be split over several raw nodes, as in
+//! `\if{html}{\out{<details>}}...\if{html}{\out{</details>}}`: sibling nodes
+//! between the opening and the closing tag become its content.
+
+use crate::mdast::{Align, Attributes, Node};
+
+/// Elements without content or closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose content is never rendered
+const DROPPED_ELEMENTS: &[&str] = &["head", "script", "style", "template", "title"];
+
+/// Elements that start a new block when they appear in flow content
+const BLOCK_ELEMENTS: &[&str] = &[
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "div",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Replace `html` raw nodes in a block list (and its descendants) with mdast
+///
+/// Raw nodes for other formats are kept as they are.
+pub fn translate_raw_html(nodes: Vec<Node>) -> Vec<Node> {
+    translate(nodes, false)
+}
+
+/// Replace `html` raw nodes in a list of inline nodes with mdast
+pub fn translate_raw_html_inline(nodes: Vec<Node>) -> Vec<Node> {
+    translate(nodes, true)
+}
+
+/// HTML token
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open {
+        name: String,
+        attrs: Vec<(String, String)>,
+    },
+    Close(String),
+    Text(String),
+}
+
+/// Parsed HTML element
+struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Content>,
+}
+
+/// Content of an element: HTML text, a nested element, or an existing mdast node
+/// that was enclosed by tags from separate raw nodes
+enum Content {
+    Element(Element),
+    Text(String),
+    Node(Node),
+}
+
+fn translate(nodes: Vec<Node>, inline: bool) -> Vec<Node> {
+    if !nodes.iter().any(is_raw_html) {
+        return nodes.into_iter().map(translate_children).collect();
+    }
+
+    let mut builder = TreeBuilder::default();
+    for node in nodes {
+        match node {
+            Node::RawBlock(r) | Node::RawInline(r) if r.format == "html" => {
+                for token in tokenize(&r.value) {
+                    builder.push_token(token);
+                }
+            }
+            node => builder.push(Content::Node(translate_children(node))),
+        }
+    }
+    let contents = builder.finish();
+    if inline {
+        inlines(contents)
+    } else {
+        blocks(contents)
+    }
+}
+
+fn is_raw_html(node: &Node) -> bool {
+    matches!(node, Node::RawBlock(r) | Node::RawInline(r) if r.format == "html")
+}
+
+/// Translate raw HTML inside the children of a node
+fn translate_children(node: Node) -> Node {
+    match node {
+        Node::Heading(mut h) => {
+            h.children = translate(h.children, true);
+            Node::Heading(h)
+        }
+        Node::Paragraph(mut p) => {
+            p.children = translate(p.children, true);
+            Node::Paragraph(p)
+        }
+        Node::Blockquote(mut b) => {
+            b.children = translate(b.children, false);
+            Node::Blockquote(b)
+        }
+        Node::List(mut l) => {
+            l.children = l.children.into_iter().map(translate_children).collect();
+            Node::List(l)
+        }
+        Node::ListItem(mut li) => {
+            li.children = translate(li.children, false);
+            Node::ListItem(li)
+        }
+        Node::Table(mut t) => {
+            t.children = t.children.into_iter().map(translate_children).collect();
+            Node::Table(t)
+        }
+        Node::TableRow(mut r) => {
+            r.children = r.children.into_iter().map(translate_children).collect();
+            Node::TableRow(r)
+        }
+        Node::TableCell(mut c) => {
+            c.children = translate(c.children, true);
+            Node::TableCell(c)
+        }
+        Node::DefinitionList(mut dl) => {
+            dl.children = dl.children.into_iter().map(translate_children).collect();
+            Node::DefinitionList(dl)
+        }
+        Node::DefinitionTerm(mut dt) => {
+            dt.children = translate(dt.children, true);
+            Node::DefinitionTerm(dt)
+        }
+        Node::DefinitionDescription(mut dd) => {
+            dd.children = translate(dd.children, false);
+            Node::DefinitionDescription(dd)
+        }
+        Node::Emphasis(mut e) => {
+            e.children = translate(e.children, true);
+            Node::Emphasis(e)
+        }
+        Node::Strong(mut s) => {
+            s.children = translate(s.children, true);
+            Node::Strong(s)
+        }
+        Node::Link(mut l) => {
+            l.children = translate(l.children, true);
+            Node::Link(l)
+        }
+        Node::Div(mut d) => {
+            d.children = translate(d.children, false);
+            Node::Div(d)
+        }
+        Node::Span(mut s) => {
+            s.children = translate(s.children, true);
+            Node::Span(s)
+        }
+        node => node,
+    }
+}
+
+/// Builds an element tree from tokens, closing unbalanced elements leniently
+#[derive(Default)]
+struct TreeBuilder {
+    open: Vec<Element>,
+    root: Vec<Content>,
+}
+
+impl TreeBuilder {
+    fn push(&mut self, content: Content) {
+        match self.open.last_mut() {
+            Some(element) => element.children.push(content),
+            None => self.root.push(content),
+        }
+    }
+
+    fn push_token(&mut self, token: Token) {
+        match token {
+            Token::Open { name, attrs } => {
+                let element = Element {
+                    name,
+                    attrs,
+                    children: Vec::new(),
+                };
+                if VOID_ELEMENTS.contains(&element.name.as_str()) {
+                    self.push(Content::Element(element));
+                } else {
+                    self.open.push(element);
+                }
+            }
+            Token::Close(name) => {
+                // A closing tag without a matching open element is ignored
+                if let Some(pos) = self.open.iter().rposition(|e| e.name == name) {
+                    while self.open.len() > pos {
+                        self.close_last();
+                    }
+                }
+            }
+            Token::Text(text) => self.push(Content::Text(text)),
+        }
+    }
+
+    fn close_last(&mut self) {
+        if let Some(element) = self.open.pop() {
+            self.push(Content::Element(element));
+        }
+    }
+
+    fn finish(mut self) -> Vec<Content> {
+        while !self.open.is_empty() {
+            self.close_last();
+        }
+        self.root
+    }
+}
+
+/// Convert flow content to block nodes, grouping inline runs into paragraphs
+fn blocks(contents: Vec<Content>) -> Vec<Node> {
+    let mut result = Vec::new();
+    let mut para = Vec::new();
+    for content in contents {
+        match content {
+            Content::Element(e) if BLOCK_ELEMENTS.contains(&e.name.as_str()) => {
+                flush_paragraph(&mut para, &mut result);
+                result.extend(block_element(e));
+            }
+            Content::Node(node) if !is_phrasing(&node) => {
+                flush_paragraph(&mut para, &mut result);
+                result.push(node);
+            }
+            content => para.extend(inline_content(content)),
+        }
+    }
+    flush_paragraph(&mut para, &mut result);
+    result
+}
+
+fn flush_paragraph(para: &mut Vec<Node>, result: &mut Vec<Node>) {
+    let children = trim_inlines(std::mem::take(para));
+    if !children.is_empty() {
+        result.push(Node::paragraph(children));
+    }
+}
+
+fn block_element(e: Element) -> Vec<Node> {
+    match e.name.as_str() {
+        "div" => {
+            let attributes = attributes(&e.attrs);
+            let children = blocks(e.children);
+            if attributes.is_empty() {
+                children
+            } else {
+                vec![Node::div(attributes, children)]
+            }
+        }
+        "details" => {
+            let mut attributes = attributes(&e.attrs);
+            attributes.classes.insert(0, "details".to_string());
+            let mut children = Vec::new();
+            let mut body = Vec::new();
+            for content in e.children {
+                match content {
+                    Content::Element(summary) if summary.name == "summary" => {
+                        let title = trim_inlines(inlines(summary.children));
+                        if !title.is_empty() {
+                            children.push(Node::paragraph(vec![Node::strong(title)]));
+                        }
+                    }
+                    content => body.push(content),
+                }
+            }
+            children.extend(blocks(body));
+            vec![Node::div(attributes, children)]
+        }
+        "p" => {
+            let children = trim_inlines(inlines(e.children));
+            if children.is_empty() {
+                Vec::new()
+            } else {
+                vec![Node::paragraph(children)]
+            }
+        }
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let depth = e.name[1..].parse().unwrap_or(1);
+            vec![Node::heading(depth, trim_inlines(inlines(e.children)))]
+        }
+        "pre" => {
+            let lang = code_language(&e);
+            let code = text_content(&e.children);
+            let code = code.strip_prefix('\n').unwrap_or(&code);
+            vec![Node::code(lang, code.trim_end_matches('\n'))]
+        }
+        "ul" | "ol" => {
+            let items: Vec<Node> = e
+                .children
+                .into_iter()
+                .filter_map(|content| match content {
+                    Content::Element(li) if li.name == "li" => {
+                        Some(Node::list_item(blocks(li.children)))
+                    }
+                    _ => None,
+                })
+                .collect();
+            if items.is_empty() {
+                Vec::new()
+            } else {
+                vec![Node::list(e.name == "ol", items)]
+            }
+        }
+        "blockquote" => vec![Node::blockquote(blocks(e.children))],
+        "hr" => vec![Node::thematic_break()],
+        "table" => table(e).into_iter().collect(),
+        _ => blocks(e.children),
+    }
+}
+
+/// Language of a `<pre>` block, from a `language-*` or `sourceCode *` class on
+/// the `<pre>` or its `<code>` child
+fn code_language(pre: &Element) -> Option<String> {
+    let code = pre.children.iter().find_map(|content| match content {
+        Content::Element(e) if e.name == "code" => Some(e),
+        _ => None,
+    });
+    std::iter::once(pre)
+        .chain(code)
+        .flat_map(|e| attributes(&e.attrs).classes)
+        .filter(|class| class != "sourceCode")
+        .map(|class| match class.strip_prefix("language-") {
+            Some(lang) => lang.to_string(),
+            None => class,
+        })
+        .next()
+}
+
+/// Convert a `<table>` element, using its first row as the header
+fn table(e: Element) -> Option<Node> {
+    let mut rows = Vec::new();
+    collect_rows(e.children, &mut rows);
+    let first = rows.first()?;
+
+    let align: Vec<Option<Align>> = first
+        .iter()
+        .map(|cell| {
+            let align = cell
+                .attrs
+                .iter()
+                .find(|(key, _)| key == "align")
+                .map(|(_, value)| value.to_ascii_lowercase());
+            match align.as_deref() {
+                Some("left") => Some(Align::Left),
+                Some("center") => Some(Align::Center),
+                Some("right") => Some(Align::Right),
+                _ => None,
+            }
+        })
+        .collect();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+    let rows = rows
+        .into_iter()
+        .map(|cells| {
+            let mut cells: Vec<Node> = cells
+                .into_iter()
+                .map(|cell| Node::table_cell(trim_inlines(inlines(cell.children))))
+                .collect();
+            cells.resize_with(columns, || Node::table_cell(Vec::new()));
+            Node::table_row(cells)
+        })
+        .collect();
+    let mut align = align;
+    align.resize(columns, None);
+    Some(Node::table(align, rows))
+}
+
+/// Collect the cells of `<tr>` rows, directly or inside `<thead>`, `<tbody>` and `<tfoot>`
+fn collect_rows(contents: Vec<Content>, rows: &mut Vec<Vec<Element>>) {
+    for content in contents {
+        let Content::Element(e) = content else {
+            continue;
+        };
+        match e.name.as_str() {
+            "tr" => rows.push(
+                e.children
+                    .into_iter()
+                    .filter_map(|content| match content {
+                        Content::Element(cell) if cell.name == "td" || cell.name == "th" => {
+                            Some(cell)
+                        }
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            "thead" | "tbody" | "tfoot" => collect_rows(e.children, rows),
+            _ => {}
+        }
+    }
+}
+
+/// Convert phrasing content to inline nodes
+fn inlines(contents: Vec<Content>) -> Vec<Node> {
+    contents.into_iter().flat_map(inline_content).collect()
+}
+
+fn inline_content(content: Content) -> Vec<Node> {
+    match content {
+        Content::Text(text) => {
+            let text = collapse_whitespace(&text);
+            if text.is_empty() {
+                Vec::new()
+            } else {
+                vec![Node::text(text)]
+            }
+        }
+        Content::Node(Node::Paragraph(p)) => p.children,
+        Content::Node(node) => vec![node],
+        Content::Element(e) => inline_element(e),
+    }
+}
+
+fn inline_element(e: Element) -> Vec<Node> {
+    let name = e.name.as_str();
+    if DROPPED_ELEMENTS.contains(&name) {
+        return Vec::new();
+    }
+    match name {
+        "a" => {
+            let href = attribute(&e.attrs, "href").map(str::to_string);
+            let anchor = attribute(&e.attrs, "id")
+                .or_else(|| attribute(&e.attrs, "name"))
+                .map(str::to_string);
+            let title = attribute(&e.attrs, "title").map(str::to_string);
+            let children = inlines(e.children);
+            match (href, anchor) {
+                (Some(url), _) => vec![match title {
+                    Some(title) => Node::link_with_title(url, title, children),
+                    None => Node::link(url, children),
+                }],
+                (None, Some(id)) => vec![Node::span(
+                    Attributes {
+                        id: Some(id),
+                        ..Default::default()
+                    },
+                    children,
+                )],
+                (None, None) => children,
+            }
+        }
+        "span" | "div" => {
+            let attributes = attributes(&e.attrs);
+            let children = inlines(e.children);
+            if attributes.is_empty() {
+                children
+            } else {
+                vec![Node::span(attributes, children)]
+            }
+        }
+        "code" | "kbd" | "samp" | "tt" => vec![Node::inline_code(text_content(&e.children))],
+        "em" | "i" | "cite" | "dfn" | "var" => vec![Node::emphasis(inlines(e.children))],
+        "strong" | "b" => vec![Node::strong(inlines(e.children))],
+        "br" => vec![Node::line_break()],
+        "img" => match attribute(&e.attrs, "src") {
+            Some(src) => {
+                let alt = attribute(&e.attrs, "alt").unwrap_or_default();
+                vec![match attribute(&e.attrs, "title") {
+                    Some(title) => Node::image_with_title(src, alt, title),
+                    None => Node::image(src, alt),
+                }]
+            }
+            None => Vec::new(),
+        },
+        _ => inlines(e.children),
+    }
+}
+
+/// Whether an existing node belongs in a paragraph
+fn is_phrasing(node: &Node) -> bool {
+    matches!(
+        node,
+        Node::Text(_)
+            | Node::Emphasis(_)
+            | Node::Strong(_)
+            | Node::InlineCode(_)
+            | Node::Break
+            | Node::Link(_)
+            | Node::Image(_)
+            | Node::InlineMath(_)
+            | Node::Span(_)
+            | Node::RawInline(_)
+    )
+}
+
+/// Merge adjacent text nodes and trim whitespace at the edges and after line breaks
+fn trim_inlines(nodes: Vec<Node>) -> Vec<Node> {
+    let mut result: Vec<Node> = Vec::new();
+    for node in nodes {
+        match node {
+            Node::Text(t) => {
+                let value = match result.last() {
+                    None | Some(Node::Break) => t.value.trim_start().to_string(),
+                    _ => t.value,
+                };
+                if let Some(Node::Text(last)) = result.last_mut() {
+                    last.value.push_str(&value);
+                } else if !value.is_empty() {
+                    result.push(Node::text(value));
+                }
+            }
+            Node::Break => {
+                if let Some(Node::Text(last)) = result.last_mut() {
+                    last.value.truncate(last.value.trim_end().len());
+                }
+                result.push(Node::Break);
+            }
+            node => result.push(node),
+        }
+    }
+    if let Some(Node::Text(last)) = result.last_mut() {
+        last.value.truncate(last.value.trim_end().len());
+    }
+    result.retain(|node| !matches!(node, Node::Text(t) if t.value.is_empty()));
+    result
+}
+
+/// Concatenated text of elements, without collapsing whitespace
+fn text_content(contents: &[Content]) -> String {
+    let mut text = String::new();
+    for content in contents {
+        match content {
+            Content::Text(t) => text.push_str(t),
+            Content::Element(e) if e.name == "br" => text.push('\n'),
+            Content::Element(e) => text.push_str(&text_content(&e.children)),
+            Content::Node(Node::Text(t)) => text.push_str(&t.value),
+            Content::Node(Node::InlineCode(c)) => text.push_str(&c.value),
+            Content::Node(_) => {}
+        }
+    }
+    text
+}
+
+fn attribute<'a>(attrs: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Pandoc attributes from HTML attributes
+fn attributes(attrs: &[(String, String)]) -> Attributes {
+    let mut attributes = Attributes::default();
+    for (key, value) in attrs {
+        match key.as_str() {
+            "id" => attributes.id = Some(value.clone()),
+            "class" => attributes
+                .classes
+                .extend(value.split_whitespace().map(str::to_string)),
+            _ => attributes.attributes.push((
+                key.strip_prefix("data-").unwrap_or(key).to_string(),
+                value.clone(),
+            )),
+        }
+    }
+    attributes
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut space = false;
+    for c in s.chars() {
+        if c.is_whitespace() {
+            space = true;
+        } else {
+            if space {
+                result.push(' ');
+            }
+            space = false;
+            result.push(c);
+        }
+    }
+    if space {
+        result.push(' ');
+    }
+    result
+}
+
+/// Split HTML into tags and text; comments, doctypes and processing instructions are skipped
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some((token, len)) = parse_tag(rest) {
+            tokens.push(token);
+            rest = &rest[len..];
+        } else {
+            // Text runs up to the next `<` (a stray `<` is kept as text)
+            let end = rest
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| c == '<')
+                .map_or(rest.len(), |(i, _)| i);
+            tokens.push(Token::Text(decode_entities(&rest[..end])));
+            rest = &rest[end..];
+        }
+    }
+    tokens
+}
+
+/// Parse an opening or closing tag at the start of `s`, returning it with its length
+fn parse_tag(s: &str) -> Option<(Token, usize)> {
+    let bytes = s.as_bytes();
+    if bytes.first() != Some(&b'<') {
+        return None;
+    }
+    let closing = bytes.get(1) == Some(&b'/');
+    let mut pos = if closing { 2 } else { 1 };
+    if !bytes.get(pos)?.is_ascii_alphabetic() {
+        return None;
+    }
+    let start = pos;
+    while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'-') {
+        pos += 1;
+    }
+    let name = s[start..pos].to_ascii_lowercase();
+
+    if closing {
+        let end = s[pos..].find('>')?;
+        return Some((Token::Close(name), pos + end + 1));
+    }
+
+    let mut attrs = Vec::new();
+    loop {
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        match bytes.get(pos)? {
+            b'>' => return Some((Token::Open { name, attrs }, pos + 1)),
+            b'/' => {
+                pos += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        let key_start = pos;
+        while pos < bytes.len()
+            && !bytes[pos].is_ascii_whitespace()
+            && !matches!(bytes[pos], b'=' | b'>' | b'/')
+        {
+            pos += 1;
+        }
+        let key = s[key_start..pos].to_ascii_lowercase();
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+
+        let mut value = String::new();
+        if bytes.get(pos) == Some(&b'=') {
+            pos += 1;
+            while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            match bytes.get(pos)? {
+                quote @ (b'"' | b'\'') => {
+                    let end = s[pos + 1..].find(*quote as char)?;
+                    value = decode_entities(&s[pos + 1..pos + 1 + end]);
+                    pos += end + 2;
+                }
+                _ => {
+                    let value_start = pos;
+                    while pos < bytes.len()
+                        && !bytes[pos].is_ascii_whitespace()
+                        && bytes[pos] != b'>'
+                    {
+                        pos += 1;
+                    }
+                    value = decode_entities(&s[value_start..pos]);
+                }
+            }
+        }
+        attrs.push((key, value));
+    }
+}
+
+/// Decode named entities common in Rd output and numeric character references
+fn decode_entities(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        result.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| {
+                let entity = &rest[1..end + 1];
+                let c = match entity {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    "nbsp" => Some('\u{a0}'),
+                    _ => entity
+                        .strip_prefix("#x")
+                        .or_else(|| entity.strip_prefix("#X"))
+                        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                        .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                        .and_then(char::from_u32),
+                };
+                c.map(|c| (c, end + 2))
+            });
+        match decoded {
+            Some((c, len)) => {
+                result.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mdast::{Root, Table};
+    use crate::writer::{WriterOptions, mdast_to_qmd};
+
+    fn html_block(value: &str) -> Node {
+        Node::raw_block("html", value)
+    }
+
+    fn to_md(nodes: Vec<Node>) -> String {
+        mdast_to_qmd(
+            &Root::new(translate_raw_html(nodes)),
+            &WriterOptions::default(),
+        )
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("<a id='x' hidden>A &amp; B</a><!-- c --><br/>"),
+            vec![
+                Token::Open {
+                    name: "a".to_string(),
+                    attrs: vec![
+                        ("id".to_string(), "x".to_string()),
+                        ("hidden".to_string(), String::new())
+                    ],
+                },
+                Token::Text("A & B".to_string()),
+                Token::Close("a".to_string()),
+                Token::Open {
+                    name: "br".to_string(),
+                    attrs: vec![],
+                },
+            ]
+        );
+        assert_eq!(
+            tokenize("1 < 2"),
+            vec![
+                Token::Text("1 ".to_string()),
+                Token::Text("< 2".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_div() {
+        let nodes = translate_raw_html(vec![html_block(
+            "<div class=\"note wide\" id=\"n1\" data-kind=\"x\"><p>Hello <b>world</b></p></div>",
+        )]);
+        let Node::Div(div) = &nodes[0] else {
+            panic!("expected a div, got {:?}", nodes);
+        };
+        assert_eq!(div.attributes.id.as_deref(), Some("n1"));
+        assert_eq!(div.attributes.classes, vec!["note", "wide"]);
+        assert_eq!(
+            div.attributes.attributes,
+            vec![("kind".to_string(), "x".to_string())]
+        );
+        assert_eq!(
+            div.children,
+            vec![Node::paragraph(vec![
+                Node::text("Hello "),
+                Node::strong(vec![Node::text("world")]),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_div_split_across_raw_nodes() {
+        // roxygen2 emits opening and closing tags as separate \out{} blocks
+        let nodes = translate_raw_html(vec![
+            html_block("<div class=\"warning\">"),
+            Node::paragraph(vec![Node::text("Inside")]),
+            html_block("</div>"),
+            Node::paragraph(vec![Node::text("Outside")]),
+        ]);
+        assert_eq!(
+            nodes,
+            vec![
+                Node::div(
+                    Attributes::class("warning"),
+                    vec![Node::paragraph(vec![Node::text("Inside")])]
+                ),
+                Node::paragraph(vec![Node::text("Outside")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_anchor_and_link() {
+        let nodes = translate_raw_html(vec![Node::paragraph(vec![
+            Node::raw_inline("html", "<a id=\"method-new\"></a>"),
+            Node::text("See "),
+            Node::raw_inline("html", "<a href=\"https://example.com\">here</a>"),
+        ])]);
+        assert_eq!(
+            to_md(nodes.clone()),
+            "[]{#method-new}See [here](https://example.com)\n"
+        );
+        assert!(matches!(&nodes[0], Node::Paragraph(p) if p.children.len() == 3));
+    }
+
+    #[test]
+    fn test_details() {
+        let md = to_md(vec![
+            html_block("<details><summary>Show more</summary>"),
+            Node::code(Some("r".to_string()), "x <- 1"),
+            html_block("</details>"),
+        ]);
+        assert_eq!(
+            md,
+            "::: {.details}\n**Show more**\n\n```r\nx <- 1\n```\n:::\n"
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let nodes = translate_raw_html(vec![html_block(
+            "<table>\n<thead><tr><th align=\"left\">Name</th><th>Value</th></tr></thead>\n\
+             <tbody><tr><td><code>x</code></td><td>1</td></tr>\n<tr><td>y</td></tr></tbody>\n</table>",
+        )]);
+        let Node::Table(Table { align, children }) = &nodes[0] else {
+            panic!("expected a table, got {:?}", nodes);
+        };
+        assert_eq!(align, &vec![Some(Align::Left), None]);
+        assert_eq!(children.len(), 3);
+        assert_eq!(
+            to_md(nodes.clone()),
+            "| Name | Value |\n|:---|----|\n| `x` | 1 |\n| y |  |\n"
+        );
+    }
+
+    #[test]
+    fn test_pre_and_lists() {
+        let nodes = translate_raw_html(vec![html_block(
+            "<pre><code class=\"language-r\">f(x)\n</code></pre><ul><li>one</li><li>two</li></ul>",
+        )]);
+        assert_eq!(
+            nodes,
+            vec![
+                Node::code(Some("r".to_string()), "f(x)"),
+                Node::list(
+                    false,
+                    vec![
+                        Node::list_item(vec![Node::paragraph(vec![Node::text("one")])]),
+                        Node::list_item(vec![Node::paragraph(vec![Node::text("two")])]),
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scripts_dropped_and_other_formats_kept() {
+        let nodes = translate_raw_html(vec![
+            html_block("<script>alert(1)</script><style>p {}</style>"),
+            Node::raw_block("latex", "\\newpage"),
+        ]);
+        assert_eq!(nodes, vec![Node::raw_block("latex", "\\newpage")]);
+    }
+
+    #[test]
+    fn test_nested_containers() {
+        let nodes = translate_raw_html(vec![Node::list(
+            false,
+            vec![Node::list_item(vec![Node::paragraph(vec![
+                Node::raw_inline("html", "<em>"),
+                Node::text("Note"),
+                Node::raw_inline("html", "</em>"),
+            ])])],
+        )]);
+        assert_eq!(
+            nodes,
+            vec![Node::list(
+                false,
+                vec![Node::list_item(vec![Node::paragraph(vec![
+                    Node::emphasis(vec![Node::text("Note")])
+                ])])]
+            )]
+        );
+    }
+}
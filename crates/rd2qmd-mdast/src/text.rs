@@ -0,0 +1,759 @@
+//! mdast to plain-text writer
+//!
+//! Converts an mdast tree into wrapped plain text for reading in a terminal,
+//! in the spirit of R's `Rd2txt()`: section bodies are indented, lists and
+//! definition lists use hanging indentation, and tables are drawn with ASCII
+//! borders. Optionally, headings, code and links are styled with ANSI escape
+//! sequences, and links are emitted as OSC 8 hyperlinks.
+
+use crate::mdast::{Node, Root};
+
+/// Indentation of section bodies (as in `Rd2txt()`)
+const SECTION_INDENT: usize = 5;
+
+/// Indentation of definition descriptions relative to their term
+const DESCRIPTION_INDENT: usize = 4;
+
+/// Options for the plain-text writer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextOptions {
+    /// Maximum line width for wrapped text (default: 80)
+    pub width: usize,
+    /// Style headings, code, emphasis and links with ANSI escape sequences
+    pub ansi: bool,
+    /// Emit links as OSC 8 terminal hyperlinks instead of printing the URL
+    pub hyperlinks: bool,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            width: 80,
+            ansi: false,
+            hyperlinks: false,
+        }
+    }
+}
+
+/// Convert mdast to plain text
+pub fn mdast_to_text(root: &Root, options: &TextOptions) -> String {
+    let mut writer = TextWriter::new(options);
+    writer.write_root(root);
+    writer.output
+}
+
+/// Text writer state
+struct TextWriter<'a> {
+    options: &'a TextOptions,
+    output: String,
+}
+
+impl<'a> TextWriter<'a> {
+    fn new(options: &'a TextOptions) -> Self {
+        Self {
+            options,
+            output: String::new(),
+        }
+    }
+
+    fn write_root(&mut self, root: &Root) {
+        // Body indentation of the current section
+        let mut indent = 0;
+        for node in &root.children {
+            if !self.output.is_empty() {
+                self.output.push('\n');
+            }
+            match node {
+                Node::Heading(h) if h.depth == 1 => {
+                    self.write_heading(h, 0);
+                    indent = 0;
+                }
+                Node::Heading(h) if h.depth == 2 => {
+                    self.write_heading(h, 0);
+                    indent = SECTION_INDENT;
+                }
+                Node::Heading(h) => {
+                    self.write_heading(h, SECTION_INDENT);
+                    indent = SECTION_INDENT;
+                }
+                _ => self.write_block(node, indent),
+            }
+        }
+    }
+
+    fn write_heading(&mut self, h: &crate::mdast::Heading, indent: usize) {
+        let text = self.render_inline(&h.children).replace('\n', " ");
+        let text = if h.depth == 1 {
+            text
+        } else {
+            format!("{}:", text)
+        };
+        self.output.push_str(&" ".repeat(indent));
+        self.output.push_str(&self.style(&text, "1", "22"));
+        self.output.push('\n');
+        if h.depth == 1 {
+            self.output.push_str(&"=".repeat(visible_width(&text)));
+            self.output.push('\n');
+        }
+    }
+
+    /// Write a sequence of blocks separated by blank lines
+    fn write_blocks(&mut self, nodes: &[Node], indent: usize) {
+        for (i, node) in nodes.iter().enumerate() {
+            if i > 0 {
+                self.output.push('\n');
+            }
+            self.write_block(node, indent);
+        }
+    }
+
+    fn write_html_block(&mut self, html: &str, indent: usize) {
+        let text = html_to_text(html);
+        if !text.trim().is_empty() {
+            self.write_wrapped(&text, &" ".repeat(indent), indent);
+        }
+    }
+
+    fn write_block(&mut self, node: &Node, indent: usize) {
+        match node {
+            Node::Heading(h) => self.write_heading(h, indent),
+            Node::Paragraph(p) => {
+                let text = self.render_inline(&p.children);
+                self.write_wrapped(&text, &" ".repeat(indent), indent);
+            }
+            Node::ThematicBreak => {
+                let len = self.options.width.saturating_sub(indent).max(3);
+                self.output.push_str(&" ".repeat(indent));
+                self.output.push_str(&"-".repeat(len));
+                self.output.push('\n');
+            }
+            Node::Blockquote(b) => self.write_blocks(&b.children, indent + DESCRIPTION_INDENT),
+            Node::List(l) => self.write_list(l, indent),
+            Node::ListItem(li) => self.write_blocks(&li.children, indent),
+            Node::Code(c) => {
+                for line in c.value.trim_end_matches('\n').lines() {
+                    if !line.is_empty() {
+                        self.output.push_str(&" ".repeat(indent));
+                        self.output.push_str(&self.style(line, "36", "39"));
+                    }
+                    self.output.push('\n');
+                }
+            }
+            Node::Math(m) => {
+                for line in m.value.trim().lines() {
+                    self.output
+                        .push_str(&" ".repeat(indent + DESCRIPTION_INDENT));
+                    self.output.push_str(line.trim());
+                    self.output.push('\n');
+                }
+            }
+            Node::Table(t) => self.write_table(t, indent),
+            Node::DefinitionList(dl) => self.write_definition_list(dl, indent),
+            Node::TableRow(_)
+            | Node::TableCell(_)
+            | Node::DefinitionTerm(_)
+            | Node::DefinitionDescription(_) => {} // Handled by their containers
+            Node::Html(h) => self.write_html_block(&h.value, indent),
+            Node::RawBlock(r) if r.format == "html" => self.write_html_block(&r.value, indent),
+            Node::RawBlock(_) => {}
+            Node::Div(d) => self.write_blocks(&d.children, indent),
+            // Inline content at block level is written as a paragraph
+            _ => {
+                let text = self.render_inline(std::slice::from_ref(node));
+                self.write_wrapped(&text, &" ".repeat(indent), indent);
+            }
+        }
+    }
+
+    fn write_list(&mut self, l: &crate::mdast::List, indent: usize) {
+        let mut num = l.start.unwrap_or(1);
+        let items: Vec<&crate::mdast::ListItem> = l
+            .children
+            .iter()
+            .filter_map(|n| match n {
+                Node::ListItem(li) => Some(li),
+                _ => None,
+            })
+            .collect();
+
+        for (i, li) in items.iter().enumerate() {
+            if i > 0 && (l.spread || li.spread) {
+                self.output.push('\n');
+            }
+            let marker = if l.ordered {
+                let m = format!("{}. ", num);
+                num += 1;
+                m
+            } else {
+                "- ".to_string()
+            };
+            self.write_item(&li.children, indent, &marker);
+        }
+    }
+
+    /// Write blocks with a hanging marker (list bullet) before the first line
+    fn write_item(&mut self, children: &[Node], indent: usize, marker: &str) {
+        let first_prefix = format!("{}{}", " ".repeat(indent), marker);
+        let rest_indent = indent + marker.len();
+
+        for (i, child) in children.iter().enumerate() {
+            match child {
+                Node::Paragraph(p) if i == 0 => {
+                    let text = self.render_inline(&p.children);
+                    self.write_wrapped(&text, &first_prefix, rest_indent);
+                }
+                // Nested lists directly follow the item text without a blank line
+                Node::List(_) if i > 0 => self.write_block(child, rest_indent),
+                _ => {
+                    if i == 0 {
+                        self.output.push_str(first_prefix.trim_end());
+                        self.output.push('\n');
+                    } else {
+                        self.output.push('\n');
+                    }
+                    self.write_block(child, rest_indent);
+                }
+            }
+        }
+        if children.is_empty() {
+            self.output.push_str(first_prefix.trim_end());
+            self.output.push('\n');
+        }
+    }
+
+    fn write_definition_list(&mut self, dl: &crate::mdast::DefinitionList, indent: usize) {
+        let mut first = true;
+        for child in &dl.children {
+            match child {
+                Node::DefinitionTerm(dt) => {
+                    if !first {
+                        self.output.push('\n');
+                    }
+                    first = false;
+                    let term = self.render_inline(&dt.children);
+                    let term = self.style(&term, "1", "22");
+                    self.write_wrapped(&term, &" ".repeat(indent), indent);
+                }
+                Node::DefinitionDescription(dd) => {
+                    self.write_blocks(&dd.children, indent + DESCRIPTION_INDENT);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn write_table(&mut self, t: &crate::mdast::Table, indent: usize) {
+        let rows: Vec<Vec<String>> = t
+            .children
+            .iter()
+            .filter_map(|n| match n {
+                Node::TableRow(r) => Some(
+                    r.children
+                        .iter()
+                        .map(|c| match c {
+                            Node::TableCell(cell) => self.render_cell(&cell.children),
+                            _ => String::new(),
+                        })
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .collect();
+
+        let num_cols = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        if num_cols == 0 {
+            return;
+        }
+
+        // Natural column widths: the longest line in each column
+        let mut widths = vec![1; num_cols];
+        for row in &rows {
+            for (i, cell) in row.iter().enumerate() {
+                for line in cell.lines() {
+                    widths[i] = widths[i].max(visible_width(line));
+                }
+            }
+        }
+
+        // Shrink the widest columns until the table fits (borders take 3 per column + 1)
+        let available = self.options.width.saturating_sub(indent + 3 * num_cols + 1);
+        while widths.iter().sum::<usize>() > available {
+            let (widest, &w) = widths
+                .iter()
+                .enumerate()
+                .max_by_key(|&(_, w)| *w)
+                .expect("at least one column");
+            if w <= 10 {
+                break;
+            }
+            widths[widest] = w - 1;
+        }
+
+        let border = |fill: char| {
+            let mut line = " ".repeat(indent);
+            line.push('+');
+            for w in &widths {
+                line.push_str(&fill.to_string().repeat(w + 2));
+                line.push('+');
+            }
+            line.push('\n');
+            line
+        };
+
+        self.output.push_str(&border('-'));
+        for (r, row) in rows.iter().enumerate() {
+            let cells: Vec<Vec<String>> = (0..num_cols)
+                .map(|i| {
+                    let cell = row.get(i).map(String::as_str).unwrap_or("");
+                    cell.lines()
+                        .flat_map(|line| wrap_words(line, widths[i]))
+                        .collect()
+                })
+                .collect();
+            let height = cells.iter().map(|c| c.len()).max().unwrap_or(0).max(1);
+            for line_no in 0..height {
+                self.output.push_str(&" ".repeat(indent));
+                self.output.push('|');
+                for (i, cell) in cells.iter().enumerate() {
+                    let text = cell.get(line_no).map(String::as_str).unwrap_or("");
+                    self.output.push(' ');
+                    self.output.push_str(text);
+                    self.output
+                        .push_str(&" ".repeat(widths[i].saturating_sub(visible_width(text))));
+                    self.output.push_str(" |");
+                }
+                self.output.push('\n');
+            }
+            self.output
+                .push_str(&border(if r == 0 { '=' } else { '-' }));
+        }
+    }
+
+    /// Render table cell content; hard breaks become separate lines
+    fn render_cell(&self, children: &[Node]) -> String {
+        let parts: Vec<String> = children
+            .iter()
+            .map(|child| match child {
+                Node::Paragraph(p) => self.render_inline(&p.children),
+                _ => self.render_inline(std::slice::from_ref(child)),
+            })
+            .collect();
+        parts
+            .concat()
+            .lines()
+            .map(normalize_spaces)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Render inline nodes to a single string (`\n` marks hard line breaks)
+    fn render_inline(&self, nodes: &[Node]) -> String {
+        let mut out = String::new();
+        for node in nodes {
+            match node {
+                Node::Text(t) => out.push_str(&t.value.replace('\n', " ")),
+                Node::Emphasis(e) => {
+                    let inner = self.render_inline(&e.children);
+                    if self.options.ansi {
+                        out.push_str(&self.style(&inner, "3", "23"));
+                    } else {
+                        out.push_str(&format!("_{}_", inner));
+                    }
+                }
+                Node::Strong(s) => {
+                    let inner = self.render_inline(&s.children);
+                    if self.options.ansi {
+                        out.push_str(&self.style(&inner, "1", "22"));
+                    } else {
+                        out.push_str(&format!("*{}*", inner));
+                    }
+                }
+                Node::InlineCode(c) => {
+                    if self.options.ansi {
+                        out.push_str(&self.style(&c.value, "36", "39"));
+                    } else {
+                        out.push_str(&format!("\u{2018}{}\u{2019}", c.value));
+                    }
+                }
+                Node::Link(l) => {
+                    let text = self.render_inline(&l.children);
+                    out.push_str(&self.render_link(&text, &l.url));
+                }
+                Node::Image(img) => out.push_str(&format!("[image: {}]", img.alt)),
+                Node::InlineMath(m) => out.push_str(&m.value),
+                Node::Break => out.push('\n'),
+                Node::Html(h) => out.push_str(&html_to_text(&h.value)),
+                Node::RawInline(r) if r.format == "html" => out.push_str(&html_to_text(&r.value)),
+                Node::Span(s) => out.push_str(&self.render_inline(&s.children)),
+                Node::Paragraph(p) => out.push_str(&self.render_inline(&p.children)),
+                _ => {}
+            }
+        }
+        out
+    }
+
+    fn render_link(&self, text: &str, url: &str) -> String {
+        // Relative links point to other topics, which have no meaning on a terminal
+        let is_absolute = url.contains("://") || url.starts_with("mailto:");
+        if !is_absolute {
+            return text.to_string();
+        }
+
+        if self.options.hyperlinks {
+            let styled = self.style(text, "4", "24");
+            return format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, styled);
+        }
+
+        let bare = url.strip_prefix("mailto:").unwrap_or(url);
+        if text == url || text == bare {
+            self.style(text, "4", "24")
+        } else {
+            format!("{} <{}>", text, self.style(url, "4", "24"))
+        }
+    }
+
+    /// Wrap `text` at the configured width.
+    ///
+    /// The first line starts with `first_prefix`; continuation lines are indented
+    /// by `rest_indent` spaces.
+    fn write_wrapped(&mut self, text: &str, first_prefix: &str, rest_indent: usize) {
+        let rest_prefix = " ".repeat(rest_indent);
+        let mut prefix = first_prefix.to_string();
+        for hard_line in text.split('\n') {
+            let avail = self.options.width.saturating_sub(visible_width(&prefix));
+            let lines = wrap_words(hard_line, avail);
+            if lines.is_empty() {
+                continue;
+            }
+            for line in lines {
+                self.output.push_str(&prefix);
+                self.output.push_str(&line);
+                self.output.push('\n');
+                prefix.clone_from(&rest_prefix);
+            }
+        }
+        if prefix != rest_prefix {
+            // Nothing was written; still emit the marker (e.g. an empty list item)
+            self.output.push_str(first_prefix.trim_end());
+            self.output.push('\n');
+        }
+    }
+
+    /// Wrap `text` in an ANSI SGR sequence when styling is enabled
+    fn style(&self, text: &str, on: &str, off: &str) -> String {
+        if self.options.ansi {
+            format!("\x1b[{}m{}\x1b[{}m", on, text, off)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Greedy word wrap to `width` visible columns; long words are kept whole
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+    for word in text.split_whitespace() {
+        let w = visible_width(word);
+        if current.is_empty() {
+            current.push_str(word);
+            current_width = w;
+        } else if current_width + 1 + w <= width {
+            current.push(' ');
+            current.push_str(word);
+            current_width += 1 + w;
+        } else {
+            lines.push(std::mem::take(&mut current));
+            current.push_str(word);
+            current_width = w;
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn normalize_spaces(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Number of visible columns in `s`, ignoring ANSI CSI and OSC escape sequences
+fn visible_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            width += 1;
+            continue;
+        }
+        match chars.next() {
+            // CSI: ESC [ ... final byte in @..~
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: ESC ] ... terminated by BEL or ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    width
+}
+
+/// Reduce raw HTML to text: `<br>` becomes a line break, other tags are dropped
+pub(crate) fn html_to_text(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        match rest[start..].find('>') {
+            Some(end) => {
+                let tag = &rest[start + 1..start + end];
+                if tag.trim_end_matches('/').trim().eq_ignore_ascii_case("br") {
+                    out.push('\n');
+                }
+                rest = &rest[start + end + 1..];
+            }
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        }
+    }
+    out.push_str(rest);
+    out.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mdast::*;
+
+    fn to_text(children: Vec<Node>, width: usize) -> String {
+        let options = TextOptions {
+            width,
+            ..Default::default()
+        };
+        mdast_to_text(&Root::new(children), &options)
+    }
+
+    #[test]
+    fn test_sections_indented() {
+        let text = to_text(
+            vec![
+                Node::heading(1, vec![Node::text("Title")]),
+                Node::heading(2, vec![Node::text("Description")]),
+                Node::paragraph(vec![Node::text("Body text.")]),
+            ],
+            80,
+        );
+        assert_eq!(text, "Title\n=====\n\nDescription:\n\n     Body text.\n");
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let text = to_text(
+            vec![Node::paragraph(vec![Node::text(
+                "one two three four five six seven",
+            )])],
+            14,
+        );
+        assert_eq!(text, "one two three\nfour five six\nseven\n");
+    }
+
+    #[test]
+    fn test_list_hanging_indent() {
+        let text = to_text(
+            vec![Node::list(
+                false,
+                vec![
+                    Node::list_item(vec![Node::paragraph(vec![Node::text(
+                        "alpha beta gamma delta",
+                    )])]),
+                    Node::list_item(vec![Node::paragraph(vec![Node::text("second")])]),
+                ],
+            )],
+            14,
+        );
+        assert_eq!(text, "- alpha beta\n  gamma delta\n- second\n");
+    }
+
+    #[test]
+    fn test_ordered_list() {
+        let text = to_text(
+            vec![Node::list(
+                true,
+                vec![
+                    Node::list_item(vec![Node::paragraph(vec![Node::text("one")])]),
+                    Node::list_item(vec![Node::paragraph(vec![Node::text("two")])]),
+                ],
+            )],
+            80,
+        );
+        assert_eq!(text, "1. one\n2. two\n");
+    }
+
+    #[test]
+    fn test_definition_list() {
+        let text = to_text(
+            vec![Node::definition_list(vec![
+                Node::definition_term(vec![Node::inline_code("x")]),
+                Node::definition_description(vec![Node::paragraph(vec![Node::text("An object.")])]),
+                Node::definition_term(vec![Node::inline_code("y")]),
+                Node::definition_description(vec![Node::paragraph(vec![Node::text("Another.")])]),
+            ])],
+            80,
+        );
+        assert_eq!(
+            text,
+            "\u{2018}x\u{2019}\n    An object.\n\n\u{2018}y\u{2019}\n    Another.\n"
+        );
+    }
+
+    #[test]
+    fn test_ascii_table() {
+        let text = to_text(
+            vec![Node::table(
+                vec![None, None],
+                vec![
+                    Node::table_row(vec![
+                        Node::table_cell(vec![Node::text("Argument")]),
+                        Node::table_cell(vec![Node::text("Description")]),
+                    ]),
+                    Node::table_row(vec![
+                        Node::table_cell(vec![Node::inline_code("x")]),
+                        Node::table_cell(vec![Node::text("A value.")]),
+                    ]),
+                ],
+            )],
+            80,
+        );
+        assert_eq!(
+            text,
+            "+----------+-------------+\n\
+             | Argument | Description |\n\
+             +==========+=============+\n\
+             | \u{2018}x\u{2019}      | A value.    |\n\
+             +----------+-------------+\n"
+        );
+    }
+
+    #[test]
+    fn test_table_cells_wrap_to_width() {
+        let text = to_text(
+            vec![Node::table(
+                vec![None, None],
+                vec![
+                    Node::table_row(vec![
+                        Node::table_cell(vec![Node::text("Name")]),
+                        Node::table_cell(vec![Node::text("Description")]),
+                    ]),
+                    Node::table_row(vec![
+                        Node::table_cell(vec![Node::text("a")]),
+                        Node::table_cell(vec![Node::text(
+                            "a fairly long description that needs wrapping",
+                        )]),
+                    ]),
+                ],
+            )],
+            30,
+        );
+        assert!(text.lines().all(|l| l.chars().count() <= 30), "{}", text);
+        assert!(text.contains("| a    | a fairly long"));
+    }
+
+    #[test]
+    fn test_code_block_indented_not_wrapped() {
+        let text = to_text(
+            vec![
+                Node::heading(2, vec![Node::text("Usage")]),
+                Node::code(Some("r".to_string()), "foo(x, y = some_long_default_value)"),
+            ],
+            20,
+        );
+        assert_eq!(text, "Usage:\n\n     foo(x, y = some_long_default_value)\n");
+    }
+
+    #[test]
+    fn test_links_plain() {
+        let text = to_text(
+            vec![Node::paragraph(vec![
+                Node::link("https://example.com", vec![Node::text("site")]),
+                Node::text(" and "),
+                Node::link("other.qmd", vec![Node::text("other")]),
+            ])],
+            80,
+        );
+        assert_eq!(text, "site <https://example.com> and other\n");
+    }
+
+    #[test]
+    fn test_ansi_styles() {
+        let options = TextOptions {
+            ansi: true,
+            hyperlinks: true,
+            ..Default::default()
+        };
+        let root = Root::new(vec![
+            Node::heading(2, vec![Node::text("Details")]),
+            Node::paragraph(vec![
+                Node::inline_code("x"),
+                Node::text(" "),
+                Node::link("https://example.com", vec![Node::text("site")]),
+            ]),
+        ]);
+        let text = mdast_to_text(&root, &options);
+        assert!(text.starts_with("\x1b[1mDetails:\x1b[22m\n"));
+        assert!(text.contains("\x1b[36mx\x1b[39m"));
+        assert!(
+            text.contains("\x1b]8;;https://example.com\x1b\\\x1b[4msite\x1b[24m\x1b]8;;\x1b\\")
+        );
+    }
+
+    #[test]
+    fn test_visible_width_ignores_escapes() {
+        assert_eq!(visible_width("\x1b[1mabc\x1b[22m"), 3);
+        assert_eq!(
+            visible_width("\x1b]8;;https://x.org\x1b\\ab\x1b]8;;\x1b\\"),
+            2
+        );
+        assert_eq!(visible_width("\u{2018}x\u{2019}"), 3);
+    }
+
+    #[test]
+    fn test_html_break_in_table_cell() {
+        let text = to_text(
+            vec![Node::table(
+                vec![None],
+                vec![
+                    Node::table_row(vec![Node::table_cell(vec![Node::text("H")])]),
+                    Node::table_row(vec![Node::table_cell(vec![
+                        Node::text("a "),
+                        Node::html("<br>"),
+                        Node::text("b"),
+                    ])]),
+                ],
+            )],
+            80,
+        );
+        assert!(text.contains("| a |\n| b |"), "{}", text);
+    }
+}
@@ -1,9 +1,17 @@
 //! mdast to Quarto Markdown writer
 //!
-//! Converts an mdast tree into a Quarto Markdown string.
-
+//! Converts an mdast tree into Quarto Markdown, written as it is produced to a
+//! [`fmt::Write`] ([`write_qmd`]) or [`io::Write`] ([`write_qmd_io`]) sink, or
+//! collected in a string ([`mdast_to_qmd`]). The [`MarkdownFlavor::Mdx`] flavor
+//! instead targets MDX (Docusaurus), where braces and angle brackets in text
+//! would be read as JSX.
+
+use crate::escape::{TextContext, Typography, escape_text, is_inline_r_code, typeset};
+use crate::html::escape_attribute;
 use crate::mdast::{Align, Node, Root};
 use serde::Serialize;
+use std::fmt;
+use std::io;
 
 /// Options for the QMD writer
 #[derive(Debug, Clone, Default)]
@@ -12,6 +20,36 @@ pub struct WriterOptions {
     pub frontmatter: Option<Frontmatter>,
     /// Use {r} instead of r for R code blocks
     pub quarto_code_blocks: bool,
+    /// Markdown dialect to write
+    pub flavor: MarkdownFlavor,
+    /// Rendering of typographic quotes, dashes and ellipses in text
+    pub typography: Typography,
+}
+
+/// Markdown dialect written by [`mdast_to_qmd`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MarkdownFlavor {
+    /// Pandoc Markdown, as read by Quarto and R Markdown
+    #[default]
+    Pandoc,
+    /// R Markdown: leading `#| name: value` chunk options of executable R chunks
+    /// are moved into the chunk header (`{r label, error=TRUE}`)
+    RMarkdown,
+    /// MDX for Docusaurus: `{}<>` are escaped, Note and Warning sections become
+    /// `:::note`/`:::warning` admonitions, definition lists become bullet lists,
+    /// and frontmatter has `id`, `sidebar_label` and `slug`
+    Mdx,
+    /// CommonMark (with GitHub tables and math) for plain Markdown renderers:
+    /// divs and spans are unwrapped, raw HTML is passed through, and Pandoc
+    /// attributes and definition lists are not written
+    CommonMark,
+}
+
+impl MarkdownFlavor {
+    /// Whether this flavor is read by Pandoc (Quarto or R Markdown)
+    pub fn is_pandoc(self) -> bool {
+        matches!(self, MarkdownFlavor::Pandoc | MarkdownFlavor::RMarkdown)
+    }
 }
 
 /// YAML frontmatter content
@@ -21,6 +59,10 @@ pub struct Frontmatter {
     /// Page title for browser tab/SEO (pkgdown style: "<title> — <name>")
     pub pagetitle: Option<String>,
     pub format: Option<String>,
+    /// Topic name (from \name{}), used as the MDX `sidebar_label`
+    pub name: Option<String>,
+    /// Document id (the Rd file basename), used as the MDX `id` and `slug`
+    pub id: Option<String>,
     /// Rd metadata (lifecycle, aliases, keywords, concepts)
     pub metadata: Option<RdMetadata>,
 }
@@ -50,43 +92,159 @@ pub struct RdMetadata {
 }
 
 /// Convert mdast to Quarto Markdown
+///
+/// Convenience wrapper around [`write_qmd`] that collects the output in a string.
 pub fn mdast_to_qmd(root: &Root, options: &WriterOptions) -> String {
-    let mut writer = Writer::new(options);
+    let mut output = String::new();
+    write_qmd(root, options, &mut output).expect("writing to a String cannot fail");
+    output
+}
+
+/// Write mdast as Quarto Markdown to a [`fmt::Write`] sink
+///
+/// # Example
+///
+/// ```
+/// use rd2qmd_mdast::{Node, Root, WriterOptions, write_qmd};
+///
+/// let root = Root::new(vec![Node::paragraph(vec![Node::text("Hello")])]);
+/// let mut output = String::new();
+/// write_qmd(&root, &WriterOptions::default(), &mut output).unwrap();
+/// assert_eq!(output, "Hello\n");
+/// ```
+pub fn write_qmd<W: fmt::Write>(root: &Root, options: &WriterOptions, out: W) -> fmt::Result {
+    let mut writer = Writer::new(options, out);
     writer.write_root(root)
 }
 
+/// Write mdast as Quarto Markdown to an [`io::Write`] sink, such as a file or stdout
+///
+/// Output is written as it is produced, in many small writes; wrap unbuffered
+/// sinks in an [`io::BufWriter`].
+pub fn write_qmd_io<W: io::Write>(root: &Root, options: &WriterOptions, out: W) -> io::Result<()> {
+    let mut out = IoOutput {
+        inner: out,
+        error: None,
+    };
+    write_qmd(root, options, &mut out).map_err(|_| {
+        out.error
+            .unwrap_or_else(|| io::Error::other("formatter error"))
+    })
+}
+
+/// [`fmt::Write`] adapter for an [`io::Write`] that keeps the I/O error
+struct IoOutput<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoOutput<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+/// Output of the writer
+///
+/// Forwards text to the sink and remembers what the writer looks back at:
+/// whether anything was written, the last two characters and the current line.
+/// After the first error, later writes are skipped and the error is returned at
+/// the end.
+struct Output<W> {
+    inner: W,
+    result: fmt::Result,
+    written: bool,
+    /// Last two characters written, the last one second
+    tail: [Option<char>; 2],
+    /// Text written since the last newline
+    line: String,
+}
+
+impl<W: fmt::Write> Output<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            result: Ok(()),
+            written: false,
+            tail: [None, None],
+            line: String::new(),
+        }
+    }
+
+    fn push_str(&mut self, s: &str) {
+        let mut chars = s.chars().rev();
+        let Some(last) = chars.next() else {
+            return;
+        };
+        if self.result.is_ok() {
+            self.result = self.inner.write_str(s);
+        }
+        self.written = true;
+        self.tail = [chars.next().or(self.tail[1]), Some(last)];
+        match s.rfind('\n') {
+            Some(i) => {
+                self.line.clear();
+                self.line.push_str(&s[i + 1..]);
+            }
+            None => self.line.push_str(s),
+        }
+    }
+
+    fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    fn is_empty(&self) -> bool {
+        !self.written
+    }
+
+    /// Whether the output ends with `suffix`, of at most two characters
+    fn ends_with(&self, suffix: &str) -> bool {
+        debug_assert!(suffix.chars().count() <= 2);
+        let mut tail = self.tail.iter().rev();
+        suffix.chars().rev().all(|c| tail.next() == Some(&Some(c)))
+    }
+
+    /// Text written since the last newline
+    fn current_line(&self) -> &str {
+        &self.line
+    }
+}
+
 /// QMD writer state
-struct Writer<'a> {
+struct Writer<'a, W> {
     options: &'a WriterOptions,
-    output: String,
+    output: Output<W>,
     /// Whether we're at the start of a line
     at_line_start: bool,
+    /// Where text is currently written, for escaping
+    context: TextContext,
 }
 
-impl<'a> Writer<'a> {
-    fn new(options: &'a WriterOptions) -> Self {
+impl<'a, W: fmt::Write> Writer<'a, W> {
+    fn new(options: &'a WriterOptions, out: W) -> Self {
         Self {
             options,
-            output: String::new(),
+            output: Output::new(out),
             at_line_start: true,
+            context: TextContext::Block,
         }
     }
 
-    fn write_root(&mut self, root: &Root) -> String {
+    fn write_root(&mut self, root: &Root) -> fmt::Result {
         // Write frontmatter if provided
         if let Some(fm) = &self.options.frontmatter {
             self.write_frontmatter(fm);
         }
 
         // Write children
-        for (i, node) in root.children.iter().enumerate() {
-            if i > 0 {
-                self.ensure_blank_line();
-            }
-            self.write_node(node);
-        }
+        self.write_blocks(&root.children);
+        self.ensure_newline();
 
-        self.output.clone()
+        self.output.result
     }
 
     fn write_frontmatter(&mut self, fm: &Frontmatter) {
@@ -96,7 +254,24 @@ impl<'a> Writer<'a> {
                 .push_str(&format!(r#"title: "{}""#, escape_yaml_string(title)));
             self.output.push('\n');
         }
-        if let Some(pagetitle) = &fm.pagetitle {
+        if self.options.flavor == MarkdownFlavor::Mdx {
+            // Docusaurus keys
+            if let Some(id) = &fm.id {
+                self.output
+                    .push_str(&format!(r#"id: "{}""#, escape_yaml_string(id)));
+                self.output.push('\n');
+            }
+            if let Some(name) = &fm.name {
+                self.output
+                    .push_str(&format!(r#"sidebar_label: "{}""#, escape_yaml_string(name)));
+                self.output.push('\n');
+            }
+            if let Some(id) = &fm.id {
+                self.output
+                    .push_str(&format!(r#"slug: "{}""#, escape_yaml_string(id)));
+                self.output.push('\n');
+            }
+        } else if let Some(pagetitle) = &fm.pagetitle {
             self.output.push_str(&format!(
                 r#"pagetitle: "{}""#,
                 escape_yaml_string(pagetitle)
@@ -160,10 +335,18 @@ impl<'a> Writer<'a> {
             Node::Table(t) => self.write_table(t),
             Node::TableRow(_) => {}  // Handled by write_table
             Node::TableCell(_) => {} // Handled by write_table
+            Node::DefinitionList(dl) if !self.options.flavor.is_pandoc() => {
+                self.write_list(&definition_list_as_list(dl))
+            }
             Node::DefinitionList(dl) => self.write_definition_list(dl),
             Node::DefinitionTerm(_) => {} // Handled by write_definition_list
             Node::DefinitionDescription(_) => {} // Handled by write_definition_list
-            Node::Text(t) => self.output.push_str(&t.value),
+            Node::Text(t) => {
+                let line_start = self.context == TextContext::Block && self.at_block_start();
+                let text = escape_text(&t.value, self.context, self.options.flavor, line_start);
+                self.output
+                    .push_str(&typeset(&text, self.options.typography));
+            }
             Node::Emphasis(e) => self.write_emphasis(e),
             Node::Strong(s) => self.write_strong(s),
             Node::InlineCode(c) => self.write_inline_code(c),
@@ -172,18 +355,161 @@ impl<'a> Writer<'a> {
             Node::Image(img) => self.write_image(img),
             Node::Math(m) => self.write_math(m),
             Node::InlineMath(m) => self.write_inline_math(m),
-            Node::Html(h) => self.output.push_str(&h.value),
+            // JSX requires void elements to be self-closed
+            Node::Html(h) if self.options.flavor == MarkdownFlavor::Mdx => {
+                self.output.push_str(&h.value.replace("<br>", "<br />"));
+                self.at_line_start = h.value.ends_with('\n');
+            }
+            Node::Html(h) => {
+                self.output.push_str(&h.value);
+                self.at_line_start = h.value.ends_with('\n');
+            }
+            Node::Div(d) => self.write_div(d),
+            Node::Span(s) => self.write_span(s),
+            Node::RawBlock(r) => self.write_raw_block(r),
+            Node::RawInline(r) => self.write_raw_inline(r),
+        }
+    }
+
+    /// Write block nodes separated by blank lines
+    fn write_blocks(&mut self, nodes: &[Node]) {
+        // MDX and CommonMark have no way to pass through raw content for other formats
+        let nodes = nodes.iter().filter(|node| {
+            self.options.flavor.is_pandoc()
+                || !matches!(node, Node::RawBlock(r) if r.format != "html")
+        });
+        for (i, node) in nodes.enumerate() {
+            if i > 0 {
+                self.ensure_blank_line();
+            }
+            self.write_node(node);
         }
     }
 
+    fn write_div(&mut self, d: &crate::mdast::Div) {
+        self.ensure_newline();
+        if self.options.flavor == MarkdownFlavor::Mdx {
+            self.write_admonition(d);
+            return;
+        }
+        if self.options.flavor == MarkdownFlavor::CommonMark {
+            self.write_blocks(&d.children);
+            return;
+        }
+
+        // Outer fences are longer, so nesting stays readable
+        let fence = ":".repeat(3 + div_depth(&d.children));
+        self.output.push_str(&fence);
+        if !d.attributes.is_empty() {
+            self.output.push(' ');
+            self.output.push_str(&format_attributes(&d.attributes));
+        }
+        self.output.push('\n');
+        self.at_line_start = true;
+        self.write_blocks(&d.children);
+        self.ensure_newline();
+        self.output.push_str(&fence);
+        self.output.push('\n');
+        self.at_line_start = true;
+    }
+
+    /// Write a callout div as a Docusaurus admonition; other divs are unwrapped
+    fn write_admonition(&mut self, d: &crate::mdast::Div) {
+        let Some(kind) = d.attributes.classes.iter().find_map(|c| admonition_kind(c)) else {
+            self.write_blocks(&d.children);
+            return;
+        };
+
+        // A leading heading is the callout title
+        let mut children = d.children.as_slice();
+        let mut title = String::new();
+        if let Some(Node::Heading(h)) = children.first() {
+            title = plain_text(&h.children);
+            children = &children[1..];
+        }
+        self.output.push_str(":::");
+        self.output.push_str(kind);
+        if !title.is_empty() && !title.eq_ignore_ascii_case(kind) {
+            self.output.push('[');
+            self.output.push_str(&escape_mdx(&title));
+            self.output.push(']');
+        }
+        self.output.push_str("\n\n");
+        self.at_line_start = true;
+        self.write_blocks(children);
+        self.ensure_blank_line();
+        self.output.push_str(":::\n");
+        self.at_line_start = true;
+    }
+
+    fn write_span(&mut self, s: &crate::mdast::Span) {
+        if !self.options.flavor.is_pandoc() {
+            // Keep the id (such as an argument anchor) as an HTML anchor
+            if let Some(id) = &s.attributes.id {
+                self.output
+                    .push_str(&format!(r#"<a id="{}"></a>"#, escape_attribute(id)));
+            }
+            for child in &s.children {
+                self.write_node(child);
+            }
+            return;
+        }
+        self.output.push('[');
+        self.write_inlines(&s.children, TextContext::LinkText);
+        self.output.push(']');
+        self.output.push_str(&format_attributes(&s.attributes));
+    }
+
+    fn write_raw_block(&mut self, r: &crate::mdast::Raw) {
+        if !self.options.flavor.is_pandoc() {
+            // MDX (as JSX) and CommonMark pass HTML through; other formats are dropped
+            if r.format == "html" {
+                self.ensure_newline();
+                self.output.push_str(r.value.trim_end_matches('\n'));
+                self.output.push('\n');
+                self.at_line_start = true;
+            }
+            return;
+        }
+        self.ensure_newline();
+        let fence = "`".repeat(calculate_fence_length(&r.value));
+        self.output
+            .push_str(&format!("{}{{={}}}\n", fence, r.format));
+        self.output.push_str(r.value.trim_end_matches('\n'));
+        self.output.push('\n');
+        self.output.push_str(&fence);
+        self.output.push('\n');
+        self.at_line_start = true;
+    }
+
+    fn write_raw_inline(&mut self, r: &crate::mdast::Raw) {
+        if !self.options.flavor.is_pandoc() {
+            if r.format == "html" {
+                self.output.push_str(&r.value);
+            }
+            return;
+        }
+        self.write_inline_code(&crate::mdast::InlineCode {
+            value: r.value.clone(),
+        });
+        self.output.push_str(&format!("{{={}}}", r.format));
+    }
+
     fn write_heading(&mut self, h: &crate::mdast::Heading) {
         self.ensure_newline();
         for _ in 0..h.depth {
             self.output.push('#');
         }
         self.output.push(' ');
-        for child in &h.children {
-            self.write_node(child);
+        self.write_inlines(&h.children, TextContext::Heading);
+        if self.options.flavor == MarkdownFlavor::Mdx {
+            // Docusaurus reads explicit heading ids only
+            if let Some(id) = &h.attributes.id {
+                self.output.push_str(&format!(" {{#{}}}", id));
+            }
+        } else if self.options.flavor.is_pandoc() && !h.attributes.is_empty() {
+            self.output.push(' ');
+            self.output.push_str(&format_attributes(&h.attributes));
         }
         self.output.push('\n');
         self.at_line_start = true;
@@ -281,19 +607,26 @@ impl<'a> Writer<'a> {
         let fence_len = calculate_fence_length(&c.value);
         let fence = "`".repeat(fence_len);
 
+        let mut body = c.value.as_str();
         self.output.push_str(&fence);
         if let Some(lang) = &c.lang {
             // Only use {r} for executable code blocks (Examples section)
             let is_executable = c.meta.as_deref() == Some("executable");
             if self.options.quarto_code_blocks && lang == "r" && is_executable {
-                self.output.push_str("{r}");
+                if self.options.flavor == MarkdownFlavor::RMarkdown {
+                    let (header, rest) = rmd_chunk_header(body);
+                    self.output.push_str(&header);
+                    body = rest;
+                } else {
+                    self.output.push_str("{r}");
+                }
             } else {
                 self.output.push_str(lang);
             }
         }
         self.output.push('\n');
-        self.output.push_str(&c.value);
-        if !c.value.ends_with('\n') {
+        self.output.push_str(body);
+        if !body.ends_with('\n') {
             self.output.push('\n');
         }
         self.output.push_str(&fence);
@@ -357,9 +690,7 @@ impl<'a> Writer<'a> {
             }
             if let Node::TableCell(c) = cell {
                 self.output.push(' ');
-                for child in &c.children {
-                    self.write_node(child);
-                }
+                self.write_inlines(&c.children, TextContext::TableCell);
                 self.output.push_str(" |");
             }
         }
@@ -510,13 +841,14 @@ impl<'a> Writer<'a> {
     fn write_inline_code(&mut self, c: &crate::mdast::InlineCode) {
         // Add space before if the previous character is a backtick
         // This prevents `foo``bar` which CommonMark parses as a single code span
-        if self.output.ends_with('`') {
+        if self.output.ends_with("`") {
             self.output.push(' ');
         }
 
-        // Handle backticks in content
+        // Handle backticks in content. Code starting with `r ` is also padded, so
+        // knitr and Quarto don't run it as inline R code.
         let value = &c.value;
-        if value.contains('`') {
+        if value.contains('`') || is_inline_r_code(value) {
             self.output.push_str("`` ");
             self.output.push_str(value);
             self.output.push_str(" ``");
@@ -534,14 +866,12 @@ impl<'a> Writer<'a> {
 
     fn write_link(&mut self, l: &crate::mdast::Link) {
         self.output.push('[');
-        for child in &l.children {
-            self.write_node(child);
-        }
+        self.write_inlines(&l.children, TextContext::LinkText);
         self.output.push_str("](");
         self.output.push_str(&l.url);
         if let Some(title) = &l.title {
             self.output.push_str(" \"");
-            self.output.push_str(title);
+            self.output.push_str(&title.replace('"', "\\\""));
             self.output.push('"');
         }
         self.output.push(')');
@@ -549,15 +879,24 @@ impl<'a> Writer<'a> {
 
     fn write_image(&mut self, img: &crate::mdast::Image) {
         self.output.push_str("![");
-        self.output.push_str(&img.alt);
+        self.output.push_str(&escape_text(
+            &img.alt,
+            TextContext::LinkText,
+            self.options.flavor,
+            false,
+        ));
         self.output.push_str("](");
         self.output.push_str(&img.url);
         if let Some(title) = &img.title {
             self.output.push_str(" \"");
-            self.output.push_str(title);
+            self.output.push_str(&title.replace('"', "\\\""));
             self.output.push('"');
         }
         self.output.push(')');
+        // Image attributes are Pandoc syntax that MDX and CommonMark don't read
+        if !img.attributes.is_empty() && self.options.flavor.is_pandoc() {
+            self.output.push_str(&format_attributes(&img.attributes));
+        }
     }
 
     fn write_math(&mut self, m: &crate::mdast::Math) {
@@ -579,6 +918,38 @@ impl<'a> Writer<'a> {
 
     // Helper methods
 
+    /// Write inline nodes in a text context, restoring the previous one afterwards
+    fn write_inlines(&mut self, nodes: &[Node], context: TextContext) {
+        let outer = std::mem::replace(&mut self.context, context);
+        for node in nodes {
+            self.write_node(node);
+        }
+        self.context = outer;
+    }
+
+    /// Whether inline content written now starts a block, so that a leading `#`,
+    /// `-` or `1.` would be read as block syntax
+    ///
+    /// This is the case at the start of a line, possibly after list item,
+    /// blockquote or definition markers.
+    fn at_block_start(&self) -> bool {
+        let mut rest = self.output.current_line().trim_start();
+        loop {
+            if rest.is_empty() {
+                return true;
+            }
+            let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            let marker = ["- ", "> ", ": "]
+                .iter()
+                .find_map(|marker| rest.strip_prefix(marker))
+                .or_else(|| rest[digits..].strip_prefix(". ").filter(|_| digits > 0));
+            match marker {
+                Some(after) => rest = after.trim_start(),
+                None => return false,
+            }
+        }
+    }
+
     fn ensure_newline(&mut self) {
         if !self.at_line_start && !self.output.is_empty() {
             self.output.push('\n');
@@ -598,6 +969,168 @@ fn escape_yaml_string(s: &str) -> String {
     s.replace('\\', "\\\\").replace('"', "\\\"")
 }
 
+/// Backslash-escape characters that MDX reads as JSX or expressions
+fn escape_mdx(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '{' | '}' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Format Pandoc attributes: `{#id .class key="value"}`
+fn format_attributes(attributes: &crate::mdast::Attributes) -> String {
+    let mut parts = Vec::new();
+    if let Some(id) = &attributes.id {
+        parts.push(format!("#{}", id));
+    }
+    for class in &attributes.classes {
+        parts.push(format!(".{}", class));
+    }
+    for (key, value) in &attributes.attributes {
+        parts.push(format!(
+            r#"{}="{}""#,
+            key,
+            value.replace('\\', "\\\\").replace('"', "\\\"")
+        ));
+    }
+    format!("{{{}}}", parts.join(" "))
+}
+
+/// Nesting depth of divs within block nodes
+fn div_depth(nodes: &[Node]) -> usize {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Div(d) => 1 + div_depth(&d.children),
+            Node::Blockquote(b) => div_depth(&b.children),
+            Node::List(l) => div_depth(&l.children),
+            Node::ListItem(li) => div_depth(&li.children),
+            Node::DefinitionList(dl) => div_depth(&dl.children),
+            Node::DefinitionDescription(dd) => div_depth(&dd.children),
+            _ => 0,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Docusaurus admonition type for a Quarto callout class
+fn admonition_kind(class: &str) -> Option<&'static str> {
+    match class {
+        "callout-note" => Some("note"),
+        "callout-tip" => Some("tip"),
+        "callout-important" => Some("info"),
+        "callout-warning" => Some("warning"),
+        "callout-caution" => Some("danger"),
+        _ => None,
+    }
+}
+
+/// Concatenate the text content of inline nodes
+fn plain_text(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(t) => out.push_str(&t.value),
+            Node::InlineCode(c) => out.push_str(&c.value),
+            Node::Emphasis(e) => out.push_str(&plain_text(&e.children)),
+            Node::Strong(s) => out.push_str(&plain_text(&s.children)),
+            Node::Link(l) => out.push_str(&plain_text(&l.children)),
+            Node::Span(s) => out.push_str(&plain_text(&s.children)),
+            _ => {}
+        }
+    }
+    out.trim().to_string()
+}
+
+/// Rewrite a definition list as a bullet list of `term: description` items,
+/// since MDX has no definition list syntax
+fn definition_list_as_list(dl: &crate::mdast::DefinitionList) -> crate::mdast::List {
+    let mut items: Vec<Vec<Node>> = Vec::new();
+    for child in &dl.children {
+        match child {
+            Node::DefinitionTerm(dt) => items.push(vec![Node::paragraph(dt.children.clone())]),
+            Node::DefinitionDescription(dd) => {
+                let Some(item) = items.last_mut() else {
+                    continue;
+                };
+                let mut blocks = dd.children.iter();
+                // The first description paragraph continues the term's line
+                if item.len() == 1
+                    && let Some(Node::Paragraph(term)) = item.first_mut()
+                {
+                    match dd.children.first() {
+                        Some(Node::Paragraph(p)) => {
+                            term.children.push(Node::text(": "));
+                            term.children.extend(p.children.iter().cloned());
+                            blocks.next();
+                        }
+                        Some(_) => term.children.push(Node::text(":")),
+                        None => {}
+                    }
+                }
+                item.extend(blocks.cloned());
+            }
+            _ => {}
+        }
+    }
+
+    crate::mdast::List {
+        ordered: false,
+        start: None,
+        spread: false,
+        children: items.into_iter().map(Node::list_item).collect(),
+    }
+}
+
+/// R Markdown chunk header for code with leading `#| name: value` options
+///
+/// Returns the header (`{r ex-foo-1, error=TRUE}`) and the code after the options.
+/// Option names use knitr's dotted form (`fig-width` becomes `fig.width`) and
+/// YAML booleans become R logicals; bare YAML strings are quoted.
+fn rmd_chunk_header(code: &str) -> (String, &str) {
+    let mut label = None;
+    let mut options = Vec::new();
+    let mut rest = code;
+    while let Some(line) = rest.strip_prefix("#| ") {
+        let (line, next) = line.split_once('\n').unwrap_or((line, ""));
+        let Some((name, value)) = line.split_once(':') else {
+            break;
+        };
+        let value = value.trim();
+        if name == "label" {
+            label = Some(value);
+        } else {
+            let value = match value {
+                "true" | "yes" => "TRUE".to_string(),
+                "false" | "no" => "FALSE".to_string(),
+                value if value.parse::<f64>().is_ok() || value.starts_with(['"', '\'']) => {
+                    value.to_string()
+                }
+                value => format!("{:?}", value),
+            };
+            options.push(format!("{}={}", name.trim().replace('-', "."), value));
+        }
+        rest = next;
+    }
+
+    let mut header = String::from("{r");
+    for (i, part) in label
+        .into_iter()
+        .map(str::to_string)
+        .chain(options)
+        .enumerate()
+    {
+        header.push_str(if i == 0 { " " } else { ", " });
+        header.push_str(&part);
+    }
+    header.push('}');
+    (header, rest)
+}
+
 /// Calculate the minimum fence length needed for a code block.
 ///
 /// The fence must be longer than any sequence of consecutive backticks in the content.
@@ -744,6 +1277,31 @@ mod tests {
         assert!(!qmd2.contains("```{r}"));
     }
 
+    #[test]
+    fn test_rmarkdown_chunk_header() {
+        let root = Root::new(vec![Node::code_with_meta(
+            Some("r".to_string()),
+            Some("executable".to_string()),
+            "#| label: ex-foo-1\n#| error: true\n#| fig-width: 7\n#| fig-align: center\n#| include: false\nx <- 1",
+        )]);
+        let opts = WriterOptions {
+            quarto_code_blocks: true,
+            flavor: MarkdownFlavor::RMarkdown,
+            ..Default::default()
+        };
+        assert_eq!(
+            mdast_to_qmd(&root, &opts),
+            "```{r ex-foo-1, error=TRUE, fig.width=7, fig.align=\"center\", include=FALSE}\nx <- 1\n```\n"
+        );
+
+        let plain = Root::new(vec![Node::code_with_meta(
+            Some("r".to_string()),
+            Some("executable".to_string()),
+            "x <- 1",
+        )]);
+        assert_eq!(mdast_to_qmd(&plain, &opts), "```{r}\nx <- 1\n```\n");
+    }
+
     #[test]
     fn test_inline_code() {
         let root = Root::new(vec![Node::paragraph(vec![
@@ -755,6 +1313,68 @@ mod tests {
         assert!(qmd.contains("`foo()`"));
     }
 
+    #[test]
+    fn test_inline_r_code_not_executed() {
+        let root = Root::new(vec![Node::paragraph(vec![
+            Node::inline_code("r x"),
+            Node::text(" and "),
+            Node::inline_code("{r} 1 + 1"),
+            Node::text(" and "),
+            Node::inline_code("rnorm(1)"),
+        ])]);
+        let qmd = mdast_to_qmd(&root, &WriterOptions::default());
+        assert_eq!(qmd, "`` r x `` and `` {r} 1 + 1 `` and `rnorm(1)`\n");
+    }
+
+    #[test]
+    fn test_text_escaping_by_context() {
+        let root = Root::new(vec![
+            Node::heading(2, vec![Node::text("Issue #1 {x}")]),
+            Node::paragraph(vec![Node::text("# not a heading, *not* emphasis, $5")]),
+            Node::list(
+                false,
+                vec![Node::list_item(vec![Node::paragraph(vec![Node::text(
+                    "1. not a nested list",
+                )])])],
+            ),
+            Node::paragraph(vec![Node::link(
+                "https://example.org",
+                vec![Node::text("x[1]")],
+            )]),
+            Node::Table(crate::mdast::Table {
+                align: vec![None, None],
+                children: vec![
+                    Node::TableRow(crate::mdast::TableRow {
+                        children: vec![
+                            Node::TableCell(crate::mdast::TableCell {
+                                children: vec![Node::text("a")],
+                            }),
+                            Node::TableCell(crate::mdast::TableCell {
+                                children: vec![Node::text("b")],
+                            }),
+                        ],
+                    }),
+                    Node::TableRow(crate::mdast::TableRow {
+                        children: vec![
+                            Node::TableCell(crate::mdast::TableCell {
+                                children: vec![Node::text("x | y")],
+                            }),
+                            Node::TableCell(crate::mdast::TableCell {
+                                children: vec![Node::text("z")],
+                            }),
+                        ],
+                    }),
+                ],
+            }),
+        ]);
+        let qmd = mdast_to_qmd(&root, &WriterOptions::default());
+        assert!(qmd.contains("## Issue \\#1 \\{x}\n"));
+        assert!(qmd.contains("\\# not a heading, \\*not\\* emphasis, \\$5\n"));
+        assert!(qmd.contains("- 1\\. not a nested list\n"));
+        assert!(qmd.contains("[x\\[1\\]](https://example.org)"));
+        assert!(qmd.contains("| x \\| y | z |"));
+    }
+
     #[test]
     fn test_inline_code_with_backticks() {
         let root = Root::new(vec![Node::paragraph(vec![Node::inline_code(
@@ -842,6 +1462,26 @@ mod tests {
         assert!(qmd.contains("![An image](image.png \"Image Title\")"));
     }
 
+    #[test]
+    fn test_image_with_attributes() {
+        let mut image = Node::image("logo.png", "Logo");
+        if let Node::Image(img) = &mut image {
+            img.attributes
+                .attributes
+                .push(("width".to_string(), "100".to_string()));
+        }
+        let root = Root::new(vec![Node::paragraph(vec![image])]);
+        let qmd = mdast_to_qmd(&root, &WriterOptions::default());
+        assert!(qmd.contains(r#"![Logo](logo.png){width="100"}"#));
+
+        let options = WriterOptions {
+            flavor: MarkdownFlavor::Mdx,
+            ..Default::default()
+        };
+        let mdx = mdast_to_qmd(&root, &options);
+        assert!(mdx.contains("![Logo](logo.png)\n"));
+    }
+
     #[test]
     fn test_unordered_list() {
         let root = Root::new(vec![Node::list(
@@ -1006,6 +1646,8 @@ mod tests {
             frontmatter: Some(Frontmatter {
                 title: Some("My Document".to_string()),
                 pagetitle: None,
+                name: None,
+                id: None,
                 format: Some("html".to_string()),
                 metadata: None,
             }),
@@ -1024,6 +1666,8 @@ mod tests {
             frontmatter: Some(Frontmatter {
                 title: Some("Order rows using column values".to_string()),
                 pagetitle: Some("Order rows using column values — arrange".to_string()),
+                name: None,
+                id: None,
                 format: None,
                 metadata: None,
             }),
@@ -1042,6 +1686,8 @@ mod tests {
             frontmatter: Some(Frontmatter {
                 title: Some(r#"Title with "quotes" and \backslash"#.to_string()),
                 pagetitle: None,
+                name: None,
+                id: None,
                 format: None,
                 metadata: None,
             }),
@@ -1058,6 +1704,8 @@ mod tests {
             frontmatter: Some(Frontmatter {
                 title: Some("My Function".to_string()),
                 pagetitle: None,
+                name: None,
+                id: None,
                 format: None,
                 metadata: Some(RdMetadata {
                     lifecycle: Some("deprecated".to_string()),
@@ -1090,6 +1738,8 @@ mod tests {
             frontmatter: Some(Frontmatter {
                 title: Some("Function".to_string()),
                 pagetitle: None,
+                name: None,
+                id: None,
                 format: None,
                 metadata: Some(RdMetadata {
                     lifecycle: Some("stable".to_string()),
@@ -1117,6 +1767,8 @@ mod tests {
             frontmatter: Some(Frontmatter {
                 title: Some("coord_map".to_string()),
                 pagetitle: None,
+                name: None,
+                id: None,
                 format: None,
                 metadata: Some(RdMetadata {
                     lifecycle: None,
@@ -1136,4 +1788,235 @@ mod tests {
         assert!(qmd.contains(r#"  - "R/coord-map.R""#));
         assert!(qmd.contains(r#"  - "R/coord-quickmap.R""#));
     }
+
+    fn mdx_options() -> WriterOptions {
+        WriterOptions {
+            flavor: MarkdownFlavor::Mdx,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_mdx_escaping() {
+        let root = Root::new(vec![Node::paragraph(vec![
+            Node::text("Returns {x} or <NA> "),
+            Node::inline_code("{x}"),
+            Node::html(" <br>"),
+        ])]);
+        let mdx = mdast_to_qmd(&root, &mdx_options());
+        assert_eq!(mdx.trim(), r"Returns \{x\} or \<NA\> `{x}` <br />");
+    }
+
+    #[test]
+    fn test_mdx_admonitions() {
+        let root = Root::new(vec![
+            Node::div(
+                Attributes::class("callout-note"),
+                vec![
+                    Node::heading(2, vec![Node::text("Note")]),
+                    Node::paragraph(vec![Node::text("Be careful.")]),
+                ],
+            ),
+            Node::div(
+                Attributes::class("callout-warning"),
+                vec![
+                    Node::heading(2, vec![Node::text("Thread safety")]),
+                    Node::paragraph(vec![Node::text("Danger.")]),
+                ],
+            ),
+            Node::div(
+                Attributes::class("other"),
+                vec![Node::paragraph(vec![Node::text("Plain.")])],
+            ),
+        ]);
+        let mdx = mdast_to_qmd(&root, &mdx_options());
+        assert_eq!(
+            mdx,
+            ":::note\n\nBe careful.\n\n:::\n\n:::warning[Thread safety]\n\nDanger.\n\n:::\n\nPlain.\n"
+        );
+    }
+
+    #[test]
+    fn test_fenced_div() {
+        let root = Root::new(vec![Node::div(
+            Attributes {
+                id: Some("note".to_string()),
+                classes: vec!["callout-note".to_string()],
+                attributes: vec![("title".to_string(), "A \"note\"".to_string())],
+            },
+            vec![
+                Node::heading(2, vec![Node::text("Note")]),
+                Node::paragraph(vec![Node::text("Text.")]),
+            ],
+        )]);
+        let qmd = mdast_to_qmd(&root, &WriterOptions::default());
+        assert_eq!(
+            qmd,
+            "::: {#note .callout-note title=\"A \\\"note\\\"\"}\n## Note\n\nText.\n:::\n"
+        );
+    }
+
+    #[test]
+    fn test_nested_fenced_div() {
+        let root = Root::new(vec![Node::div(
+            Attributes::class("outer"),
+            vec![Node::div(
+                Attributes::class("inner"),
+                vec![Node::paragraph(vec![Node::text("x")])],
+            )],
+        )]);
+        let qmd = mdast_to_qmd(&root, &WriterOptions::default());
+        assert_eq!(qmd, ":::: {.outer}\n::: {.inner}\nx\n:::\n::::\n");
+    }
+
+    #[test]
+    fn test_span() {
+        let root = Root::new(vec![Node::paragraph(vec![
+            Node::text("A "),
+            Node::span(Attributes::class("badge"), vec![Node::text("stable")]),
+        ])]);
+        let qmd = mdast_to_qmd(&root, &WriterOptions::default());
+        assert_eq!(qmd.trim(), "A [stable]{.badge}");
+    }
+
+    #[test]
+    fn test_mdx_span_anchor() {
+        let anchor = Attributes {
+            id: Some("arg-na-rm".to_string()),
+            ..Default::default()
+        };
+        let root = Root::new(vec![Node::paragraph(vec![
+            Node::span(anchor, vec![]),
+            Node::inline_code("na.rm"),
+        ])]);
+        let mdx = mdast_to_qmd(&root, &mdx_options());
+        assert_eq!(mdx.trim(), r#"<a id="arg-na-rm"></a>`na.rm`"#);
+    }
+
+    #[test]
+    fn test_raw_block_and_inline() {
+        let root = Root::new(vec![
+            Node::raw_block("html", "<details>\n<summary>More</summary>\n</details>"),
+            Node::paragraph(vec![Node::raw_inline("html", "<kbd>x</kbd>")]),
+        ]);
+        let qmd = mdast_to_qmd(&root, &WriterOptions::default());
+        assert_eq!(
+            qmd,
+            "```{=html}\n<details>\n<summary>More</summary>\n</details>\n```\n\n`<kbd>x</kbd>`{=html}\n"
+        );
+
+        // MDX passes HTML through and drops other formats
+        let root = Root::new(vec![
+            Node::raw_block("html", "<hr />"),
+            Node::raw_block("latex", "\\newpage"),
+        ]);
+        let mdx = mdast_to_qmd(&root, &mdx_options());
+        assert_eq!(mdx, "<hr />\n");
+    }
+
+    #[test]
+    fn test_commonmark_unwraps_divs_and_spans() {
+        let root = Root::new(vec![
+            Node::div(
+                Attributes::class("sourceCode"),
+                vec![Node::paragraph(vec![
+                    Node::span(Attributes::class("badge"), vec![Node::text("stable")]),
+                    Node::raw_inline("html", "<kbd>x</kbd>"),
+                ])],
+            ),
+            Node::raw_block("html", "<details>\n<summary>More</summary>\n</details>"),
+            Node::raw_block("latex", "\\newpage"),
+            Node::heading_with_id(2, vec![Node::text("Usage")], "usage"),
+            Node::definition_list(vec![
+                Node::definition_term(vec![Node::inline_code("x")]),
+                Node::definition_description(vec![Node::paragraph(vec![Node::text("An object.")])]),
+            ]),
+        ]);
+        let options = WriterOptions {
+            flavor: MarkdownFlavor::CommonMark,
+            ..Default::default()
+        };
+        let md = mdast_to_qmd(&root, &options);
+        assert_eq!(
+            md,
+            "stable<kbd>x</kbd>\n\n<details>\n<summary>More</summary>\n</details>\n\n## Usage\n\n- `x`: An object.\n"
+        );
+    }
+
+    #[test]
+    fn test_mdx_definition_list() {
+        let root = Root::new(vec![Node::definition_list(vec![
+            Node::definition_term(vec![Node::inline_code("x")]),
+            Node::definition_description(vec![Node::paragraph(vec![Node::text("An object.")])]),
+        ])]);
+        let mdx = mdast_to_qmd(&root, &mdx_options());
+        assert_eq!(mdx.trim(), "- `x`: An object.");
+    }
+
+    #[test]
+    fn test_mdx_frontmatter() {
+        let root = Root::new(vec![]);
+        let opts = WriterOptions {
+            frontmatter: Some(Frontmatter {
+                title: Some("Foo Bar".to_string()),
+                pagetitle: Some("Foo Bar — foo.bar".to_string()),
+                name: Some("foo.bar".to_string()),
+                id: Some("foo-bar".to_string()),
+                ..Default::default()
+            }),
+            ..mdx_options()
+        };
+        let mdx = mdast_to_qmd(&root, &opts);
+        assert_eq!(
+            mdx,
+            "---\ntitle: \"Foo Bar\"\nid: \"foo-bar\"\nsidebar_label: \"foo.bar\"\nslug: \"foo-bar\"\n---\n\n"
+        );
+    }
+
+    fn streaming_root() -> Root {
+        Root::new(vec![
+            Node::heading(2, vec![Node::text("Usage")]),
+            Node::code(Some("r".to_string()), "f(x)"),
+            Node::paragraph(vec![
+                Node::text("1. Not a list, "),
+                Node::inline_code("`x`"),
+                Node::text(" and more"),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn test_write_qmd_io() {
+        let root = streaming_root();
+        let opts = WriterOptions::default();
+        let mut bytes = Vec::new();
+        write_qmd_io(&root, &opts, &mut bytes).unwrap();
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            mdast_to_qmd(&root, &opts)
+        );
+    }
+
+    #[test]
+    fn test_write_qmd_io_error() {
+        /// Sink that fails after a number of bytes
+        struct Full(usize);
+
+        impl io::Write for Full {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                if buf.len() > self.0 {
+                    return Err(io::Error::new(io::ErrorKind::StorageFull, "full"));
+                }
+                self.0 -= buf.len();
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let err = write_qmd_io(&streaming_root(), &WriterOptions::default(), Full(10)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::StorageFull);
+    }
 }
@@ -0,0 +1,391 @@
+//! Package metadata from the DESCRIPTION file
+//!
+//! [`PackageDescription`] reads the fields shown on a package home page: title,
+//! description, version, authors, license, URLs and bug reports. Authors come
+//! from the `person()` calls of `Authors@R`, which are parsed without evaluating
+//! any R code; packages without `Authors@R` keep their `Author` field as text.
+
+use r_description::RDescription;
+use rd2qmd_core::{Node, make_slug};
+use std::fs;
+use std::path::Path;
+
+use crate::r_call::{RValue, parse_r};
+use crate::{PackageError, Result};
+
+/// Metadata of an R package from its DESCRIPTION file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageDescription {
+    /// Package name
+    pub name: String,
+    /// Package title
+    pub title: String,
+    /// Package version
+    pub version: String,
+    /// Description paragraph, with line breaks and indentation collapsed
+    pub description: String,
+    /// Authors from `Authors@R`
+    pub authors: Vec<Author>,
+    /// `Author` field, used when there is no `Authors@R`
+    pub author: Option<String>,
+    /// `Maintainer` field
+    pub maintainer: Option<String>,
+    /// License
+    pub license: String,
+    /// URLs from the `URL` field
+    pub urls: Vec<String>,
+    /// URL or e-mail address for bug reports
+    pub bug_reports: Option<String>,
+}
+
+/// An author from a `person()` call in `Authors@R`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Author {
+    /// Given and family names
+    pub name: String,
+    /// E-mail address
+    pub email: Option<String>,
+    /// MARC relator codes, such as `aut` and `cre`
+    pub roles: Vec<String>,
+    /// ORCID identifier from `comment = c(ORCID = ...)`
+    pub orcid: Option<String>,
+    /// Other comments, such as the ROR of an institution
+    pub comment: Option<String>,
+}
+
+impl Author {
+    /// Names of the roles, such as "author" and "maintainer"
+    pub fn role_names(&self) -> Vec<&str> {
+        self.roles.iter().map(|role| role_name(role)).collect()
+    }
+}
+
+/// Name of a MARC relator code used in `person()` roles
+fn role_name(code: &str) -> &str {
+    match code {
+        "aut" => "author",
+        "cre" => "maintainer",
+        "ctb" => "contributor",
+        "cph" => "copyright holder",
+        "fnd" => "funder",
+        "rev" => "reviewer",
+        "ths" => "thesis advisor",
+        "trl" => "translator",
+        "dtc" => "data contributor",
+        other => other,
+    }
+}
+
+impl PackageDescription {
+    /// Read a DESCRIPTION file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content).map_err(|message| PackageError::Parse {
+            file: path.to_path_buf(),
+            message,
+        })
+    }
+
+    /// Parse the content of a DESCRIPTION file
+    pub fn parse(content: &str) -> std::result::Result<Self, String> {
+        let desc: RDescription = content.parse()?;
+        let authors = desc
+            .authors
+            .as_ref()
+            .map(|code| parse_authors(&code.to_string()))
+            .unwrap_or_default();
+
+        Ok(Self {
+            name: desc.name,
+            title: collapse_whitespace(&desc.title),
+            version: desc.version.to_string(),
+            description: collapse_whitespace(&desc.description),
+            authors,
+            author: desc.author.map(|a| collapse_whitespace(&a)),
+            maintainer: desc.maintainer.map(|m| collapse_whitespace(&m)),
+            license: collapse_whitespace(&desc.license),
+            urls: desc
+                .url
+                .unwrap_or_default()
+                .into_iter()
+                .map(|entry| entry.url.to_string())
+                .collect(),
+            bug_reports: desc.bug_reports.map(|b| b.trim().to_string()),
+        })
+    }
+}
+
+/// Sections of the package home page after the description: version, links,
+/// license and authors
+pub(crate) fn home_page_sections(desc: &PackageDescription, heading_ids: bool) -> Vec<Node> {
+    let heading = |title: &str| {
+        let children = vec![Node::text(title)];
+        if heading_ids {
+            Node::heading_with_id(2, children, make_slug(title))
+        } else {
+            Node::heading(2, children)
+        }
+    };
+    let url_link = |url: &str| Node::link(url, vec![Node::text(url)]);
+
+    let mut nodes = vec![
+        heading("Version"),
+        Node::paragraph(vec![Node::text(&desc.version)]),
+    ];
+
+    let mut links: Vec<Node> = desc
+        .urls
+        .iter()
+        .map(|url| Node::list_item(vec![Node::paragraph(vec![url_link(url)])]))
+        .collect();
+    if let Some(bug_reports) = &desc.bug_reports {
+        let target = if bug_reports.contains("://") {
+            bug_reports.clone()
+        } else {
+            format!("mailto:{}", bug_reports)
+        };
+        links.push(Node::list_item(vec![Node::paragraph(vec![
+            Node::text("Report a bug: "),
+            Node::link(target, vec![Node::text(bug_reports)]),
+        ])]));
+    }
+    if !links.is_empty() {
+        nodes.push(heading("Links"));
+        nodes.push(Node::list(false, links));
+    }
+
+    nodes.push(heading("License"));
+    nodes.push(Node::paragraph(vec![Node::text(&desc.license)]));
+
+    let authors: Vec<Node> = desc
+        .authors
+        .iter()
+        .map(|author| {
+            let mut children = vec![Node::text(&author.name)];
+            let roles = author.role_names();
+            if !roles.is_empty() {
+                children.push(Node::text(format!(" ({})", roles.join(", "))));
+            }
+            if let Some(orcid) = &author.orcid {
+                children.push(Node::text(" "));
+                children.push(Node::link(
+                    format!("https://orcid.org/{}", orcid),
+                    vec![Node::text("ORCID")],
+                ));
+            }
+            Node::list_item(vec![Node::paragraph(children)])
+        })
+        .collect();
+    if !authors.is_empty() {
+        nodes.push(heading("Authors"));
+        nodes.push(Node::list(false, authors));
+    } else if let Some(author) = &desc.author {
+        nodes.push(heading("Authors"));
+        nodes.push(Node::paragraph(vec![Node::text(author)]));
+        if let Some(maintainer) = &desc.maintainer {
+            nodes.push(Node::paragraph(vec![Node::text(format!(
+                "Maintainer: {}",
+                maintainer
+            ))]));
+        }
+    }
+
+    nodes
+}
+
+/// Join the lines of a field, dropping their indentation
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse the authors from the `person()` calls of an `Authors@R` field
+///
+/// Both `person(...)` and `c(person(...), ...)` are understood, with arguments
+/// matched by name or position as R does. Anything else is ignored.
+pub fn parse_authors(code: &str) -> Vec<Author> {
+    let persons = match parse_r(code) {
+        RValue::Call(name, args) if name == "c" => args.into_iter().map(|(_, v)| v).collect(),
+        value => vec![value],
+    };
+    persons
+        .into_iter()
+        .filter_map(|value| match value {
+            RValue::Call(name, args) if name == "person" => Some(person_to_author(args)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parameters of R's `person()`, in positional order
+const PERSON_PARAMS: [&str; 8] = [
+    "given", "family", "middle", "email", "role", "comment", "first", "last",
+];
+
+/// Build an author from the arguments of a `person()` call
+fn person_to_author(args: Vec<(Option<String>, RValue)>) -> Author {
+    // Named arguments first, then positional ones fill the remaining parameters
+    let mut params: Vec<Option<RValue>> = PERSON_PARAMS.iter().map(|_| None).collect();
+    let mut positional = Vec::new();
+    for (name, value) in args {
+        match name.and_then(|n| PERSON_PARAMS.iter().position(|p| *p == n)) {
+            Some(i) => params[i] = Some(value),
+            None => positional.push(value),
+        }
+    }
+    let mut positional = positional.into_iter();
+    for param in params.iter_mut().filter(|p| p.is_none()) {
+        match positional.next() {
+            Some(value) => *param = Some(value),
+            None => break,
+        }
+    }
+
+    let strings = |i: usize| {
+        params[i]
+            .as_ref()
+            .map(|v| v.strings().into_iter().map(|(_, s)| s).collect::<Vec<_>>())
+            .unwrap_or_default()
+    };
+    // given, middle and family; `first` and `last` are their old names
+    let name = [0, 6, 2, 1, 7]
+        .into_iter()
+        .flat_map(strings)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut orcid = None;
+    let mut comments = Vec::new();
+    if let Some(comment) = &params[5] {
+        for (key, value) in comment.strings() {
+            match key.as_deref() {
+                Some("ORCID") => orcid = Some(value),
+                _ => comments.push(value),
+            }
+        }
+    }
+
+    Author {
+        name,
+        email: strings(3).into_iter().next(),
+        roles: strings(4),
+        orcid,
+        comment: (!comments.is_empty()).then(|| comments.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_authors() {
+        let code = r#"c(
+    person("Hadley", "Wickham", , "hadley@posit.co", role = c("aut", "cre"),
+           comment = c(ORCID = "0000-0003-4757-117X")),
+    # A comment
+    person(given = "Posit Software, PBC", role = c("cph", "fnd"),
+           comment = c(ROR = "03wc8by49")),
+    utils::person("Jane", "Doe", role = "ctb")
+  )"#;
+        let authors = parse_authors(code);
+        assert_eq!(
+            authors,
+            vec![
+                Author {
+                    name: "Hadley Wickham".to_string(),
+                    email: Some("hadley@posit.co".to_string()),
+                    roles: vec!["aut".to_string(), "cre".to_string()],
+                    orcid: Some("0000-0003-4757-117X".to_string()),
+                    comment: None,
+                },
+                Author {
+                    name: "Posit Software, PBC".to_string(),
+                    roles: vec!["cph".to_string(), "fnd".to_string()],
+                    comment: Some("03wc8by49".to_string()),
+                    ..Default::default()
+                },
+                Author {
+                    name: "Jane Doe".to_string(),
+                    roles: vec!["ctb".to_string()],
+                    ..Default::default()
+                },
+            ]
+        );
+        assert_eq!(authors[0].role_names(), vec!["author", "maintainer"]);
+        assert_eq!(authors[1].role_names(), vec!["copyright holder", "funder"]);
+    }
+
+    #[test]
+    fn test_parse_authors_single_person() {
+        let authors = parse_authors(r#"person('Ann', "O'Neil", email = "ann@example.org")"#);
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].name, "Ann O'Neil");
+        assert_eq!(authors[0].email.as_deref(), Some("ann@example.org"));
+        assert!(authors[0].roles.is_empty());
+    }
+
+    #[test]
+    fn test_parse_authors_unbalanced() {
+        let authors = parse_authors(r#"person("A"])"#);
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].name, "A");
+
+        let authors = parse_authors(r#"c(person("A", "B"}, role = "aut"), person("C"]"#);
+        assert_eq!(authors.len(), 2);
+        assert_eq!(authors[0].roles, vec!["aut"]);
+        assert_eq!(authors[1].name, "C");
+    }
+
+    #[test]
+    fn test_parse_description() {
+        let content = "Package: mypkg
+Title: Tools for Things
+Version: 1.2.0
+Authors@R: person(\"Ann\", \"Smith\", role = c(\"aut\", \"cre\"),
+    email = \"ann@example.org\")
+Description: Does things
+    with tools.
+License: MIT + file LICENSE
+URL: https://example.org/mypkg, https://github.com/ann/mypkg
+BugReports: https://github.com/ann/mypkg/issues
+";
+        let desc = PackageDescription::parse(content).unwrap();
+        assert_eq!(desc.name, "mypkg");
+        assert_eq!(desc.title, "Tools for Things");
+        assert_eq!(desc.version, "1.2.0");
+        assert_eq!(desc.description, "Does things with tools.");
+        assert_eq!(desc.authors.len(), 1);
+        assert_eq!(desc.authors[0].name, "Ann Smith");
+        assert_eq!(desc.license, "MIT + file LICENSE");
+        assert_eq!(
+            desc.urls,
+            vec!["https://example.org/mypkg", "https://github.com/ann/mypkg"]
+        );
+        assert_eq!(
+            desc.bug_reports.as_deref(),
+            Some("https://github.com/ann/mypkg/issues")
+        );
+    }
+
+    #[test]
+    fn test_home_page_heading_ids() {
+        let content = "Package: mypkg
+Title: Tools
+Version: 1.0.0
+Author: Ann Smith
+Maintainer: Ann Smith <ann@example.org>
+Description: Does things.
+License: MIT
+URL: https://example.org/mypkg
+";
+        let desc = PackageDescription::parse(content).unwrap();
+        let ids: Vec<_> = home_page_sections(&desc, true)
+            .into_iter()
+            .filter_map(|node| match node {
+                Node::Heading(h) => h.attributes.id,
+                _ => None,
+            })
+            .collect();
+        assert_eq!(ids, vec!["version", "links", "license", "authors"]);
+    }
+}
@@ -306,13 +306,9 @@ impl PackageUrlResolver {
 pub fn collect_external_packages(package: &RdPackage) -> HashSet<String> {
     let mut packages = HashSet::new();
 
-    for file in &package.files {
-        if let Ok(content) = fs::read_to_string(file)
-            && let Ok(doc) = rd2qmd_core::parse(&content)
-        {
-            for section in &doc.sections {
-                collect_packages_from_nodes(&section.content, &mut packages);
-            }
+    for topic in package.topics() {
+        for section in &topic.document().sections {
+            collect_packages_from_nodes(&section.content, &mut packages);
         }
     }
 
@@ -4,6 +4,11 @@
 //! (directories of Rd files) to Quarto Markdown, including:
 //! - Alias index building for correct link resolution
 //! - Batch conversion with parallel processing
+//! - An optional package home page from the DESCRIPTION file
+//! - A reference index grouped by the sections of `_pkgdown.yml`
+//!
+//! Each Rd file is parsed once, when the [`RdPackage`] is loaded; conversion,
+//! the topic index and external link collection share the parsed documents.
 //!
 //! This crate is designed to be used by various interfaces (CLI, R package, etc.)
 //!
@@ -11,13 +16,18 @@
 //!
 //! - `external-links`: Enable external package link resolution (requires network access)
 
+mod description;
 #[cfg(feature = "external-links")]
 pub mod external_links;
+mod pkgdown;
+mod r_call;
 
+pub use description::{Author, PackageDescription, parse_authors};
 #[cfg(feature = "external-links")]
 pub use external_links::{
     PackageResolveResult, PackageUrlResolver, PackageUrlResolverOptions, collect_external_packages,
 };
+pub use pkgdown::{PkgdownReference, ReferenceIndex, ReferenceSection, ReferenceSectionSpec};
 
 /// Reason why a fallback URL was used for a package
 ///
@@ -33,13 +43,18 @@ pub enum FallbackReason {
 
 use rayon::prelude::*;
 use rd2qmd_core::{
-    Frontmatter, RdMetadata, RdToMdastOptions, SectionTag, WriterOptions, extract_rd_metadata,
-    extract_text, mdast_to_qmd, parse, parse_roxygen_comments, rd_to_mdast_with_options,
+    ArgumentsFormat, ChunkOptions, DefaultLinkResolver, Diagnostic, Frontmatter, ItemsFormat,
+    LinkResolver, MathFormat, Node, RdDocument, RdMetadata, RdToMdastOptions, Root, SectionTag,
+    TableHeader, TabularFormat, Typography, WriterFormat, WriterOptions, extract_rd_metadata,
+    extract_text, latex_document, parse, parse_roxygen_comments, rd_to_mdast_with_diagnostics,
+    topic_label, topic_link_title,
 };
 use serde::Serialize;
-use std::collections::HashMap;
-use std::fs;
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Errors that can occur during package operations
 #[derive(Debug, thiserror::Error)]
@@ -76,27 +91,80 @@ pub struct RdPackage {
     root: PathBuf,
     /// List of Rd files in the package
     files: Vec<PathBuf>,
+    /// Parsed Rd files, in the order of `files`
+    topics: Vec<RdTopic>,
     /// Alias index: maps alias names to Rd file basenames (without extension)
-    alias_index: HashMap<String, String>,
+    alias_index: Arc<HashMap<String, String>>,
+    /// Link titles by Rd file basename (title and first description sentence)
+    link_titles: Arc<HashMap<String, String>>,
+}
+
+/// A parsed Rd file of a package
+#[derive(Debug, Clone)]
+pub struct RdTopic {
+    /// Path of the Rd file
+    path: PathBuf,
+    /// Parsed document, shared by all conversions of the package
+    document: Arc<RdDocument>,
+    /// Source files from the roxygen2 header comments
+    source_files: Vec<String>,
+}
+
+impl RdTopic {
+    /// Read and parse an Rd file
+    fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let document = parse(&content).map_err(|e| PackageError::Parse {
+            file: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            document: Arc::new(document),
+            source_files: parse_roxygen_comments(&content).source_files,
+        })
+    }
+
+    /// Get the path of the Rd file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the parsed document
+    pub fn document(&self) -> &RdDocument {
+        &self.document
+    }
+
+    /// Get the source files from the roxygen2 header comments
+    pub fn source_files(&self) -> &[String] {
+        &self.source_files
+    }
 }
 
 impl RdPackage {
     /// Load a package from a directory containing Rd files
     ///
-    /// This scans the directory for .Rd files and builds an alias index
-    /// by parsing each file and extracting \alias{} tags.
+    /// This scans the directory for .Rd files, parses them in parallel and
+    /// builds an alias index from their \alias{} tags, along with the link
+    /// title of each topic.
     pub fn from_directory(path: &Path, recursive: bool) -> Result<Self> {
         if !path.is_dir() {
             return Err(PackageError::DirectoryNotFound(path.to_path_buf()));
         }
 
         let files = collect_rd_files(path, recursive)?;
-        let alias_index = build_alias_index(&files)?;
+        let topics = files
+            .par_iter()
+            .map(|file| RdTopic::load(file))
+            .collect::<Result<Vec<_>>>()?;
+        let (alias_index, link_titles) = build_alias_index(&topics);
 
         Ok(Self {
             root: path.to_path_buf(),
             files,
-            alias_index,
+            topics,
+            alias_index: Arc::new(alias_index),
+            link_titles: Arc::new(link_titles),
         })
     }
 
@@ -105,16 +173,36 @@ impl RdPackage {
         &self.root
     }
 
+    /// Get the package directory, with the DESCRIPTION file: the parent of a
+    /// `man` root, or else the root itself
+    pub fn package_dir(&self) -> &Path {
+        match self.root.file_name() {
+            Some(name) if name == "man" => self.root.parent().unwrap_or(&self.root),
+            _ => &self.root,
+        }
+    }
+
     /// Get the list of Rd files in the package
     pub fn files(&self) -> &[PathBuf] {
         &self.files
     }
 
+    /// Get the parsed Rd files, in the order of [`files`](Self::files)
+    pub fn topics(&self) -> &[RdTopic] {
+        &self.topics
+    }
+
     /// Get the alias index (maps alias names to Rd file basenames)
     pub fn alias_index(&self) -> &HashMap<String, String> {
         &self.alias_index
     }
 
+    /// Get the link titles (maps Rd file basenames to the topic title and the
+    /// first sentence of its description)
+    pub fn link_titles(&self) -> &HashMap<String, String> {
+        &self.link_titles
+    }
+
     /// Get the target filename for a given alias
     ///
     /// Returns the Rd file basename (without extension) that contains this alias,
@@ -148,6 +236,12 @@ pub struct PackageConvertOptions {
     /// Used for resolving `\link[pkg]{topic}` patterns to actual URLs.
     /// Example: `{"dplyr" -> "https://dplyr.tidyverse.org/reference"}`
     pub external_package_urls: Option<HashMap<String, String>>,
+    /// Custom link resolver, used instead of the package's alias index,
+    /// `unresolved_link_url` and `external_package_urls`
+    pub link_resolver: Option<Arc<dyn LinkResolver>>,
+    /// Give links to topics of the package their title and the first sentence
+    /// of their description as link title (default: true)
+    pub link_titles: bool,
     /// Make \dontrun{} example code executable (default: false)
     /// Matches pkgdown semantics: \dontrun{} means "never run this code"
     pub exec_dontrun: bool,
@@ -155,10 +249,48 @@ pub struct PackageConvertOptions {
     /// Matches pkgdown semantics: \donttest{} means "don't run during testing"
     /// but the code should normally be executable
     pub exec_donttest: bool,
+    /// Label executable example chunks `ex-<topic>-1`, ... (default: true)
+    pub chunk_labels: bool,
+    /// Chunk options for executable example chunks, globally and per topic
+    pub chunk_options: ChunkOptions,
     /// Include topics with \keyword{internal} (default: false)
     /// By default, internal topics are skipped (matching pkgdown behavior).
     /// Set to true to include internal topics in the output.
     pub include_internal: bool,
+    /// Output document format (default: Markdown)
+    pub format: WriterFormat,
+    /// Render the Note section and warning-like sections as Quarto callouts
+    /// (default: true)
+    pub callouts: bool,
+    /// Translate `\out{}` HTML to mdast instead of keeping it as raw HTML
+    /// (default: false)
+    pub translate_html: bool,
+    /// Give section headings and arguments pkgdown-compatible anchor ids
+    /// (default: true)
+    pub heading_ids: bool,
+    /// Rendering of `\eqn` and `\deqn` equations (default: LaTeX math)
+    pub math: MathFormat,
+    /// Quotes, dashes and ellipses in text (default: Unicode)
+    pub typography: Typography,
+    /// `\tabular` table format (default: pipe table unless cells contain lists or code)
+    pub tabular_format: TabularFormat,
+    /// Header row for `\tabular` tables (default: first row)
+    pub table_header: TableHeader,
+    /// Rendering of `\value` components (default: definition list)
+    pub value_items: ItemsFormat,
+    /// Rendering of `\describe` items in the Format section (default: definition list)
+    pub format_items: ItemsFormat,
+    /// Directory under `output_dir` that figures from `man/figures` are copied to
+    /// (default: `assets`)
+    /// Image URLs are rewritten relative to each output file. If None, figures are
+    /// not copied and image URLs are the bare file names from `\figure{}`.
+    pub assets_dir: Option<PathBuf>,
+    /// Write a package home page, `index.<output_extension>`, from the DESCRIPTION
+    /// file and, for Quarto and R Markdown output, README.md of the package
+    /// directory (default: false)
+    /// The home page is skipped with a warning if a topic (`man/index.Rd`) is
+    /// written to the same file.
+    pub home_page: bool,
 }
 
 impl Default for PackageConvertOptions {
@@ -172,9 +304,25 @@ impl Default for PackageConvertOptions {
             parallel_jobs: None,
             unresolved_link_url: None,
             external_package_urls: None,
+            link_resolver: None,
+            link_titles: true,
             exec_dontrun: false,
             exec_donttest: true, // pkgdown-compatible: \donttest{} is executable by default
+            chunk_labels: true,
+            chunk_options: ChunkOptions::default(),
             include_internal: false, // pkgdown-compatible: skip internal topics by default
+            format: WriterFormat::Markdown,
+            callouts: true,
+            translate_html: false,
+            heading_ids: true,
+            math: MathFormat::Latex,
+            typography: Typography::Unicode,
+            tabular_format: TabularFormat::Auto,
+            table_header: TableHeader::FirstRow,
+            value_items: ItemsFormat::DefinitionList,
+            format_items: ItemsFormat::DefinitionList,
+            assets_dir: Some(PathBuf::from("assets")),
+            home_page: false,
         }
     }
 }
@@ -190,6 +338,12 @@ pub struct ConvertResult {
     pub output_files: Vec<PathBuf>,
     /// Files skipped because they have \keyword{internal}
     pub skipped_internal: Vec<PathBuf>,
+    /// Problems in the Rd source of converted files, with their messages
+    pub warnings: Vec<(PathBuf, String)>,
+    /// Figures copied to the assets directory
+    pub asset_files: Vec<PathBuf>,
+    /// Package home page, when requested and written
+    pub home_page: Option<PathBuf>,
 }
 
 /// Information about a single topic (Rd file) for index generation
@@ -218,6 +372,53 @@ impl TopicIndex {
     pub fn to_json(&self) -> Result<String> {
         serde_json::to_string_pretty(self).map_err(|e| PackageError::Io(std::io::Error::other(e)))
     }
+
+    /// Serialize the index as a Docusaurus sidebar category (JSON)
+    ///
+    /// The result can be `require()`d as an item of a `sidebars.js` sidebar.
+    /// Each topic becomes a doc item whose id is `id_prefix` followed by the
+    /// output file name without extension, labelled with the topic name.
+    pub fn to_sidebar_category(&self, label: &str, id_prefix: &str) -> Result<String> {
+        let category = SidebarCategory {
+            kind: "category",
+            label,
+            items: self
+                .topics
+                .iter()
+                .map(|topic| {
+                    let stem = Path::new(&topic.file)
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or(&topic.file);
+                    SidebarDoc {
+                        kind: "doc",
+                        id: format!("{}{}", id_prefix, stem),
+                        label: &topic.name,
+                    }
+                })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&category)
+            .map_err(|e| PackageError::Io(std::io::Error::other(e)))
+    }
+}
+
+/// Docusaurus sidebar category item
+#[derive(Serialize)]
+struct SidebarCategory<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    label: &'a str,
+    items: Vec<SidebarDoc<'a>>,
+}
+
+/// Docusaurus sidebar doc item
+#[derive(Serialize)]
+struct SidebarDoc<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    id: String,
+    label: &'a str,
 }
 
 /// Options for topic index generation
@@ -251,26 +452,13 @@ pub fn generate_topic_index(
 ) -> Result<TopicIndex> {
     let mut topics = Vec::new();
 
-    for file in &package.files {
-        match extract_topic_info(file, &options.output_extension) {
-            Ok(info) => {
-                // Skip internal topics unless include_internal is set
-                if !options.include_internal
-                    && info.metadata.keywords.contains(&"internal".to_string())
-                {
-                    continue;
-                }
-                topics.push(info);
-            }
-            Err(e) => {
-                // Log error but continue processing other files
-                eprintln!(
-                    "Warning: failed to extract topic info from {}: {}",
-                    file.display(),
-                    e
-                );
-            }
+    for topic in &package.topics {
+        let info = extract_topic_info(topic, &options.output_extension);
+        // Skip internal topics unless include_internal is set
+        if !options.include_internal && info.metadata.keywords.contains(&"internal".to_string()) {
+            continue;
         }
+        topics.push(info);
     }
 
     // Sort by name for consistent output
@@ -279,17 +467,9 @@ pub fn generate_topic_index(
     Ok(TopicIndex { topics })
 }
 
-/// Extract topic information from a single Rd file
-fn extract_topic_info(file: &Path, output_extension: &str) -> Result<TopicInfo> {
-    let content = fs::read_to_string(file)?;
-
-    // Extract roxygen2 metadata (source files) from header comments
-    let roxygen = parse_roxygen_comments(&content);
-
-    let doc = parse(&content).map_err(|e| PackageError::Parse {
-        file: file.to_path_buf(),
-        message: e.to_string(),
-    })?;
+/// Extract topic information from a parsed Rd file
+fn extract_topic_info(topic: &RdTopic, output_extension: &str) -> TopicInfo {
+    let doc = &topic.document;
 
     // Extract name
     let name = doc
@@ -304,24 +484,24 @@ fn extract_topic_info(file: &Path, output_extension: &str) -> Result<TopicInfo>
         .unwrap_or_default();
 
     // Extract metadata using shared function
-    let metadata = extract_rd_metadata(&doc, roxygen.source_files);
+    let metadata = extract_rd_metadata(doc, topic.source_files.clone());
 
     // Determine output filename
-    let basename = file.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-    let output_file = format!("{}.{}", basename, output_extension);
+    let output_file = format!("{}.{}", topic_basename(&topic.path), output_extension);
 
-    Ok(TopicInfo {
+    TopicInfo {
         name,
         file: output_file,
         title,
         metadata,
-    })
+    }
 }
 
 /// Outcome of converting a single file
 enum ConvertOutcome {
-    /// Successfully converted, contains output path
-    Success(PathBuf),
+    /// Successfully converted, contains input path, output path, diagnostics and
+    /// the figures referenced by the file
+    Success(PathBuf, PathBuf, Vec<Diagnostic>, Vec<String>),
     /// Skipped because the topic has \keyword{internal}
     SkippedInternal(PathBuf),
     /// Failed to convert, contains input path and error message
@@ -336,35 +516,47 @@ pub fn convert_package(
     package: &RdPackage,
     options: &PackageConvertOptions,
 ) -> Result<ConvertResult> {
-    // Configure thread pool if specified
-    if let Some(n) = options.parallel_jobs {
-        rayon::ThreadPoolBuilder::new()
-            .num_threads(n)
-            .build_global()
-            .ok();
-    }
-
     // Create output directory if needed
     fs::create_dir_all(&options.output_dir)?;
 
-    // Convert files in parallel
-    let results: Vec<_> = package
-        .files
-        .par_iter()
-        .map(|file| convert_single_file(file, package, options))
-        .collect();
+    // Convert files in parallel, sharing one link resolver
+    let link_resolver = package_link_resolver(package, options);
+    let convert_all = || -> Vec<_> {
+        package
+            .topics
+            .par_iter()
+            .map(|topic| convert_single_file(topic, package, options, &link_resolver))
+            .collect()
+    };
+
+    // Use a thread pool of the requested size if specified
+    let pool = options
+        .parallel_jobs
+        .and_then(|n| rayon::ThreadPoolBuilder::new().num_threads(n).build().ok());
+    let results = match pool {
+        Some(pool) => pool.install(convert_all),
+        None => convert_all(),
+    };
 
     // Collect results
     let mut success_count = 0;
     let mut failed_files = Vec::new();
     let mut output_files = Vec::new();
     let mut skipped_internal = Vec::new();
+    let mut warnings = Vec::new();
+    let mut figures = BTreeSet::new();
 
     for result in results {
         match result {
-            ConvertOutcome::Success(output_path) => {
+            ConvertOutcome::Success(input_path, output_path, diagnostics, file_figures) => {
                 success_count += 1;
                 output_files.push(output_path);
+                figures.extend(file_figures);
+                warnings.extend(
+                    diagnostics
+                        .into_iter()
+                        .map(|d| (input_path.clone(), d.message)),
+                );
             }
             ConvertOutcome::SkippedInternal(input_path) => {
                 skipped_internal.push(input_path);
@@ -375,94 +567,505 @@ pub fn convert_package(
         }
     }
 
+    let home_page = if options.home_page {
+        let path = options
+            .output_dir
+            .join("index")
+            .with_extension(&options.output_extension);
+        // A topic named `index` (man/index.Rd) is written to the same file
+        let result = if output_files.contains(&path) {
+            Err((path, "a topic was converted to the same file".to_string()))
+        } else {
+            write_home_page(package, options, path, &mut figures)
+        };
+        match result {
+            Ok(path) => Some(path),
+            Err((path, message)) => {
+                warnings.push((path, format!("home page not written: {}", message)));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Copy each referenced figure once, after the parallel conversion
+    let asset_files = copy_figures(package, options, &figures, &mut warnings);
+
     Ok(ConvertResult {
         success_count,
         failed_files,
         output_files,
         skipped_internal,
+        warnings,
+        asset_files,
+        home_page,
     })
 }
 
+/// A package reference manual rendered as a single LaTeX document
+#[derive(Debug)]
+pub struct ReferenceManual {
+    /// Complete LaTeX document
+    pub document: String,
+    /// Number of topics included in the manual
+    pub topic_count: usize,
+    /// Files that failed to convert, with their errors
+    pub failed_files: Vec<(PathBuf, String)>,
+    /// Files skipped because they have \keyword{internal}
+    pub skipped_internal: Vec<PathBuf>,
+    /// Problems in the Rd source of included topics, with their messages
+    pub warnings: Vec<(PathBuf, String)>,
+}
+
+/// Render all topics of a package into a single LaTeX document
+///
+/// This is the package-level counterpart of the LaTeX writer, producing a
+/// reference manual like `R CMD Rd2pdf` without R. Topics are ordered by file
+/// name and each is anchored by a `topic:<basename>` label, so links between
+/// topics (resolved through the alias index) become `hyperref` references.
+///
+/// `options.format`, `options.frontmatter` and `options.output_extension` are
+/// ignored; `title` is typeset on the title page.
+pub fn package_to_latex(
+    package: &RdPackage,
+    options: &PackageConvertOptions,
+    title: Option<&str>,
+) -> ReferenceManual {
+    let options = PackageConvertOptions {
+        output_extension: "tex".to_string(),
+        frontmatter: false,
+        format: WriterFormat::Latex,
+        assets_dir: None,
+        ..options.clone()
+    };
+
+    let mut topics: Vec<&RdTopic> = package.topics.iter().collect();
+    topics.sort_by_key(|t| topic_basename(&t.path).to_lowercase());
+
+    let link_resolver = package_link_resolver(package, &options);
+    let results: Vec<_> = topics
+        .par_iter()
+        .map(|topic| {
+            convert_file(topic, package, &options, &link_resolver)
+                .map(|file| (file.write(&options.format), file.diagnostics))
+        })
+        .collect();
+
+    let mut body = String::new();
+    if title.is_some() {
+        body.push_str("\\maketitle\n");
+    }
+    body.push_str("\\tableofcontents\n");
+
+    let mut topic_count = 0;
+    let mut failed_files = Vec::new();
+    let mut skipped_internal = Vec::new();
+    let mut warnings = Vec::new();
+    for (topic, result) in topics.into_iter().zip(results) {
+        let file = &topic.path;
+        match result {
+            Ok((latex, diagnostics)) => {
+                warnings.extend(diagnostics.into_iter().map(|d| (file.clone(), d.message)));
+                topic_count += 1;
+                body.push('\n');
+                body.push_str(&format!(
+                    "\\phantomsection\\label{{{}}}\n",
+                    topic_label(topic_basename(file))
+                ));
+                body.push_str(&latex);
+            }
+            Err(ConvertError::SkipInternal) => skipped_internal.push(file.clone()),
+            Err(ConvertError::Failed(msg)) => failed_files.push((file.clone(), msg)),
+        }
+    }
+
+    ReferenceManual {
+        document: latex_document(title, &body),
+        topic_count,
+        failed_files,
+        skipped_internal,
+        warnings,
+    }
+}
+
+/// Rd file basename (without extension), the target of internal links
+fn topic_basename(file: &Path) -> &str {
+    file.file_stem().and_then(|s| s.to_str()).unwrap_or("")
+}
+
 /// Check if a document has \keyword{internal}
-fn has_keyword_internal(doc: &rd2qmd_core::RdDocument) -> bool {
+fn has_keyword_internal(doc: &RdDocument) -> bool {
     doc.get_sections(&SectionTag::Keyword)
         .iter()
         .any(|s| extract_text(&s.content).eq_ignore_ascii_case("internal"))
 }
 
-/// Convert a single Rd file
-fn convert_single_file(
-    input: &Path,
+/// A single Rd file converted to mdast, ready to be written
+struct ConvertedFile {
+    /// Converted document
+    mdast: Root,
+    /// Options for writing the document
+    writer_options: WriterOptions,
+    /// Problems in the Rd source
+    diagnostics: Vec<Diagnostic>,
+    /// Figures under `man/figures` referenced by the file, relative to that directory
+    figures: Vec<String>,
+}
+
+impl ConvertedFile {
+    /// Render the document in an output format
+    fn write(&self, format: &WriterFormat) -> String {
+        format.write(&self.mdast, &self.writer_options)
+    }
+
+    /// Render the document in an output format to a file, streaming when the
+    /// format allows
+    fn write_file(&self, format: &WriterFormat, path: &Path) -> std::io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        format.write_to(&self.mdast, &self.writer_options, &mut out)?;
+        out.flush()
+    }
+}
+
+/// Link resolver for a package conversion: the custom resolver, or one using the
+/// package's alias index
+fn package_link_resolver(
     package: &RdPackage,
     options: &PackageConvertOptions,
-) -> ConvertOutcome {
-    let convert = || -> std::result::Result<PathBuf, ConvertError> {
-        // Read input file
-        let content = fs::read_to_string(input).map_err(|e| ConvertError::Failed(e.to_string()))?;
+) -> Arc<dyn LinkResolver> {
+    match &options.link_resolver {
+        Some(resolver) => Arc::clone(resolver),
+        None => Arc::new(DefaultLinkResolver {
+            extension: Some(options.output_extension.clone()),
+            alias_map: Arc::clone(&package.alias_index),
+            titles: if options.link_titles {
+                Arc::clone(&package.link_titles)
+            } else {
+                Arc::default()
+            },
+            unresolved_url: options.unresolved_link_url.clone(),
+            external_package_urls: options.external_package_urls.clone().unwrap_or_default(),
+        }),
+    }
+}
+
+/// Convert a single Rd file to mdast for the requested output format
+fn convert_file(
+    topic: &RdTopic,
+    package: &RdPackage,
+    options: &PackageConvertOptions,
+    link_resolver: &Arc<dyn LinkResolver>,
+) -> std::result::Result<ConvertedFile, ConvertError> {
+    let input = topic.path.as_path();
+    let doc = &*topic.document;
+
+    // Check for \keyword{internal} - skip unless include_internal is set
+    if !options.include_internal && has_keyword_internal(doc) {
+        return Err(ConvertError::SkipInternal);
+    }
+
+    // Build converter options with the shared link resolver
+    let converter_options = RdToMdastOptions {
+        link_resolver: Arc::clone(link_resolver),
+        exec_dontrun: options.exec_dontrun,
+        exec_donttest: options.exec_donttest,
+        quarto_code_blocks: options.format.supports_code_execution() && options.quarto_code_blocks,
+        chunk_labels: options.chunk_labels,
+        chunk_options: options.chunk_options.clone(),
+        arguments_format: if options.format.supports_grid_tables() {
+            ArgumentsFormat::default()
+        } else {
+            ArgumentsFormat::PipeTable
+        },
+        tabular_format: if options.format.supports_grid_tables() {
+            options.tabular_format
+        } else {
+            TabularFormat::PipeTable
+        },
+        table_header: options.table_header,
+        value_items: options.value_items,
+        format_items: options.format_items,
+        callouts: options.callouts,
+        translate_html: options.translate_html,
+        heading_ids: options.heading_ids,
+        rd_format: options.format.rd_format(),
+        math: options.math,
+        typography: options.typography,
+    };
 
-        // Parse Rd
-        let doc =
-            parse(&content).map_err(|e| ConvertError::Failed(format!("Parse error: {}", e)))?;
+    // Convert to mdast
+    let (mut mdast, mut diagnostics) = rd_to_mdast_with_diagnostics(doc, &converter_options);
 
-        // Check for \keyword{internal} - skip unless include_internal is set
-        if !options.include_internal && has_keyword_internal(&doc) {
-            return Err(ConvertError::SkipInternal);
+    // Point figures at the copied assets
+    let mut figures = Vec::new();
+    if let Some(assets_dir) = &options.assets_dir {
+        let relative = input.strip_prefix(&package.root).unwrap_or(input);
+        let depth = relative.components().count().saturating_sub(1);
+        let mut prefix = "../".repeat(depth);
+        for component in assets_dir.components() {
+            prefix.push_str(&component.as_os_str().to_string_lossy());
+            prefix.push('/');
         }
+        let figures_dir = package.root.join("figures");
+        rewrite_figure_urls(
+            &mut mdast.children,
+            &figures_dir,
+            &prefix,
+            &mut figures,
+            &mut diagnostics,
+        );
+    }
 
-        // Build converter options with alias map
-        let converter_options = RdToMdastOptions {
-            link_extension: Some(options.output_extension.clone()),
-            alias_map: Some(package.alias_index.clone()),
-            unresolved_link_url: options.unresolved_link_url.clone(),
-            external_package_urls: options.external_package_urls.clone(),
-            exec_dontrun: options.exec_dontrun,
-            exec_donttest: options.exec_donttest,
-            quarto_code_blocks: options.quarto_code_blocks,
-            ..Default::default()
-        };
+    // Extract title and name for frontmatter
+    let title = doc
+        .get_section(&SectionTag::Title)
+        .map(|s| extract_text(&s.content));
+    let name = doc
+        .get_section(&SectionTag::Name)
+        .map(|s| extract_text(&s.content));
 
-        // Convert to mdast
-        let mdast = rd_to_mdast_with_options(&doc, &converter_options);
-
-        // Extract title and name for frontmatter
-        let title = doc
-            .get_section(&SectionTag::Title)
-            .map(|s| extract_text(&s.content));
-        let name = doc
-            .get_section(&SectionTag::Name)
-            .map(|s| extract_text(&s.content));
-
-        // Build pagetitle in pkgdown style: "<title> — <name>"
-        let pagetitle = if options.pagetitle {
-            match (&title, &name) {
-                (Some(t), Some(n)) => Some(format!("{} \u{2014} {}", t, n)),
-                _ => None,
-            }
+    // Build pagetitle in pkgdown style: "<title> — <name>"
+    let pagetitle = if options.pagetitle {
+        match (&title, &name) {
+            (Some(t), Some(n)) => Some(format!("{} \u{2014} {}", t, n)),
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    // Extract Rd metadata, including source files from roxygen2 comments
+    let metadata = extract_rd_metadata(doc, topic.source_files.clone());
+
+    // Build writer options
+    let writer_options = WriterOptions {
+        frontmatter: if options.frontmatter {
+            Some(Frontmatter {
+                title,
+                pagetitle,
+                format: None,
+                name,
+                id: Some(topic_basename(input).to_string()),
+                metadata: Some(metadata),
+            })
         } else {
             None
-        };
+        },
+        quarto_code_blocks: options.quarto_code_blocks,
+        typography: options.typography,
+        ..Default::default()
+    };
 
-        // Extract Rd metadata, including source files from roxygen2 comments
-        let roxygen = parse_roxygen_comments(&content);
-        let metadata = extract_rd_metadata(&doc, roxygen.source_files);
-
-        // Build writer options
-        let writer_options = WriterOptions {
-            frontmatter: if options.frontmatter {
-                Some(Frontmatter {
-                    title,
-                    pagetitle,
-                    format: None,
-                    metadata: Some(metadata),
-                })
-            } else {
-                None
-            },
-            quarto_code_blocks: options.quarto_code_blocks,
-        };
+    Ok(ConvertedFile {
+        mdast,
+        writer_options,
+        diagnostics,
+        figures,
+    })
+}
+
+/// Rewrite relative image URLs to `<prefix><file>` for figures found in `figures_dir`
+///
+/// R resolves `\figure{file}` relative to `man/figures`. Found figures are collected in
+/// `figures`; missing ones are reported and keep their URL.
+fn rewrite_figure_urls(
+    nodes: &mut [Node],
+    figures_dir: &Path,
+    prefix: &str,
+    figures: &mut Vec<String>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for node in nodes {
+        if let Node::Image(img) = node {
+            let is_relative = !img.url.contains("://")
+                && !img.url.starts_with('/')
+                && !img.url.starts_with("data:");
+            if is_relative {
+                if figures_dir.join(&img.url).is_file() {
+                    figures.push(img.url.clone());
+                    img.url = format!("{}{}", prefix, img.url);
+                } else {
+                    diagnostics.push(Diagnostic {
+                        message: format!("figure not found: figures/{}", img.url),
+                    });
+                }
+            }
+        } else if let Some(children) = node.children_mut() {
+            rewrite_figure_urls(children, figures_dir, prefix, figures, diagnostics);
+        }
+    }
+}
+
+/// Copy figures from `man/figures` to the assets directory, returning the copies
+fn copy_figures(
+    package: &RdPackage,
+    options: &PackageConvertOptions,
+    figures: &BTreeSet<String>,
+    warnings: &mut Vec<(PathBuf, String)>,
+) -> Vec<PathBuf> {
+    let Some(assets_dir) = &options.assets_dir else {
+        return Vec::new();
+    };
+    let figures_dir = package.root.join("figures");
+    let assets_dir = options.output_dir.join(assets_dir);
+
+    let mut copied = Vec::new();
+    for figure in figures {
+        let source = figures_dir.join(figure);
+        let target = assets_dir.join(figure);
+        let result = target
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::copy(&source, &target));
+        match result {
+            Ok(_) => copied.push(target),
+            Err(e) => warnings.push((source, format!("failed to copy figure: {}", e))),
+        }
+    }
+    copied
+}
+
+/// Write the package home page from DESCRIPTION and, for Pandoc Markdown output,
+/// README.md
+///
+/// Figures of `man/figures` that the README shows are added to `figures`.
+/// Returns the path of the page, or the file that could not be read or written
+/// with the error.
+fn write_home_page(
+    package: &RdPackage,
+    options: &PackageConvertOptions,
+    path: PathBuf,
+    figures: &mut BTreeSet<String>,
+) -> std::result::Result<PathBuf, (PathBuf, String)> {
+    let package_dir = package.package_dir();
+    let desc_path = package_dir.join("DESCRIPTION");
+    let desc = PackageDescription::from_file(&desc_path).map_err(|e| (desc_path, e.to_string()))?;
+
+    // README.md can only be included in Markdown output. MDX is left out: raw
+    // HTML, braces and HTML comments in a README break MDX.
+    let readme = match options.format {
+        WriterFormat::Markdown | WriterFormat::Rmd | WriterFormat::CommonMark => {
+            fs::read_to_string(package_dir.join("README.md")).ok()
+        }
+        _ => None,
+    };
+    // Point its figures at the copied assets, relative to the home page
+    let readme = match (readme, &options.assets_dir) {
+        (Some(readme), Some(assets_dir)) => {
+            let mut prefix = String::new();
+            for component in assets_dir.components() {
+                prefix.push_str(&component.as_os_str().to_string_lossy());
+                prefix.push('/');
+            }
+            let figures_dir = package.root.join("figures");
+            Some(rewrite_readme_figures(
+                &readme,
+                &figures_dir,
+                &prefix,
+                figures,
+            ))
+        }
+        (readme, _) => readme,
+    };
+
+    let writer_options = WriterOptions {
+        frontmatter: options.frontmatter.then(|| Frontmatter {
+            title: Some(desc.title.clone()),
+            pagetitle: options
+                .pagetitle
+                .then(|| format!("{} \u{2014} {}", desc.title, desc.name)),
+            format: None,
+            name: Some(desc.name.clone()),
+            id: Some("index".to_string()),
+            metadata: None,
+        }),
+        quarto_code_blocks: options.quarto_code_blocks,
+        typography: options.typography,
+        ..Default::default()
+    };
+    let intro = vec![Node::paragraph(vec![Node::text(&desc.description)])];
+    let sections = description::home_page_sections(&desc, options.heading_ids);
+
+    let write = || -> std::io::Result<()> {
+        let mut out = BufWriter::new(File::create(&path)?);
+        match readme {
+            // The README goes between the description and the package metadata
+            Some(readme) => {
+                let format = &options.format;
+                format.write_to(&Root::new(intro), &writer_options, &mut out)?;
+                writeln!(out)?;
+                writeln!(out, "{}", readme.trim())?;
+                writeln!(out)?;
+                let writer_options = WriterOptions {
+                    frontmatter: None,
+                    ..writer_options.clone()
+                };
+                format.write_to(&Root::new(sections), &writer_options, &mut out)?;
+            }
+            None => {
+                let root = Root::new(intro.into_iter().chain(sections).collect());
+                options.format.write_to(&root, &writer_options, &mut out)?;
+            }
+        }
+        out.flush()
+    };
+    write().map_err(|e| (path.clone(), e.to_string()))?;
+    Ok(path)
+}
+
+/// Rewrite the URLs of figures in `figures_dir` (`man/figures`) in a README to
+/// `<prefix><file>`
+///
+/// READMEs refer to figures relative to the package directory, in Markdown
+/// (`![](man/figures/logo.png)`) or HTML (`<img src="man/figures/logo.png">`).
+/// Found figures are collected in `figures`; other relative URLs are kept.
+fn rewrite_readme_figures(
+    readme: &str,
+    figures_dir: &Path,
+    prefix: &str,
+    figures: &mut BTreeSet<String>,
+) -> String {
+    const FIGURES: &str = "man/figures/";
+    let mut output = String::with_capacity(readme.len());
+    let mut rest = readme;
+    while let Some(pos) = rest.find(FIGURES) {
+        let (before, after) = rest.split_at(pos);
+        let end = after[FIGURES.len()..]
+            .find(|c: char| matches!(c, ')' | '"' | '\'' | '>') || c.is_whitespace())
+            .map_or(after.len(), |i| FIGURES.len() + i);
+        let file = &after[FIGURES.len()..end];
+
+        // Only URLs of links, images and HTML attributes, optionally with `./`
+        let url_start = before.strip_suffix("./").unwrap_or(before);
+        let is_url = ["](", "src=\"", "src='", "href=\"", "href='"]
+            .iter()
+            .any(|p| url_start.ends_with(p));
+        if is_url && !file.is_empty() && figures_dir.join(file).is_file() {
+            output.push_str(url_start);
+            output.push_str(prefix);
+            output.push_str(file);
+            figures.insert(file.to_string());
+        } else {
+            output.push_str(before);
+            output.push_str(&after[..end]);
+        }
+        rest = &after[end..];
+    }
+    output.push_str(rest);
+    output
+}
 
-        // Convert to QMD string
-        let qmd = mdast_to_qmd(&mdast, &writer_options);
+/// Convert a single Rd file
+fn convert_single_file(
+    topic: &RdTopic,
+    package: &RdPackage,
+    options: &PackageConvertOptions,
+    link_resolver: &Arc<dyn LinkResolver>,
+) -> ConvertOutcome {
+    let input = topic.path.as_path();
+    let convert = || -> std::result::Result<(PathBuf, ConvertedFile), ConvertError> {
+        let converted = convert_file(topic, package, options, link_resolver)?;
 
         // Determine output path
         let relative = input.strip_prefix(&package.root).unwrap_or(input);
@@ -477,13 +1080,20 @@ fn convert_single_file(
         }
 
         // Write output
-        fs::write(&output_path, qmd).map_err(|e| ConvertError::Failed(e.to_string()))?;
+        converted
+            .write_file(&options.format, &output_path)
+            .map_err(|e| ConvertError::Failed(e.to_string()))?;
 
-        Ok(output_path)
+        Ok((output_path, converted))
     };
 
     match convert() {
-        Ok(path) => ConvertOutcome::Success(path),
+        Ok((path, converted)) => ConvertOutcome::Success(
+            input.to_path_buf(),
+            path,
+            converted.diagnostics,
+            converted.figures,
+        ),
         Err(ConvertError::SkipInternal) => ConvertOutcome::SkippedInternal(input.to_path_buf()),
         Err(ConvertError::Failed(msg)) => ConvertOutcome::Failed(input.to_path_buf(), msg),
     }
@@ -511,25 +1121,17 @@ fn collect_rd_files(dir: &Path, recursive: bool) -> Result<Vec<PathBuf>> {
     Ok(files)
 }
 
-/// Build an alias index from a list of Rd files
+/// Build an alias index from parsed Rd files
 ///
-/// Returns a HashMap mapping alias names to Rd file basenames (without extension)
-fn build_alias_index(files: &[PathBuf]) -> Result<HashMap<String, String>> {
+/// Returns a HashMap mapping alias names to Rd file basenames (without extension),
+/// and the link titles of the files by basename
+fn build_alias_index(topics: &[RdTopic]) -> (HashMap<String, String>, HashMap<String, String>) {
     let mut index = HashMap::new();
+    let mut titles = HashMap::new();
 
-    for file in files {
-        let basename = file
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_string();
-
-        // Parse the file to extract aliases
-        let content = fs::read_to_string(file)?;
-        let doc = parse(&content).map_err(|e| PackageError::Parse {
-            file: file.clone(),
-            message: e.to_string(),
-        })?;
+    for topic in topics {
+        let basename = topic_basename(&topic.path).to_string();
+        let doc = &topic.document;
 
         // Extract all \alias{} sections
         let alias_sections = doc.get_sections(&SectionTag::Alias);
@@ -547,9 +1149,13 @@ fn build_alias_index(files: &[PathBuf]) -> Result<HashMap<String, String>> {
                 index.insert(name, basename.clone());
             }
         }
+
+        if let Some(title) = topic_link_title(doc) {
+            titles.insert(basename, title);
+        }
     }
 
-    Ok(index)
+    (index, titles)
 }
 
 // ============================================================================
@@ -732,11 +1338,15 @@ mod tests {
         let rd_path = dir.path().join("my_func.Rd");
         fs::write(&rd_path, rd_content).unwrap();
 
-        let files = vec![rd_path];
-        let index = build_alias_index(&files).unwrap();
+        let topics = vec![RdTopic::load(&rd_path).unwrap()];
+        let (index, titles) = build_alias_index(&topics);
 
         assert_eq!(index.get("my_func"), Some(&"my_func".to_string()));
         assert_eq!(index.get("my_func_alias"), Some(&"my_func".to_string()));
+        assert_eq!(
+            titles.get("my_func"),
+            Some(&"My Function: A test function".to_string())
+        );
     }
 
     #[test]
@@ -765,6 +1375,27 @@ mod tests {
         assert_eq!(package.resolve_alias("nonexistent"), None);
     }
 
+    #[test]
+    fn test_rd_package_topics() {
+        let dir = tempdir().unwrap();
+        let rd = "% Generated by roxygen2: do not edit by hand\n\
+                  % Please edit documentation in R/a.R\n\
+                  \\name{a}\n\\alias{a}\n\\title{A}\n";
+        fs::write(dir.path().join("a.Rd"), rd).unwrap();
+
+        let package = RdPackage::from_directory(dir.path(), false).unwrap();
+        let [topic] = package.topics() else {
+            panic!("expected one topic");
+        };
+        assert_eq!(topic.path(), dir.path().join("a.Rd"));
+        assert!(topic.document().get_section(&SectionTag::Title).is_some());
+        assert_eq!(topic.source_files(), ["R/a.R"]);
+
+        // Clones share the parsed documents
+        let clone = package.clone();
+        assert!(Arc::ptr_eq(&clone.topics[0].document, &topic.document));
+    }
+
     #[test]
     fn test_generate_topic_index() {
         let dir = tempdir().unwrap();
@@ -863,6 +1494,35 @@ An old deprecated function.
         assert!(manual_topic.metadata.source_files.is_empty());
     }
 
+    #[test]
+    fn test_topic_index_sidebar_category() {
+        let topic = |name: &str, file: &str| TopicInfo {
+            name: name.to_string(),
+            file: file.to_string(),
+            title: String::new(),
+            metadata: RdMetadata::default(),
+        };
+        let index = TopicIndex {
+            topics: vec![topic("foo", "foo.mdx"), topic("bar.baz", "bar-baz.mdx")],
+        };
+
+        let json = index
+            .to_sidebar_category("Reference", "reference/")
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed,
+            serde_json::json!({
+                "type": "category",
+                "label": "Reference",
+                "items": [
+                    {"type": "doc", "id": "reference/foo", "label": "foo"},
+                    {"type": "doc", "id": "reference/bar-baz", "label": "bar.baz"}
+                ]
+            })
+        );
+    }
+
     #[test]
     fn test_topic_index_json_serialization() {
         let index = TopicIndex {
@@ -948,9 +1608,25 @@ An old deprecated function.
             parallel_jobs: Some(1),
             unresolved_link_url: None,
             external_package_urls: None,
+            link_resolver: None,
+            link_titles: true,
             exec_dontrun: false,
             exec_donttest: true,
+            chunk_labels: false,
+            chunk_options: ChunkOptions::default(),
             include_internal: false,
+            format: WriterFormat::Markdown,
+            callouts: true,
+            translate_html: false,
+            heading_ids: false,
+            math: MathFormat::Latex,
+            typography: Typography::Unicode,
+            tabular_format: TabularFormat::Auto,
+            table_header: TableHeader::FirstRow,
+            value_items: ItemsFormat::DefinitionList,
+            format_items: ItemsFormat::DefinitionList,
+            assets_dir: None,
+            home_page: false,
         };
 
         let result = PackageConverter::new(&package, options).convert().unwrap();
@@ -973,6 +1649,217 @@ An old deprecated function.
         assert!(result.fallbacks.is_empty());
     }
 
+    #[test]
+    fn test_package_converter_copies_figures() {
+        let dir = tempdir().unwrap();
+        let out_dir = tempdir().unwrap();
+
+        fs::create_dir_all(dir.path().join("figures")).unwrap();
+        fs::create_dir_all(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("figures/logo.png"), "png").unwrap();
+        fs::write(
+            dir.path().join("alpha.Rd"),
+            r#"\name{alpha}
+\alias{alpha}
+\title{Alpha}
+\description{\figure{logo.png}{options: width=100 alt="Logo"} \figure{missing.png}}
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("sub/beta.Rd"),
+            "\\name{beta}\n\\alias{beta}\n\\title{Beta}\n\\description{\\figure{logo.png}{Logo}}\n",
+        )
+        .unwrap();
+
+        let package = RdPackage::from_directory(dir.path(), true).unwrap();
+        let options = PackageConvertOptions {
+            output_dir: out_dir.path().to_path_buf(),
+            parallel_jobs: Some(1),
+            ..Default::default()
+        };
+        let result = PackageConverter::new(&package, options).convert().unwrap();
+
+        // Copied once, though referenced twice
+        assert_eq!(
+            result.conversion.asset_files,
+            vec![out_dir.path().join("assets/logo.png")]
+        );
+        assert!(out_dir.path().join("assets/logo.png").exists());
+
+        // URLs are relative to each output file
+        let alpha = fs::read_to_string(out_dir.path().join("alpha.qmd")).unwrap();
+        assert!(alpha.contains(r#"![Logo](assets/logo.png){width="100"}"#));
+        let beta = fs::read_to_string(out_dir.path().join("sub/beta.qmd")).unwrap();
+        assert!(beta.contains("![Logo](../assets/logo.png)"));
+
+        // Missing figures keep their URL and are reported
+        assert!(alpha.contains("![missing.png](missing.png)"));
+        assert_eq!(result.conversion.warnings.len(), 1);
+        assert!(result.conversion.warnings[0].1.contains("missing.png"));
+    }
+
+    #[test]
+    fn test_package_converter_home_page() {
+        let dir = tempdir().unwrap();
+        let out_dir = tempdir().unwrap();
+
+        let man_dir = dir.path().join("man");
+        fs::create_dir_all(&man_dir).unwrap();
+        fs::write(
+            man_dir.join("alpha.Rd"),
+            "\\name{alpha}\n\\alias{alpha}\n\\title{Alpha}\n\\description{Alpha.}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("DESCRIPTION"),
+            r#"Package: mypkg
+Title: Tools for Things
+Version: 1.2.0
+Authors@R: person("Ann", "Smith", role = c("aut", "cre"),
+    comment = c(ORCID = "0000-0001-2345-6789"))
+Description: Does things
+    with tools.
+License: MIT + file LICENSE
+URL: https://example.org/mypkg
+BugReports: https://github.com/ann/mypkg/issues
+"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("README.md"), "# mypkg\n\nREADME body.\n").unwrap();
+
+        let package = RdPackage::from_directory(&man_dir, false).unwrap();
+        assert_eq!(package.package_dir(), dir.path());
+        let options = PackageConvertOptions {
+            output_dir: out_dir.path().to_path_buf(),
+            home_page: true,
+            ..Default::default()
+        };
+        let result = convert_package(&package, &options).unwrap();
+        let index_path = out_dir.path().join("index.qmd");
+        assert_eq!(result.home_page, Some(index_path.clone()));
+        assert!(result.warnings.is_empty());
+
+        let index = fs::read_to_string(index_path).unwrap();
+        assert!(index.starts_with("---\ntitle: \"Tools for Things\"\n"));
+        assert!(index.contains("Does things with tools.\n\n# mypkg\n\nREADME body.\n\n## Version"));
+        assert!(index.contains("- [https://example.org/mypkg](https://example.org/mypkg)"));
+        assert!(index.contains("Report a bug: [https://github.com/ann/mypkg/issues]"));
+        assert!(index.contains("MIT + file LICENSE"));
+        assert!(index.contains(
+            "- Ann Smith (author, maintainer) [ORCID](https://orcid.org/0000-0001-2345-6789)"
+        ));
+
+        // Without DESCRIPTION, the conversion succeeds with a warning
+        fs::remove_file(dir.path().join("DESCRIPTION")).unwrap();
+        let result = convert_package(&package, &options).unwrap();
+        assert_eq!(result.home_page, None);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].1.starts_with("home page not written"));
+    }
+
+    #[test]
+    fn test_package_converter_home_page_readme() {
+        let dir = tempdir().unwrap();
+        let out_dir = tempdir().unwrap();
+
+        let man_dir = dir.path().join("man");
+        fs::create_dir_all(man_dir.join("figures")).unwrap();
+        fs::write(man_dir.join("figures").join("logo.png"), b"png").unwrap();
+        fs::write(
+            man_dir.join("alpha.Rd"),
+            "\\name{alpha}\n\\alias{alpha}\n\\title{Alpha}\n\\description{Alpha.}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("DESCRIPTION"),
+            "Package: mypkg\nTitle: Tools\nVersion: 1.0.0\nAuthor: Ann\nMaintainer: Ann <ann@example.org>\nDescription: Does things.\nLicense: MIT\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("README.md"),
+            r#"# mypkg <img src="man/figures/logo.png" align="right" />
+
+<!-- badges: start -->
+![Logo](./man/figures/logo.png) ![Missing](man/figures/missing.png)
+See [the vignette](vignettes/intro.Rmd).
+"#,
+        )
+        .unwrap();
+
+        let package = RdPackage::from_directory(&man_dir, false).unwrap();
+        let options = PackageConvertOptions {
+            output_dir: out_dir.path().to_path_buf(),
+            home_page: true,
+            ..Default::default()
+        };
+        let result = convert_package(&package, &options).unwrap();
+        assert_eq!(
+            result.asset_files,
+            vec![out_dir.path().join("assets").join("logo.png")]
+        );
+
+        // Figures point at the copied assets; other relative URLs are kept
+        let index = fs::read_to_string(out_dir.path().join("index.qmd")).unwrap();
+        assert!(index.contains(r#"# mypkg <img src="assets/logo.png" align="right" />"#));
+        assert!(index.contains("![Logo](assets/logo.png) ![Missing](man/figures/missing.png)"));
+        assert!(index.contains("See [the vignette](vignettes/intro.Rmd)."));
+
+        // MDX leaves the README out
+        let mdx_dir = tempdir().unwrap();
+        let options = PackageConvertOptions {
+            output_dir: mdx_dir.path().to_path_buf(),
+            format: WriterFormat::Mdx,
+            output_extension: "mdx".to_string(),
+            home_page: true,
+            ..Default::default()
+        };
+        let result = convert_package(&package, &options).unwrap();
+        let index = fs::read_to_string(result.home_page.unwrap()).unwrap();
+        assert!(index.contains("Does things."));
+        assert!(!index.contains("badges"));
+    }
+
+    #[test]
+    fn test_package_converter_home_page_topic_named_index() {
+        let dir = tempdir().unwrap();
+        let out_dir = tempdir().unwrap();
+
+        let man_dir = dir.path().join("man");
+        fs::create_dir_all(&man_dir).unwrap();
+        fs::write(
+            man_dir.join("index.Rd"),
+            "\\name{index}\n\\alias{index}\n\\title{Index}\n\\description{Index topic.}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("DESCRIPTION"),
+            "Package: mypkg\nTitle: Tools\nVersion: 1.0.0\nAuthor: Ann\nMaintainer: Ann <ann@example.org>\nDescription: Does things.\nLicense: MIT\n",
+        )
+        .unwrap();
+
+        let package = RdPackage::from_directory(&man_dir, false).unwrap();
+        let options = PackageConvertOptions {
+            output_dir: out_dir.path().to_path_buf(),
+            home_page: true,
+            ..Default::default()
+        };
+        let result = convert_package(&package, &options).unwrap();
+
+        // The topic page is kept and the home page is skipped with a warning
+        let index_path = out_dir.path().join("index.qmd");
+        assert_eq!(result.output_files, vec![index_path.clone()]);
+        assert_eq!(result.home_page, None);
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].0, index_path);
+        assert!(result.warnings[0].1.starts_with("home page not written"));
+        assert!(
+            fs::read_to_string(index_path)
+                .unwrap()
+                .contains("Index topic.")
+        );
+    }
+
     #[test]
     fn test_package_converter_with_alias_resolution() {
         let dir = tempdir().unwrap();
@@ -1003,21 +1890,118 @@ An old deprecated function.
             parallel_jobs: Some(1),
             unresolved_link_url: None,
             external_package_urls: None,
+            link_resolver: None,
+            link_titles: true,
             exec_dontrun: false,
             exec_donttest: true,
+            chunk_labels: false,
+            chunk_options: ChunkOptions::default(),
             include_internal: false,
+            format: WriterFormat::Markdown,
+            callouts: true,
+            translate_html: false,
+            heading_ids: false,
+            math: MathFormat::Latex,
+            typography: Typography::Unicode,
+            tabular_format: TabularFormat::Auto,
+            table_header: TableHeader::FirstRow,
+            value_items: ItemsFormat::DefinitionList,
+            format_items: ItemsFormat::DefinitionList,
+            assets_dir: None,
+            home_page: false,
         };
 
         let result = PackageConverter::new(&package, options).convert().unwrap();
         assert_eq!(result.conversion.success_count, 2);
 
-        // Check alias resolution works (links use [`text`](url) format)
+        // Check alias resolution works (links use [`text`](url "title") format)
         let main_content = fs::read_to_string(out_dir.path().join("main_func.qmd")).unwrap();
-        assert!(main_content.contains("[`helper_func`](helper_func.qmd)"));
+        assert!(
+            main_content.contains(
+                r#"[`helper_func`](helper_func.qmd "Helper Function: A helper for mf.")"#
+            )
+        );
 
         let helper_content = fs::read_to_string(out_dir.path().join("helper_func.qmd")).unwrap();
         // "mf" alias should resolve to main_func
-        assert!(helper_content.contains("[`mf`](main_func.qmd)"));
+        assert!(
+            helper_content
+                .contains(r#"[`mf`](main_func.qmd "Main Function: See helper_func for details.")"#)
+        );
+    }
+
+    #[test]
+    fn test_package_converter_without_link_titles() {
+        let dir = tempdir().unwrap();
+        let out_dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("a.Rd"),
+            "\\name{a}\n\\title{A}\n\\description{See \\link{b}.}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("b.Rd"),
+            "\\name{b}\n\\title{B}\n\\description{Topic b.}\n",
+        )
+        .unwrap();
+
+        let package = RdPackage::from_directory(dir.path(), false).unwrap();
+        assert_eq!(
+            package.link_titles().get("b"),
+            Some(&"B: Topic b.".to_string())
+        );
+
+        let options = PackageConvertOptions {
+            output_dir: out_dir.path().to_path_buf(),
+            link_titles: false,
+            ..Default::default()
+        };
+        PackageConverter::new(&package, options).convert().unwrap();
+        let content = fs::read_to_string(out_dir.path().join("a.qmd")).unwrap();
+        assert!(content.contains("[`b`](b.qmd)"));
+    }
+
+    #[test]
+    fn test_package_to_latex() {
+        let dir = tempdir().unwrap();
+
+        let rd_main = r#"\name{main_func}
+\alias{main_func}
+\title{Main Function}
+\description{See \link{helper_func} for details.}
+"#;
+        let rd_helper = r#"\name{helper_func}
+\alias{helper_func}
+\title{Helper Function}
+\description{A helper.}
+"#;
+        let rd_internal = r#"\name{internal_func}
+\alias{internal_func}
+\title{Internal Function}
+\keyword{internal}
+"#;
+        fs::write(dir.path().join("main_func.Rd"), rd_main).unwrap();
+        fs::write(dir.path().join("helper_func.Rd"), rd_helper).unwrap();
+        fs::write(dir.path().join("internal_func.Rd"), rd_internal).unwrap();
+
+        let package = RdPackage::from_directory(dir.path(), false).unwrap();
+        let manual = package_to_latex(&package, &PackageConvertOptions::default(), Some("mypkg"));
+
+        assert_eq!(manual.topic_count, 2);
+        assert_eq!(manual.skipped_internal.len(), 1);
+        assert!(manual.failed_files.is_empty());
+
+        let doc = &manual.document;
+        assert!(doc.starts_with("\\documentclass{article}\n"));
+        assert!(doc.contains("\\title{mypkg}\n"));
+        assert!(doc.contains("\\maketitle\n\\tableofcontents\n"));
+        // Topics are sorted by file name and anchored by labels
+        let helper = doc.find("\\label{topic:helper_func}").unwrap();
+        let main = doc.find("\\label{topic:main_func}").unwrap();
+        assert!(helper < main);
+        // Links between topics become hyperref references
+        assert!(doc.contains("\\hyperref[topic:helper_func]{\\texttt{helper\\_func}}"));
+        assert!(doc.ends_with("\\end{document}\n"));
     }
 
     #[test]
@@ -1045,9 +2029,25 @@ x <- 1
             parallel_jobs: Some(1),
             unresolved_link_url: None,
             external_package_urls: None,
+            link_resolver: None,
+            link_titles: true,
             exec_dontrun: false,
             exec_donttest: true,
+            chunk_labels: false,
+            chunk_options: ChunkOptions::default(),
             include_internal: false,
+            format: WriterFormat::Markdown,
+            callouts: true,
+            translate_html: false,
+            heading_ids: false,
+            math: MathFormat::Latex,
+            typography: Typography::Unicode,
+            tabular_format: TabularFormat::Auto,
+            table_header: TableHeader::FirstRow,
+            value_items: ItemsFormat::DefinitionList,
+            format_items: ItemsFormat::DefinitionList,
+            assets_dir: None,
+            home_page: false,
         };
 
         let result = PackageConverter::new(&package, options).convert().unwrap();
@@ -1112,9 +2112,25 @@ x <- 1
             parallel_jobs: Some(1),
             unresolved_link_url: Some("https://rdrr.io/r/base/{topic}.html".to_string()),
             external_package_urls: None,
+            link_resolver: None,
+            link_titles: true,
             exec_dontrun: false,
             exec_donttest: true,
+            chunk_labels: false,
+            chunk_options: ChunkOptions::default(),
             include_internal: false,
+            format: WriterFormat::Markdown,
+            callouts: true,
+            translate_html: false,
+            heading_ids: false,
+            math: MathFormat::Latex,
+            typography: Typography::Unicode,
+            tabular_format: TabularFormat::Auto,
+            table_header: TableHeader::FirstRow,
+            value_items: ItemsFormat::DefinitionList,
+            format_items: ItemsFormat::DefinitionList,
+            assets_dir: None,
+            home_page: false,
         };
 
         let result = PackageConverter::new(&package, options).convert().unwrap();
@@ -1159,9 +2175,25 @@ x <- 1
             parallel_jobs: Some(1),
             unresolved_link_url: None,
             external_package_urls: Some(external_urls),
+            link_resolver: None,
+            link_titles: true,
             exec_dontrun: false,
             exec_donttest: true,
+            chunk_labels: false,
+            chunk_options: ChunkOptions::default(),
             include_internal: false,
+            format: WriterFormat::Markdown,
+            callouts: true,
+            translate_html: false,
+            heading_ids: false,
+            math: MathFormat::Latex,
+            typography: Typography::Unicode,
+            tabular_format: TabularFormat::Auto,
+            table_header: TableHeader::FirstRow,
+            value_items: ItemsFormat::DefinitionList,
+            format_items: ItemsFormat::DefinitionList,
+            assets_dir: None,
+            home_page: false,
         };
 
         let result = PackageConverter::new(&package, options).convert().unwrap();
@@ -1197,9 +2229,25 @@ x <- 1
             parallel_jobs: Some(1),
             unresolved_link_url: None,
             external_package_urls: None,
+            link_resolver: None,
+            link_titles: true,
             exec_dontrun: false,
             exec_donttest: true,
+            chunk_labels: false,
+            chunk_options: ChunkOptions::default(),
             include_internal: false,
+            format: WriterFormat::Markdown,
+            callouts: true,
+            translate_html: false,
+            heading_ids: false,
+            math: MathFormat::Latex,
+            typography: Typography::Unicode,
+            tabular_format: TabularFormat::Auto,
+            table_header: TableHeader::FirstRow,
+            value_items: ItemsFormat::DefinitionList,
+            format_items: ItemsFormat::DefinitionList,
+            assets_dir: None,
+            home_page: false,
         };
 
         let result = PackageConverter::new(&package, options).convert().unwrap();
@@ -1231,9 +2279,25 @@ x <- 1
             parallel_jobs: Some(1),
             unresolved_link_url: None,
             external_package_urls: None,
+            link_resolver: None,
+            link_titles: true,
             exec_dontrun: false,
             exec_donttest: true,
+            chunk_labels: false,
+            chunk_options: ChunkOptions::default(),
             include_internal: false,
+            format: WriterFormat::Markdown,
+            callouts: true,
+            translate_html: false,
+            heading_ids: false,
+            math: MathFormat::Latex,
+            typography: Typography::Unicode,
+            tabular_format: TabularFormat::Auto,
+            table_header: TableHeader::FirstRow,
+            value_items: ItemsFormat::DefinitionList,
+            format_items: ItemsFormat::DefinitionList,
+            assets_dir: None,
+            home_page: false,
         };
 
         let result = PackageConverter::new(&package, options).convert().unwrap();
@@ -1283,9 +2347,25 @@ x <- 1
             parallel_jobs: Some(1),
             unresolved_link_url: None,
             external_package_urls: None,
+            link_resolver: None,
+            link_titles: true,
             exec_dontrun: false,
             exec_donttest: true,
+            chunk_labels: false,
+            chunk_options: ChunkOptions::default(),
             include_internal: false, // Default: skip internal
+            format: WriterFormat::Markdown,
+            callouts: true,
+            translate_html: false,
+            heading_ids: false,
+            math: MathFormat::Latex,
+            typography: Typography::Unicode,
+            tabular_format: TabularFormat::Auto,
+            table_header: TableHeader::FirstRow,
+            value_items: ItemsFormat::DefinitionList,
+            format_items: ItemsFormat::DefinitionList,
+            assets_dir: None,
+            home_page: false,
         };
 
         let result = PackageConverter::new(&package, options).convert().unwrap();
@@ -1338,9 +2418,25 @@ x <- 1
             parallel_jobs: Some(1),
             unresolved_link_url: None,
             external_package_urls: None,
+            link_resolver: None,
+            link_titles: true,
             exec_dontrun: false,
             exec_donttest: true,
+            chunk_labels: false,
+            chunk_options: ChunkOptions::default(),
             include_internal: true, // Include internal topics
+            format: WriterFormat::Markdown,
+            callouts: true,
+            translate_html: false,
+            heading_ids: false,
+            math: MathFormat::Latex,
+            typography: Typography::Unicode,
+            tabular_format: TabularFormat::Auto,
+            table_header: TableHeader::FirstRow,
+            value_items: ItemsFormat::DefinitionList,
+            format_items: ItemsFormat::DefinitionList,
+            assets_dir: None,
+            home_page: false,
         };
 
         let result = PackageConverter::new(&package, options).convert().unwrap();
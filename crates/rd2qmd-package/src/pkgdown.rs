@@ -0,0 +1,516 @@
+//! Reference index grouped by the `reference` sections of `_pkgdown.yml`
+//!
+//! Topics are selected the way pkgdown selects them: by name or alias, or with
+//! `starts_with()`, `ends_with()`, `contains()`, `matches()`, `has_keyword()`,
+//! `has_concept()`, `lacks_concepts()` and `has_lifecycle()` (also `lifecycle()`).
+//! A leading `-` removes topics selected earlier in the section. Topics without
+//! `\keyword{internal}` that no section selects are reported as missing.
+//!
+//! See <https://pkgdown.r-lib.org/reference/build_reference.html>.
+
+use rd2qmd_core::{Node, Root, make_slug};
+use regex::Regex;
+use saphyr::{LoadableYamlNode, Yaml};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::r_call::{RValue, parse_r_strict};
+use crate::{PackageError, Result, TopicIndex, TopicInfo};
+
+/// Locations of the pkgdown configuration in a package directory, in the order
+/// pkgdown looks for it
+const PKGDOWN_CONFIG_FILES: [&str; 4] = [
+    "_pkgdown.yml",
+    "_pkgdown.yaml",
+    "pkgdown/_pkgdown.yml",
+    "pkgdown/_pkgdown.yaml",
+];
+
+/// The `reference` field of a `_pkgdown.yml` file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PkgdownReference {
+    /// Sections in the order of the file
+    pub sections: Vec<ReferenceSectionSpec>,
+}
+
+/// A section of the `reference` field
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReferenceSectionSpec {
+    /// Section title
+    pub title: Option<String>,
+    /// Subsection title
+    pub subtitle: Option<String>,
+    /// Section description
+    pub desc: Option<String>,
+    /// Topic selectors, such as `foo` or `starts_with("geom_")`
+    pub contents: Vec<String>,
+}
+
+impl PkgdownReference {
+    /// Find the pkgdown configuration of a package directory
+    pub fn find(package_dir: &Path) -> Option<PathBuf> {
+        PKGDOWN_CONFIG_FILES
+            .iter()
+            .map(|file| package_dir.join(file))
+            .find(|path| path.is_file())
+    }
+
+    /// Read the `reference` field of a pkgdown configuration file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content).map_err(|message| PackageError::Parse {
+            file: path.to_path_buf(),
+            message,
+        })
+    }
+
+    /// Parse the `reference` field of pkgdown configuration YAML
+    ///
+    /// A file without `reference` has no sections.
+    pub fn parse(content: &str) -> std::result::Result<Self, String> {
+        let docs = Yaml::load_from_str(content).map_err(|e| e.to_string())?;
+        let Some(reference) = docs.first().and_then(|doc| doc.as_mapping_get("reference")) else {
+            return Ok(Self::default());
+        };
+        let Some(entries) = reference.as_vec() else {
+            return Err("`reference` must be a list of sections".to_string());
+        };
+
+        let field = |entry: &Yaml, key: &str| {
+            entry
+                .as_mapping_get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+        };
+        let sections = entries
+            .iter()
+            .map(|entry| ReferenceSectionSpec {
+                title: field(entry, "title"),
+                subtitle: field(entry, "subtitle"),
+                desc: field(entry, "desc"),
+                contents: entry
+                    .as_mapping_get("contents")
+                    .and_then(|c| c.as_vec())
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(yaml_scalar_string)
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default(),
+            })
+            .collect();
+        Ok(Self { sections })
+    }
+}
+
+/// A scalar as a string, so that topic names such as `1` or `TRUE` are kept
+fn yaml_scalar_string(node: &Yaml) -> Option<String> {
+    if let Some(s) = node.as_str() {
+        return Some(s.to_string());
+    }
+    node.as_integer().map(|i| i.to_string()).or_else(|| {
+        node.as_bool()
+            .map(|b| if b { "TRUE" } else { "FALSE" }.to_string())
+    })
+}
+
+/// Topics of a package grouped into reference sections
+#[derive(Debug, Clone)]
+pub struct ReferenceIndex {
+    /// Sections with their selected topics
+    pub sections: Vec<ReferenceSection>,
+    /// Names of the topics without `\keyword{internal}` that no section selects
+    pub missing_topics: Vec<String>,
+    /// Selectors that could not be used, with the reason
+    pub problems: Vec<String>,
+}
+
+/// A reference section with its topics
+#[derive(Debug, Clone)]
+pub struct ReferenceSection {
+    /// Section title
+    pub title: Option<String>,
+    /// Subsection title
+    pub subtitle: Option<String>,
+    /// Section description
+    pub desc: Option<String>,
+    /// Selected topics, in the order of selection
+    pub topics: Vec<TopicInfo>,
+}
+
+impl ReferenceIndex {
+    /// Group the topics of an index by the sections of a pkgdown reference
+    ///
+    /// Without sections, all topics except internal ones form a single
+    /// untitled section, as in pkgdown.
+    pub fn new(index: &TopicIndex, reference: &PkgdownReference) -> Self {
+        let topics = &index.topics;
+        if reference.sections.is_empty() {
+            return Self {
+                sections: vec![ReferenceSection {
+                    title: None,
+                    subtitle: None,
+                    desc: None,
+                    topics: topics.iter().filter(|t| !is_internal(t)).cloned().collect(),
+                }],
+                missing_topics: Vec::new(),
+                problems: Vec::new(),
+            };
+        }
+
+        let mut problems = Vec::new();
+        let mut covered = vec![false; topics.len()];
+        let sections = reference
+            .sections
+            .iter()
+            .map(|spec| {
+                let selected = select_topics(topics, &spec.contents, &mut problems);
+                for &i in &selected {
+                    covered[i] = true;
+                }
+                ReferenceSection {
+                    title: spec.title.clone(),
+                    subtitle: spec.subtitle.clone(),
+                    desc: spec.desc.clone(),
+                    topics: selected.into_iter().map(|i| topics[i].clone()).collect(),
+                }
+            })
+            .collect();
+
+        let missing_topics = topics
+            .iter()
+            .zip(&covered)
+            .filter(|(topic, covered)| !**covered && !is_internal(topic))
+            .map(|(topic, _)| topic.name.clone())
+            .collect();
+
+        Self {
+            sections,
+            missing_topics,
+            problems,
+        }
+    }
+
+    /// Build the reference index page
+    ///
+    /// Section titles become level 2 headings and subtitles level 3 headings,
+    /// with pkgdown anchors when `heading_ids` is set. Each topic is a definition
+    /// list term with its aliases, linked to `<link_prefix><file>`, and its title.
+    pub fn to_mdast(&self, link_prefix: &str, heading_ids: bool) -> Root {
+        let heading = |depth: u8, title: &str| {
+            let children = vec![Node::text(title)];
+            if heading_ids {
+                Node::heading_with_id(depth, children, make_slug(title))
+            } else {
+                Node::heading(depth, children)
+            }
+        };
+
+        let mut children = Vec::new();
+        for section in &self.sections {
+            if let Some(title) = &section.title {
+                children.push(heading(2, title));
+            }
+            if let Some(subtitle) = &section.subtitle {
+                children.push(heading(3, subtitle));
+            }
+            if let Some(desc) = &section.desc {
+                children.push(Node::paragraph(vec![Node::text(desc)]));
+            }
+            if section.topics.is_empty() {
+                continue;
+            }
+            let items = section.topics.iter().flat_map(|topic| {
+                let url = format!("{}{}", link_prefix, topic.file);
+                let names = if topic.metadata.aliases.is_empty() {
+                    std::slice::from_ref(&topic.name)
+                } else {
+                    &topic.metadata.aliases[..]
+                };
+                let mut term = Vec::new();
+                for (i, name) in names.iter().enumerate() {
+                    if i > 0 {
+                        term.push(Node::text(" "));
+                    }
+                    term.push(Node::link(url.clone(), vec![Node::inline_code(name)]));
+                }
+                [
+                    Node::definition_term(term),
+                    Node::definition_description(vec![Node::paragraph(vec![Node::text(
+                        &topic.title,
+                    )])]),
+                ]
+            });
+            children.push(Node::definition_list(items.collect()));
+        }
+        Root::new(children)
+    }
+}
+
+/// Whether a topic has `\keyword{internal}`
+fn is_internal(topic: &TopicInfo) -> bool {
+    topic.metadata.keywords.iter().any(|k| k == "internal")
+}
+
+/// Select topics with the `contents` of a section, in the order of selection
+fn select_topics(
+    topics: &[TopicInfo],
+    contents: &[String],
+    problems: &mut Vec<String>,
+) -> Vec<usize> {
+    let mut selected: Vec<usize> = Vec::new();
+    for (i, selector) in contents.iter().enumerate() {
+        let selector = selector.trim();
+        // `-foo` removes topics, unless a topic is called `-foo`
+        let (exclude, expr) = match selector.strip_prefix('-') {
+            Some(rest) if find_topic(topics, selector).is_none() => (true, rest.trim()),
+            _ => (false, selector),
+        };
+        if exclude && i == 0 {
+            // Exclusion first: start from all topics
+            selected = (0..topics.len())
+                .filter(|&t| !is_internal(&topics[t]))
+                .collect();
+        }
+        match match_selector(topics, expr) {
+            Ok(matched) if exclude => selected.retain(|t| !matched.contains(t)),
+            Ok(matched) => {
+                for t in matched {
+                    if !selected.contains(&t) {
+                        selected.push(t);
+                    }
+                }
+            }
+            Err(reason) => problems.push(format!("`{}` {}", selector, reason)),
+        }
+    }
+    selected
+}
+
+/// Find a topic by name, or else by alias
+fn find_topic(topics: &[TopicInfo], name: &str) -> Option<usize> {
+    topics.iter().position(|t| t.name == name).or_else(|| {
+        topics
+            .iter()
+            .position(|t| t.metadata.aliases.iter().any(|a| a == name))
+    })
+}
+
+/// Topics matched by a selector: a topic name or alias, or a selector call
+fn match_selector(topics: &[TopicInfo], expr: &str) -> std::result::Result<Vec<usize>, String> {
+    let name = expr.trim_matches('`');
+    if let Some(t) = find_topic(topics, name) {
+        return Ok(vec![t]);
+    }
+
+    let RValue::Call(function, args) = parse_r_strict(expr)? else {
+        return Err("is not a known topic name or alias".to_string());
+    };
+    let values: Vec<String> = args
+        .iter()
+        .filter(|(key, _)| key.is_none())
+        .flat_map(|(_, value)| value.strings())
+        .map(|(_, s)| s)
+        .collect();
+    let internal = args.iter().any(|(key, value)| {
+        key.as_deref() == Some("internal")
+            && matches!(value, RValue::Name(n) if n == "TRUE" || n == "T")
+    });
+    if values.is_empty() {
+        return Err(format!("needs a string argument for `{}()`", function));
+    }
+
+    let any_alias = |topic: &TopicInfo, test: &dyn Fn(&str) -> bool| {
+        (internal || !is_internal(topic)) && topic.metadata.aliases.iter().any(|a| test(a))
+    };
+    let has_concept =
+        |topic: &TopicInfo, x: &str| topic.metadata.concepts.iter().any(|c| c.trim() == x);
+    let select = |test: &dyn Fn(&TopicInfo) -> bool| -> Vec<usize> {
+        (0..topics.len()).filter(|&t| test(&topics[t])).collect()
+    };
+
+    let matched = match function.as_str() {
+        "starts_with" => {
+            select(&|t| any_alias(t, &|a| values.iter().any(|x| a.starts_with(x.as_str()))))
+        }
+        "ends_with" => {
+            select(&|t| any_alias(t, &|a| values.iter().any(|x| a.ends_with(x.as_str()))))
+        }
+        "contains" => select(&|t| any_alias(t, &|a| values.iter().any(|x| a.contains(x.as_str())))),
+        "matches" => {
+            let patterns = values
+                .iter()
+                .map(|x| Regex::new(x))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| format!("has an invalid regular expression: {}", e))?;
+            select(&|t| any_alias(t, &|a| patterns.iter().any(|p| p.is_match(a))))
+        }
+        "has_keyword" => select(&|t| t.metadata.keywords.iter().any(|k| values.contains(k))),
+        "has_concept" => {
+            select(&|t| (internal || !is_internal(t)) && values.iter().any(|x| has_concept(t, x)))
+        }
+        "lacks_concepts" => {
+            select(&|t| (internal || !is_internal(t)) && !values.iter().any(|x| has_concept(t, x)))
+        }
+        "has_lifecycle" | "lifecycle" => select(&|t| {
+            t.metadata
+                .lifecycle
+                .as_ref()
+                .is_some_and(|stage| values.contains(stage))
+        }),
+        _ => return Err(format!("uses an unknown selector `{}()`", function)),
+    };
+    if matched.is_empty() {
+        return Err("matches no topics".to_string());
+    }
+    Ok(matched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rd2qmd_core::RdMetadata;
+
+    fn topic(name: &str, aliases: &[&str], keywords: &[&str], concepts: &[&str]) -> TopicInfo {
+        TopicInfo {
+            name: name.to_string(),
+            file: format!("{}.qmd", name),
+            title: format!("Title of {}", name),
+            metadata: RdMetadata {
+                aliases: aliases.iter().map(|s| s.to_string()).collect(),
+                keywords: keywords.iter().map(|s| s.to_string()).collect(),
+                concepts: concepts.iter().map(|s| s.to_string()).collect(),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn index() -> TopicIndex {
+        let mut deprecated = topic("old_fun", &["old_fun"], &[], &[]);
+        deprecated.metadata.lifecycle = Some("deprecated".to_string());
+        TopicIndex {
+            topics: vec![
+                topic("geom_point", &["geom_point"], &[], &["layers"]),
+                topic("geom_line", &["geom_line", "geom_path"], &[], &["layers"]),
+                topic("ggplot", &["ggplot"], &[], &[]),
+                topic("helper", &["helper"], &["internal"], &[]),
+                topic("mpg", &["mpg"], &["datasets"], &[]),
+                deprecated,
+                topic("theme", &["theme"], &[], &[]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_parse_pkgdown_reference() {
+        let yaml = r#"
+url: https://example.org
+reference:
+- title: Plots
+  desc: >
+    Create plots.
+  contents:
+  - ggplot
+  - starts_with("geom_")
+- subtitle: Data
+  contents:
+  - has_keyword("datasets")
+"#;
+        let reference = PkgdownReference::parse(yaml).unwrap();
+        assert_eq!(
+            reference.sections,
+            vec![
+                ReferenceSectionSpec {
+                    title: Some("Plots".to_string()),
+                    desc: Some("Create plots.".to_string()),
+                    contents: vec!["ggplot".to_string(), "starts_with(\"geom_\")".to_string()],
+                    ..Default::default()
+                },
+                ReferenceSectionSpec {
+                    subtitle: Some("Data".to_string()),
+                    contents: vec!["has_keyword(\"datasets\")".to_string()],
+                    ..Default::default()
+                },
+            ]
+        );
+        assert_eq!(PkgdownReference::parse("url: x").unwrap().sections, vec![]);
+    }
+
+    #[test]
+    fn test_reference_index_selectors() {
+        let reference = PkgdownReference {
+            sections: vec![
+                ReferenceSectionSpec {
+                    title: Some("Plots".to_string()),
+                    contents: vec![
+                        "ggplot".to_string(),
+                        "has_concept('layers')".to_string(),
+                        "-geom_path".to_string(),
+                    ],
+                    ..Default::default()
+                },
+                ReferenceSectionSpec {
+                    title: Some("Data".to_string()),
+                    contents: vec![
+                        "has_keyword(\"datasets\")".to_string(),
+                        "matches(\"^he\", internal = TRUE)".to_string(),
+                        "lifecycle(\"deprecated\")".to_string(),
+                        "starts_with(\"zzz\")".to_string(),
+                        "nope".to_string(),
+                        "starts_with(\"geom_\"]".to_string(),
+                        "contains(\"a".to_string(),
+                    ],
+                    ..Default::default()
+                },
+            ],
+        };
+        let index = ReferenceIndex::new(&index(), &reference);
+
+        fn names(section: &ReferenceSection) -> Vec<&str> {
+            section.topics.iter().map(|t| t.name.as_str()).collect()
+        }
+        // `-geom_path` removes the topic with that alias
+        assert_eq!(names(&index.sections[0]), vec!["ggplot", "geom_point"]);
+        assert_eq!(names(&index.sections[1]), vec!["mpg", "helper", "old_fun"]);
+        // Internal topics are never missing
+        assert_eq!(index.missing_topics, vec!["geom_line", "theme"]);
+        assert_eq!(
+            index.problems,
+            vec![
+                "`starts_with(\"zzz\")` matches no topics",
+                "`nope` is not a known topic name or alias",
+                "`starts_with(\"geom_\"]` is not a valid R expression",
+                "`contains(\"a` is not a valid R expression",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reference_index_without_sections() {
+        let index = ReferenceIndex::new(&index(), &PkgdownReference::default());
+        assert_eq!(index.sections.len(), 1);
+        assert_eq!(index.sections[0].topics.len(), 6);
+        assert!(index.missing_topics.is_empty());
+    }
+
+    #[test]
+    fn test_reference_index_to_mdast() {
+        let reference = PkgdownReference {
+            sections: vec![ReferenceSectionSpec {
+                title: Some("Plot layers".to_string()),
+                desc: Some("Geoms.".to_string()),
+                contents: vec!["geom_line".to_string()],
+                ..Default::default()
+            }],
+        };
+        let root = ReferenceIndex::new(&index(), &reference).to_mdast("reference/", true);
+        let qmd = rd2qmd_core::mdast_to_qmd(&root, &Default::default());
+        assert_eq!(
+            qmd.trim_end(),
+            "## Plot layers {#plot-layers}\n\nGeoms.\n\n\
+             [`geom_line`](reference/geom_line.qmd) [`geom_path`](reference/geom_line.qmd)\n\
+             :   Title of geom_line"
+        );
+    }
+}
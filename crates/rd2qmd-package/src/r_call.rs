@@ -0,0 +1,241 @@
+//! Parsing of the R calls in package metadata
+//!
+//! `Authors@R` in DESCRIPTION holds `person()` calls and `_pkgdown.yml` topic
+//! selectors such as `starts_with("geom_")`. Both are read as literal calls with
+//! string arguments; no R code is evaluated.
+
+/// Parse an R expression, such as a call with string arguments
+///
+/// Unbalanced brackets and unterminated strings are skipped over.
+pub(crate) fn parse_r(code: &str) -> RValue {
+    RParser::new(code).parse_value()
+}
+
+/// Parse a single R expression, or fail if it is malformed: unbalanced brackets,
+/// an unterminated string or anything after the expression
+pub(crate) fn parse_r_strict(code: &str) -> Result<RValue, String> {
+    let mut parser = RParser::new(code);
+    let value = parser.parse_value();
+    parser.skip_whitespace();
+    if parser.malformed || !parser.rest.is_empty() {
+        return Err("is not a valid R expression".to_string());
+    }
+    Ok(value)
+}
+
+/// An R expression, as far as DESCRIPTION and `_pkgdown.yml` need it
+#[derive(Debug)]
+pub(crate) enum RValue {
+    /// String literal
+    Str(String),
+    /// Function call with optionally named arguments
+    Call(String, Vec<(Option<String>, RValue)>),
+    /// Name, such as `TRUE` or `NULL`
+    Name(String),
+    /// Anything else, such as numbers or empty arguments
+    Other,
+}
+
+impl RValue {
+    /// Strings of a literal or a `c()` call, with their names
+    pub(crate) fn strings(&self) -> Vec<(Option<String>, String)> {
+        match self {
+            RValue::Str(s) => vec![(None, s.clone())],
+            RValue::Call(name, args) if name == "c" => args
+                .iter()
+                .flat_map(|(key, value)| {
+                    value
+                        .strings()
+                        .into_iter()
+                        .map(move |(inner, s)| (key.clone().or(inner), s))
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Minimal parser for R calls
+struct RParser<'a> {
+    rest: &'a str,
+    /// Whether unbalanced brackets or an unterminated string were skipped
+    malformed: bool,
+}
+
+impl<'a> RParser<'a> {
+    fn new(code: &'a str) -> Self {
+        Self {
+            rest: code,
+            malformed: false,
+        }
+    }
+
+    /// Skip whitespace and comments
+    fn skip_whitespace(&mut self) {
+        loop {
+            self.rest = self.rest.trim_start();
+            if self.rest.starts_with('#') {
+                let end = self.rest.find('\n').unwrap_or(self.rest.len());
+                self.rest = &self.rest[end..];
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.rest = &self.rest[c.len_utf8()..];
+        }
+    }
+
+    fn parse_value(&mut self) -> RValue {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(quote @ ('"' | '\'')) => RValue::Str(self.parse_string(quote)),
+            Some(c) if is_name_char(c) => {
+                let name = self.parse_name();
+                self.skip_whitespace();
+                if self.peek() == Some('(') {
+                    self.bump();
+                    // `utils::person` is `person`
+                    let name = name.rsplit("::").next().unwrap_or(name);
+                    RValue::Call(name.to_string(), self.parse_args())
+                } else {
+                    RValue::Name(name.to_string())
+                }
+            }
+            _ => {
+                self.skip_to_delimiter();
+                RValue::Other
+            }
+        }
+    }
+
+    /// Parse the arguments of a call, after the opening parenthesis
+    fn parse_args(&mut self) -> Vec<(Option<String>, RValue)> {
+        let mut args = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => {
+                    self.malformed = true;
+                    break;
+                }
+                Some(')') => {
+                    self.bump();
+                    break;
+                }
+                _ => {}
+            }
+            let arg = self.parse_arg();
+            args.push(arg);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                    // A trailing empty argument, as in `f(x, )`
+                    self.skip_whitespace();
+                    if self.peek() == Some(')') {
+                        args.push((None, RValue::Other));
+                    }
+                }
+                Some(')') => {}
+                _ => self.skip_to_delimiter(),
+            }
+        }
+        args
+    }
+
+    /// Parse an argument, `name = value` or `value`; empty arguments are `Other`
+    fn parse_arg(&mut self) -> (Option<String>, RValue) {
+        if matches!(self.peek(), Some(',' | ')')) {
+            return (None, RValue::Other);
+        }
+        let start = self.rest;
+        let name = match self.peek() {
+            Some(quote @ ('"' | '\'' | '`')) => Some(self.parse_string(quote)),
+            Some(c) if is_name_char(c) => Some(self.parse_name().to_string()),
+            _ => None,
+        };
+        self.skip_whitespace();
+        if let Some(name) = name
+            && self.rest.starts_with('=')
+            && !self.rest.starts_with("==")
+        {
+            self.bump();
+            return (Some(name), self.parse_value());
+        }
+        self.rest = start;
+        (None, self.parse_value())
+    }
+
+    /// Parse a quoted string, unescaping backslash escapes
+    fn parse_string(&mut self, quote: char) -> String {
+        self.bump();
+        let mut value = String::new();
+        let mut chars = self.rest.char_indices();
+        let mut end = None;
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, c)) => value.push(c),
+                    None => {}
+                },
+                c if c == quote => {
+                    end = Some(i + c.len_utf8());
+                    break;
+                }
+                c => value.push(c),
+            }
+        }
+        if end.is_none() {
+            self.malformed = true;
+        }
+        self.rest = &self.rest[end.unwrap_or(self.rest.len())..];
+        value
+    }
+
+    fn parse_name(&mut self) -> &'a str {
+        let end = self
+            .rest
+            .find(|c| !is_name_char(c))
+            .unwrap_or(self.rest.len());
+        let (name, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        name
+    }
+
+    /// Skip an unsupported expression up to the next `,` or `)` at its level
+    ///
+    /// A stray `]` or `}` is skipped too, so that unbalanced input ends.
+    fn skip_to_delimiter(&mut self) {
+        let mut depth = 0usize;
+        while let Some(c) = self.peek() {
+            match c {
+                '"' | '\'' | '`' => {
+                    self.parse_string(c);
+                    continue;
+                }
+                '(' | '[' | '{' => depth += 1,
+                ')' if depth == 0 => break,
+                ']' | '}' if depth == 0 => self.malformed = true,
+                ')' | ']' | '}' => depth -= 1,
+                ',' if depth == 0 => break,
+                _ => {}
+            }
+            self.bump();
+        }
+    }
+}
+
+/// Characters of R names, including `::`
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '.' | '_' | ':')
+}